@@ -0,0 +1,262 @@
+// ITD ODD Save Manager by andromarces
+
+use crate::backup::common::{OWNER_FILE_NAME, SAVE_DIR_FILE_NAME};
+use crate::backup::{backup_root_for, ensure_backup_root, BackupSettings};
+use crate::config::AppConfig;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Archive entry holding the serialized configuration.
+const CONFIG_ENTRY: &str = "config.json";
+/// Archive directory holding the contents of the backup root.
+const BACKUPS_ENTRY: &str = "backups";
+
+/// Bundles the configuration and every backup (with the index) into a `.tar.gz` archive.
+///
+/// The owner and save path markers are left out since they only mean something on this machine.
+/// Returns the number of backup files written.
+pub fn export_app_state(config: &AppConfig, dest: &Path) -> Result<usize, String> {
+    let file = File::create(dest).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let config_json = serde_json::to_vec_pretty(config).map_err(|e| e.to_string())?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(config_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive
+        .append_data(&mut header, CONFIG_ENTRY, config_json.as_slice())
+        .map_err(|e| e.to_string())?;
+
+    let mut files = 0;
+    if let Some(save_path) = &config.save_path {
+        let backup_root = backup_root_for(Path::new(save_path), &config.backup_settings());
+        if backup_root.is_dir() {
+            files = append_backup_files(&mut archive, &backup_root, Path::new(BACKUPS_ENTRY))?;
+        }
+    }
+
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    Ok(files)
+}
+
+/// Recursively appends the files under `dir` to the archive below `archive_dir`.
+fn append_backup_files<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    dir: &Path,
+    archive_dir: &Path,
+) -> Result<usize, String> {
+    let mut files = 0;
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let archive_path = archive_dir.join(&name);
+        if path.is_dir() {
+            files += append_backup_files(archive, &path, &archive_path)?;
+        } else if name != OWNER_FILE_NAME && name != SAVE_DIR_FILE_NAME {
+            archive
+                .append_path_with_name(&path, &archive_path)
+                .map_err(|e| format!("Failed to archive {:?}: {}", path, e))?;
+            files += 1;
+        }
+    }
+    Ok(files)
+}
+
+/// Restores an archive written by `export_app_state` into `target_save_dir`.
+///
+/// Backups are unpacked into the target's backup root and the returned configuration
+/// has its save path and backup root override remapped to this machine's, taken from
+/// `settings`. Refuses to import over existing backups so nothing on the new machine
+/// is overwritten.
+pub fn import_app_state(
+    src: &Path,
+    target_save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<AppConfig, String> {
+    if !target_save_dir.is_dir() {
+        return Err(format!(
+            "Target save directory does not exist: {:?}",
+            target_save_dir
+        ));
+    }
+    let backup_root = backup_root_for(target_save_dir, settings);
+    let has_backups = fs::read_dir(&backup_root)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if has_backups {
+        return Err("Target save directory already contains backups".to_string());
+    }
+    ensure_backup_root(target_save_dir, settings)?;
+
+    let file = File::open(src).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut config: Option<AppConfig> = None;
+
+    for entry in archive.entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let entry_path = entry.path().map_err(|e| e.to_string())?.into_owned();
+
+        if entry_path == Path::new(CONFIG_ENTRY) {
+            let mut content = String::new();
+            entry
+                .read_to_string(&mut content)
+                .map_err(|e| e.to_string())?;
+            config = Some(
+                serde_json::from_str::<AppConfig>(&content)
+                    .map_err(|e| format!("Invalid configuration in archive: {}", e))?
+                    .migrate(),
+            );
+            continue;
+        }
+
+        let Some(relative) = backup_relative_path(&entry_path) else {
+            log::warn!("Skipping unexpected archive entry {:?}", entry_path);
+            continue;
+        };
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let target = backup_root.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        entry
+            .unpack(&target)
+            .map_err(|e| format!("Failed to extract {:?}: {}", entry_path, e))?;
+    }
+
+    let mut config =
+        config.ok_or_else(|| "Archive does not contain a configuration".to_string())?;
+    config.save_path = Some(target_save_dir.to_string_lossy().to_string());
+    config.backup_root_override = settings
+        .backup_root_override
+        .as_ref()
+        .map(|root| root.to_string_lossy().to_string());
+    Ok(config)
+}
+
+/// Maps an archive path under `backups/` to a safe path relative to the backup root.
+///
+/// Rejects absolute paths and parent components so entries cannot escape the root.
+fn backup_relative_path(entry_path: &Path) -> Option<PathBuf> {
+    let relative = entry_path.strip_prefix(BACKUPS_ENTRY).ok()?;
+    let safe = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    if !safe || relative.as_os_str().is_empty() || relative.ends_with(OWNER_FILE_NAME) {
+        return None;
+    }
+    Some(relative.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::common::BACKUP_DIR_NAME;
+    use crate::backup::{get_backups, perform_backup_for_game_internal, set_backup_note};
+    use crate::backup::{load_index, save_index, BackupOptions, BackupSettings, NamingScheme};
+    use tempfile::tempdir;
+
+    /// Tests a full export on one machine and import on another.
+    #[test]
+    fn export_import_round_trip_preserves_config_and_backups() {
+        let old_machine = tempdir().unwrap();
+        let new_machine = tempdir().unwrap();
+        let old_saves = old_machine.path().join("saves");
+        let new_saves = new_machine.path().join("saves");
+        fs::create_dir_all(&old_saves).unwrap();
+        fs::create_dir_all(&new_saves).unwrap();
+
+        let backup_root = old_saves.join(BACKUP_DIR_NAME);
+        fs::create_dir_all(&backup_root).unwrap();
+        let mut index = load_index(&backup_root);
+        for (game_number, content) in [(0, "slot 0"), (1, "slot 1")] {
+            fs::write(
+                old_saves.join(format!("gamesave_{}.sav", game_number)),
+                content,
+            )
+            .unwrap();
+            perform_backup_for_game_internal(
+                &old_saves,
+                &backup_root,
+                game_number,
+                &mut index,
+                &[],
+                &BackupSettings::default(),
+                BackupOptions {
+                    limit: 100,
+                    ..BackupOptions::default()
+                },
+            )
+            .unwrap()
+            .created()
+            .unwrap();
+        }
+        save_index(&backup_root, &index).unwrap();
+        fs::write(backup_root.join(OWNER_FILE_NAME), "1234").unwrap();
+        let old_backups = get_backups(&old_saves, true, None, &BackupSettings::default()).unwrap();
+        set_backup_note(
+            &old_saves,
+            &old_backups[0].filename,
+            Some("keep".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        let config = AppConfig {
+            save_path: Some(old_saves.to_string_lossy().to_string()),
+            max_backups_per_game: 42,
+            auto_close: true,
+            naming_scheme: NamingScheme::Counter,
+            ..AppConfig::default()
+        };
+        let archive = old_machine.path().join("state.tar.gz");
+        assert_eq!(export_app_state(&config, &archive).unwrap(), 8);
+
+        let imported = import_app_state(&archive, &new_saves, &BackupSettings::default()).unwrap();
+        assert_eq!(
+            imported.save_path,
+            Some(new_saves.to_string_lossy().to_string())
+        );
+        assert_eq!(imported.max_backups_per_game, 42);
+        assert!(imported.auto_close);
+        assert_eq!(imported.naming_scheme, NamingScheme::Counter);
+
+        let new_backups = get_backups(&new_saves, true, None, &BackupSettings::default()).unwrap();
+        assert_eq!(new_backups.len(), old_backups.len());
+        for (old, new) in old_backups.iter().zip(&new_backups) {
+            assert_eq!(old.filename, new.filename);
+            assert_eq!(old.hash, new.hash);
+            assert_eq!(old.id, new.id);
+        }
+        assert_eq!(new_backups[0].note.as_deref(), Some("keep"));
+        assert!(!new_saves
+            .join(BACKUP_DIR_NAME)
+            .join(OWNER_FILE_NAME)
+            .exists());
+
+        // A second import would overwrite the now-present backups, so it is refused.
+        assert!(import_app_state(&archive, &new_saves, &BackupSettings::default()).is_err());
+    }
+
+    /// Tests that archive paths cannot escape the backup root.
+    #[test]
+    fn backup_relative_path_rejects_unsafe_entries() {
+        assert_eq!(
+            backup_relative_path(Path::new("backups/Game 1 - #0001/gamesave_0.sav")),
+            Some(PathBuf::from("Game 1 - #0001/gamesave_0.sav"))
+        );
+        assert_eq!(backup_relative_path(Path::new("backups/../evil")), None);
+        assert_eq!(backup_relative_path(Path::new("other/file")), None);
+        assert_eq!(backup_relative_path(Path::new("backups")), None);
+        assert_eq!(backup_relative_path(Path::new("backups/.owner")), None);
+    }
+}
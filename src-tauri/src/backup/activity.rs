@@ -0,0 +1,93 @@
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of backups taken on one calendar day.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DayCount {
+    /// The local calendar day (YYYY-MM-DD).
+    pub day: String,
+    /// How many backups were taken that day.
+    pub count: usize,
+}
+
+/// Oldest and newest backup timestamps of one game slot.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TimeBounds {
+    /// The game number (0-based).
+    pub game_number: u32,
+    /// Timestamp of the oldest backup (ISO 8601).
+    pub oldest: String,
+    /// Timestamp of the newest backup (ISO 8601).
+    pub newest: String,
+    /// How many backups the slot has.
+    pub count: usize,
+}
+
+/// Returns the oldest and newest backup timestamps for every slot that has backups.
+///
+/// Relies on `get_backups` listing newest first, so the first backup seen for a slot
+/// is its newest and the last is its oldest. Results are ordered by game number.
+pub fn get_backup_time_bounds(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<TimeBounds>, String> {
+    let mut bounds: BTreeMap<u32, TimeBounds> = BTreeMap::new();
+    for backup in get_backups(save_dir, false, None, settings)? {
+        match bounds.entry(backup.game_number) {
+            Entry::Vacant(entry) => {
+                entry.insert(TimeBounds {
+                    game_number: backup.game_number,
+                    oldest: backup.modified.clone(),
+                    newest: backup.modified,
+                    count: 1,
+                });
+            }
+            Entry::Occupied(mut entry) => {
+                let slot = entry.get_mut();
+                slot.oldest = backup.modified;
+                slot.count += 1;
+            }
+        }
+    }
+    Ok(bounds.into_values().collect())
+}
+
+/// Buckets backups by local calendar day for a "backups per day" chart.
+///
+/// Days are derived from each backup's local timestamp, so a backup always lands on
+/// the day shown in its folder name. Days without backups are omitted and the result
+/// is ordered oldest first.
+pub fn backup_activity_histogram(
+    save_dir: &Path,
+    game_number: Option<u32>,
+    settings: &BackupSettings,
+) -> Result<Vec<DayCount>, String> {
+    let mut days: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for backup in get_backups(save_dir, false, game_number, settings)? {
+        match DateTime::parse_from_rfc3339(&backup.modified) {
+            Ok(dt) => {
+                *days
+                    .entry(dt.with_timezone(&Local).date_naive())
+                    .or_default() += 1
+            }
+            Err(e) => log::warn!(
+                "Skipping backup {} in activity histogram: {}",
+                backup.filename,
+                e
+            ),
+        }
+    }
+
+    Ok(days
+        .into_iter()
+        .map(|(day, count)| DayCount {
+            day: day.format("%Y-%m-%d").to_string(),
+            count,
+        })
+        .collect())
+}
@@ -0,0 +1,92 @@
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// File format of an exported backup catalog.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogFormat {
+    Json,
+    Csv,
+}
+
+/// One backup as written to the catalog.
+#[derive(Debug, Serialize)]
+struct CatalogEntry {
+    filename: String,
+    game_number: u32,
+    size: u64,
+    modified: String,
+    locked: bool,
+    note: Option<String>,
+    hash: String,
+}
+
+/// Column names of the CSV catalog, in `CatalogEntry` field order.
+const CSV_HEADER: &str = "filename,game_number,size,modified,locked,note,hash";
+
+/// Writes metadata of every backup of a save directory to `output_path`.
+///
+/// Backups are listed newest first. Returns the number of backups written.
+pub fn export_backup_catalog(
+    save_dir: &Path,
+    format: CatalogFormat,
+    output_path: &Path,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    let entries: Vec<CatalogEntry> = get_backups(save_dir, true, None, settings)?
+        .into_iter()
+        .map(|b| CatalogEntry {
+            filename: b.filename,
+            game_number: b.game_number,
+            size: b.size,
+            modified: b.modified,
+            locked: b.locked,
+            note: b.note,
+            hash: b.hash,
+        })
+        .collect();
+
+    let content = match format {
+        CatalogFormat::Json => serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?,
+        CatalogFormat::Csv => catalog_csv(&entries),
+    };
+    fs::write(output_path, content).map_err(|e| e.to_string())?;
+
+    log::info!(
+        "Exported catalog of {} backup(s) to {:?}",
+        entries.len(),
+        output_path
+    );
+    Ok(entries.len())
+}
+
+/// Renders catalog entries as CSV with a header row.
+fn catalog_csv(entries: &[CatalogEntry]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for entry in entries {
+        let fields = [
+            csv_field(&entry.filename),
+            entry.game_number.to_string(),
+            entry.size.to_string(),
+            csv_field(&entry.modified),
+            entry.locked.to_string(),
+            csv_field(entry.note.as_deref().unwrap_or_default()),
+            csv_field(&entry.hash),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,12 +1,91 @@
+use super::common::{
+    ARCHIVED_FILE_NAME, INDEX_FILE_NAME, LOCKED_FILE_NAME, QUARANTINE_DIR_NAME, TRASH_DIR_NAME,
+};
+use super::compression::find_stored_save_file;
 use super::data::BackupInfo;
-use super::index::{BackupIndex, BackupStore};
-use super::listing::get_backups;
-use std::collections::HashMap;
+use super::delta::release_delta_links;
+use super::fileutil::folder_size;
+use super::index::{backup_root_for, load_index, save_index, BackupIndex, BackupStore};
+use super::listing::{backup_info_from_folder, get_backups};
+use super::manifest::read_manifest;
+use super::settings::BackupSettings;
+use super::trash::{empty_trash, move_to_trash};
+use crate::filename_utils;
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-/// Deletes a specific backup folder.
-pub fn delete_backup_folder(backup_folder_path: &Path) -> Result<(), String> {
+/// Returns true when no other backup folder for the same slot exists beside this one.
+fn is_last_backup_of_slot(backup_folder_path: &Path) -> bool {
+    let (Some(folder_name), Some(backup_root)) = (
+        backup_folder_path.file_name().and_then(|n| n.to_str()),
+        backup_folder_path.parent(),
+    ) else {
+        return false;
+    };
+    let Some(info) = filename_utils::parse_backup_folder_name(folder_name) else {
+        return false;
+    };
+    let Ok(entries) = fs::read_dir(backup_root) else {
+        return false;
+    };
+
+    !entries.flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        name != folder_name
+            && entry.path().is_dir()
+            && filename_utils::parse_backup_folder_name(&name)
+                .is_some_and(|other| other.game_number == info.game_number)
+    })
+}
+
+/// Deletes a specific backup folder and its metadata in the index.
+///
+/// The folder is moved to the trash so the delete can be undone until the trash is
+/// emptied. While the "keep at least one" safety is on, refuses to delete the only
+/// remaining backup of a slot unless `force` is set. The folder's notes, tags,
+/// session membership, and dedup entry are pruned so nothing is left orphaned.
+pub fn delete_backup_folder(
+    backup_folder_path: &Path,
+    force: bool,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    let (Some(backup_root), Some(folder_name)) =
+        (backup_folder_path.parent(), backup_folder_path.file_name())
+    else {
+        return Err("Backup folder path is invalid".to_string());
+    };
+    let has_index = backup_root.join(INDEX_FILE_NAME).exists();
+    let mut index = if has_index {
+        load_index(backup_root)
+    } else {
+        BackupIndex::default()
+    };
+    let id = read_manifest(backup_folder_path).map(|m| m.id);
+    remove_backup_folder(
+        backup_folder_path,
+        &index,
+        !force && settings.never_delete_last_backup,
+    )?;
+
+    if has_index {
+        index.prune_deleted(&folder_name.to_string_lossy(), id.as_deref());
+        save_index(backup_root, &index)?;
+    }
+    Ok(())
+}
+
+/// Moves a backup folder to the trash without touching the index.
+///
+/// The folder's metadata in `index` is recorded with it for a later restore. With
+/// `keep_last` set, refuses to remove the only remaining backup of a slot.
+fn remove_backup_folder(
+    backup_folder_path: &Path,
+    index: &BackupIndex,
+    keep_last: bool,
+) -> Result<(), String> {
     if !backup_folder_path.exists() {
         return Err("Backup folder does not exist".to_string());
     }
@@ -16,22 +95,35 @@ pub fn delete_backup_folder(backup_folder_path: &Path) -> Result<(), String> {
         return Err("Path is not a directory".to_string());
     }
 
-    fs::remove_dir_all(backup_folder_path).map_err(|e| e.to_string())?;
+    if keep_last && is_last_backup_of_slot(backup_folder_path) {
+        return Err("Refusing to delete the last remaining backup of this game".to_string());
+    }
+
+    move_to_trash(backup_folder_path, index)?;
     log::info!("Deleted backup folder: {:?}", backup_folder_path);
 
     Ok(())
 }
 
-/// Batch deletes backups based on criteria.
-pub fn delete_backups_batch(
+/// Reports batch delete progress as `(deleted_so_far, total_candidates)`.
+pub type BatchDeleteProgress = Arc<dyn Fn(usize, usize) + Send + Sync + 'static>;
+
+/// Selects the backups a batch delete with these criteria removes.
+///
+/// The newest backup of each slot is always kept while the "keep at least one"
+/// safety is on, unless `force` is set. Archived backups are never selected, even
+/// with `delete_locked`, and do not count as a slot's newest backup.
+fn select_batch_delete_targets(
     save_dir: &Path,
     target_games: &[u32],
     keep_latest: bool,
     delete_locked: bool,
-) -> Result<usize, String> {
-    let mut backups = get_backups(save_dir, false, None)?;
-    let mut deleted_count = 0;
-    let mut store_opt = BackupStore::load_if_exists(save_dir)?;
+    force: bool,
+    settings: &BackupSettings,
+) -> Result<Vec<BackupInfo>, String> {
+    let keep_latest = keep_latest || (!force && settings.never_delete_last_backup);
+    let mut backups = get_backups(save_dir, false, None, settings)?;
+    backups.retain(|backup| !backup.archived);
 
     // Group backups by game number
     // backups are already sorted by modified desc (newest first)
@@ -43,6 +135,7 @@ pub fn delete_backups_batch(
             .push(backup);
     }
 
+    let mut targets = Vec::new();
     for &game_number in target_games {
         if let Some(game_list) = games_backups.get(&game_number) {
             let candidates = if keep_latest {
@@ -57,21 +150,82 @@ pub fn delete_backups_batch(
                 &game_list[..]
             };
 
-            for backup in candidates {
-                if backup.locked && !delete_locked {
-                    continue;
-                }
+            targets.extend(
+                candidates
+                    .iter()
+                    .filter(|backup| !backup.locked || delete_locked)
+                    .cloned(),
+            );
+        }
+    }
+    Ok(targets)
+}
 
-                let path = PathBuf::from(&backup.path);
-                if let Err(e) = delete_backup_folder(&path) {
-                    log::error!("Failed to delete backup {:?}: {}", path, e);
-                } else {
-                    if let Some(ref mut store) = store_opt {
-                        store.index.prune_deleted(&backup.filename);
-                    }
-                    deleted_count += 1;
-                }
+/// Lists the backups `delete_backups_batch` would remove with the same arguments,
+/// without deleting anything.
+pub fn preview_delete_backups_batch(
+    save_dir: &Path,
+    target_games: &[u32],
+    keep_latest: bool,
+    delete_locked: bool,
+    force: bool,
+    settings: &BackupSettings,
+) -> Result<Vec<BackupInfo>, String> {
+    select_batch_delete_targets(
+        save_dir,
+        target_games,
+        keep_latest,
+        delete_locked,
+        force,
+        settings,
+    )
+}
+
+/// Batch deletes backups based on criteria.
+///
+/// The newest backup of each slot is always kept while the "keep at least one"
+/// safety is on, unless `force` is set.
+///
+/// * `on_progress` - Optional callback invoked after each candidate folder is processed.
+pub fn delete_backups_batch(
+    save_dir: &Path,
+    target_games: &[u32],
+    keep_latest: bool,
+    delete_locked: bool,
+    force: bool,
+    settings: &BackupSettings,
+    on_progress: Option<BatchDeleteProgress>,
+) -> Result<usize, String> {
+    let targets = select_batch_delete_targets(
+        save_dir,
+        target_games,
+        keep_latest,
+        delete_locked,
+        force,
+        settings,
+    )?;
+    let mut deleted_count = 0;
+    let mut store_opt = BackupStore::load_if_exists(save_dir, settings)?;
+
+    let total = targets.len();
+    let empty_index = BackupIndex::default();
+    for backup in targets {
+        let path = PathBuf::from(&backup.path);
+        let index = store_opt
+            .as_ref()
+            .map_or(&empty_index, |store| &store.index);
+        if let Err(e) = remove_backup_folder(&path, index, false) {
+            log::error!("Failed to delete backup {:?}: {}", path, e);
+        } else {
+            if let Some(ref mut store) = store_opt {
+                store
+                    .index
+                    .prune_deleted(&backup.filename, backup.id.as_deref());
             }
+            deleted_count += 1;
+        }
+        if let Some(cb) = &on_progress {
+            cb(deleted_count, total);
         }
     }
 
@@ -82,12 +236,168 @@ pub fn delete_backups_batch(
     Ok(deleted_count)
 }
 
+/// Deletes the oldest backups across all games until the backup root fits in `max_bytes`.
+///
+/// The size counts every file under the backup root, including the index, archived
+/// backups, the trash, and quarantined folders. The trash is emptied first. Locked,
+/// archived and favorite backups are never deleted, and neither is `new_backup` or,
+/// with `keep_newest` set, the newest unarchived backup of each slot, so the cap may
+/// stay exceeded. Returns the number of backups deleted, not counting emptied trash.
+pub(crate) fn prune_by_total_size(
+    save_dir: &Path,
+    max_bytes: u64,
+    index: &mut BackupIndex,
+    new_backup: &Path,
+    keep_newest: bool,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    let backup_root = backup_root_for(save_dir, settings);
+    if !backup_root.is_dir() {
+        return Ok(0);
+    }
+    let mut total = folder_size(&backup_root)?;
+    if total <= max_bytes {
+        return Ok(0);
+    }
+    if backup_root.join(TRASH_DIR_NAME).is_dir() {
+        empty_trash(save_dir, settings)?;
+        total = folder_size(&backup_root)?;
+        if total <= max_bytes {
+            return Ok(0);
+        }
+    }
+
+    let backups = get_backups(save_dir, false, None, settings)?;
+    let mut sizes = HashMap::with_capacity(backups.len());
+    for backup in &backups {
+        sizes.insert(backup.path.clone(), folder_size(Path::new(&backup.path))?);
+    }
+
+    // backups is sorted newest first, so the first match per slot is its newest.
+    let mut newest_per_slot = HashMap::new();
+    for backup in backups.iter().filter(|backup| !backup.archived) {
+        newest_per_slot
+            .entry(backup.game_number)
+            .or_insert(backup.path.as_str());
+    }
+
+    let mut deleted = 0;
+    for backup in backups.iter().rev() {
+        if total <= max_bytes {
+            break;
+        }
+        let path = PathBuf::from(&backup.path);
+        if backup.locked
+            || backup.archived
+            || index.is_favorite(&backup.filename, backup.id.as_deref())
+            || path == new_backup
+            || (keep_newest
+                && newest_per_slot.get(&backup.game_number) == Some(&backup.path.as_str()))
+        {
+            continue;
+        }
+        // Deleted outright: trashing would keep the bytes under the root the cap counts.
+        release_delta_links(&path, false)?;
+        fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+        index.prune_deleted(&backup.filename, backup.id.as_deref());
+        total = total.saturating_sub(sizes[&backup.path]);
+        deleted += 1;
+    }
+
+    if deleted > 0 {
+        log::info!(
+            "Deleted {} old backup(s) to stay under the {} byte storage cap",
+            deleted,
+            max_bytes
+        );
+    }
+    Ok(deleted)
+}
+
+/// Applies the configured age limit and then the count limit to a slot's backups.
+///
+/// Age runs first so the count only considers backups young enough to keep.
+pub(crate) fn enforce_retention(
+    game_number: u32,
+    limit: usize,
+    all_backups: &[BackupInfo],
+    index: &mut BackupIndex,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    let keep_newest = settings.never_delete_last_backup;
+    let Some(max_age_days) = settings.max_backup_age_days else {
+        return enforce_backup_limit(game_number, limit, all_backups, index, keep_newest);
+    };
+    let remaining = prune_old_backups(
+        game_number,
+        max_age_days,
+        all_backups,
+        index,
+        Local::now(),
+        keep_newest,
+    )?;
+    enforce_backup_limit(game_number, limit, &remaining, index, keep_newest)
+}
+
+/// Deletes a slot's unlocked backups whose folder-name timestamp is older than `max_age_days`.
+///
+/// Counter-named folders carry no timestamp and are never aged out. With `keep_newest`
+/// set, the newest unarchived backup of the slot survives regardless of age.
+/// Returns the backups that remain, keeping the input's newest-first order.
+pub(crate) fn prune_old_backups(
+    game_number: u32,
+    max_age_days: u32,
+    all_backups: &[BackupInfo],
+    index: &mut BackupIndex,
+    now: DateTime<Local>,
+    keep_newest: bool,
+) -> Result<Vec<BackupInfo>, String> {
+    let cutoff = now - chrono::Duration::days(i64::from(max_age_days));
+    // all_backups is sorted newest first, so the first match is the newest.
+    let newest = all_backups
+        .iter()
+        .find(|b| b.game_number == game_number && !b.archived)
+        .map(|b| b.path.clone());
+
+    let mut remaining = Vec::with_capacity(all_backups.len());
+    for backup in all_backups {
+        let expired = backup.game_number == game_number
+            && !backup.locked
+            && !(keep_newest && Some(&backup.path) == newest.as_ref())
+            && filename_utils::parse_backup_folder_name(&backup.filename)
+                .and_then(|info| info.timestamp)
+                .is_some_and(|timestamp| timestamp < cutoff);
+        if !expired {
+            remaining.push(backup.clone());
+            continue;
+        }
+
+        let path = PathBuf::from(&backup.path);
+        if path.exists() {
+            log::info!(
+                "Deleting backup {} older than {} days",
+                backup.filename,
+                max_age_days
+            );
+            release_delta_links(&path, false)?;
+            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+            index.prune_deleted(&backup.filename, backup.id.as_deref());
+        }
+    }
+    Ok(remaining)
+}
+
 /// Enforces the backup limit for a specific game, pruning the index for any deleted backups.
+///
+/// Archived backups neither count toward the limit nor as the slot's newest backup.
+/// With `keep_newest` set, the newest existing backup of the slot is never evicted,
+/// even when the limit would otherwise remove it.
 pub(crate) fn enforce_backup_limit(
     game_number: u32,
     limit: usize,
     all_backups: &[BackupInfo],
     index: &mut BackupIndex,
+    keep_newest: bool,
 ) -> Result<(), String> {
     // 0 means no limit
     if limit == 0 {
@@ -112,14 +422,177 @@ pub(crate) fn enforce_backup_limit(
                 game_number
             );
 
+            // all_backups is sorted newest first, so the first match is the newest.
+            let newest = all_backups
+                .iter()
+                .find(|b| b.game_number == game_number && !b.archived)
+                .map(|b| b.path.clone());
+
             for backup in to_delete {
+                if keep_newest && Some(&backup.path) == newest.as_ref() {
+                    continue;
+                }
                 let path = PathBuf::from(&backup.path);
                 if path.exists() {
+                    release_delta_links(&path, false)?;
                     fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
-                    index.prune_deleted(&backup.filename);
+                    index.prune_deleted(&backup.filename, backup.id.as_deref());
                 }
             }
         }
     }
     Ok(())
 }
+
+/// Lists `.locked` markers in folders under the backup root that are not valid backups.
+///
+/// A marker is dangling when its folder name does not follow the backup naming
+/// contract or the folder no longer holds its main save file.
+pub fn find_dangling_locks(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<String>, String> {
+    let backup_root = backup_root_for(save_dir, settings);
+    if !backup_root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut dangling = Vec::new();
+    for entry in fs::read_dir(&backup_root).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let lock_path = path.join(LOCKED_FILE_NAME);
+        if !path.is_dir() || !lock_path.exists() {
+            continue;
+        }
+        let folder_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if backup_info_from_folder(&path, &folder_name, save_dir, false, None)?.is_none() {
+            dangling.push(lock_path.to_string_lossy().to_string());
+        }
+    }
+    dangling.sort();
+    Ok(dangling)
+}
+
+/// Removes every dangling `.locked` marker, returning how many were deleted.
+pub fn remove_dangling_locks(save_dir: &Path, settings: &BackupSettings) -> Result<usize, String> {
+    let dangling = find_dangling_locks(save_dir, settings)?;
+    for lock_path in &dangling {
+        fs::remove_file(lock_path).map_err(|e| e.to_string())?;
+        log::info!("Removed dangling lock marker: {}", lock_path);
+    }
+    Ok(dangling.len())
+}
+
+/// Lists backup folders that follow the naming contract but lack their main save file.
+///
+/// Listings skip these folders, so they stay on disk unseen. Returns folder names, sorted.
+pub fn find_incomplete_backups(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<String>, String> {
+    let backup_root = backup_root_for(save_dir, settings);
+    if !backup_root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut incomplete = Vec::new();
+    for entry in fs::read_dir(&backup_root).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let folder_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some(info) = filename_utils::parse_backup_folder_name(&folder_name) else {
+            continue;
+        };
+        let main_filename = format!("gamesave_{}.sav", info.game_number);
+        if find_stored_save_file(&path, &main_filename).is_none() {
+            incomplete.push(folder_name);
+        }
+    }
+    incomplete.sort();
+    Ok(incomplete)
+}
+
+/// Deletes incomplete backup folders and their index entries, returning how many were removed.
+///
+/// Locked and archived folders are kept; clear the mark first to remove them.
+pub fn remove_incomplete_backups(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    let incomplete = find_incomplete_backups(save_dir, settings)?;
+    if incomplete.is_empty() {
+        return Ok(0);
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut removed = 0;
+    for folder_name in &incomplete {
+        let path = store.root.join(folder_name);
+        if path.join(LOCKED_FILE_NAME).exists() || path.join(ARCHIVED_FILE_NAME).exists() {
+            log::info!("Keeping locked incomplete backup: {}", folder_name);
+            continue;
+        }
+        let id = read_manifest(&path).map(|m| m.id);
+        release_delta_links(&path, false)?;
+        fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+        store.index.prune_deleted(folder_name, id.as_deref());
+        log::info!("Removed incomplete backup: {}", folder_name);
+        removed += 1;
+    }
+    store.save()?;
+    Ok(removed)
+}
+
+/// Removes index entries for backup folders that are gone from disk.
+///
+/// Covers notes, tags, favorites, and session members of folders deleted by hand,
+/// and slot entries whose last backup is missing. Quarantined backups keep their
+/// annotations so releasing one restores it intact. The index is only written when
+/// something was removed. Returns the number of entries removed.
+pub fn compact_index(save_dir: &Path, settings: &BackupSettings) -> Result<usize, String> {
+    let Some(mut store) = BackupStore::load_if_exists(save_dir, settings)? else {
+        return Ok(0);
+    };
+
+    let mut kept = HashSet::new();
+    let mut listed = HashSet::new();
+    let quarantine_dir = store.root.join(QUARANTINE_DIR_NAME);
+    for (dir, is_listed) in [
+        (store.root.as_path(), true),
+        (quarantine_dir.as_path(), false),
+    ] {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let folder_name = entry.file_name().to_string_lossy().to_string();
+            if !path.is_dir() || folder_name == TRASH_DIR_NAME || folder_name == QUARANTINE_DIR_NAME
+            {
+                continue;
+            }
+            if let Some(manifest) = read_manifest(&path) {
+                kept.insert(manifest.id);
+            }
+            if is_listed {
+                listed.insert(folder_name.clone());
+            }
+            kept.insert(folder_name);
+        }
+    }
+
+    let removed = store.index.compact(&kept, &listed);
+    if removed > 0 {
+        store.save()?;
+        log::info!("Removed {} stale index entries", removed);
+    }
+    Ok(removed)
+}
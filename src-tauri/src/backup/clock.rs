@@ -0,0 +1,74 @@
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::path::Path;
+
+/// How far the clock may trail the newest backup before it is reported as skewed.
+pub const CLOCK_SKEW_THRESHOLD_SECS: i64 = 5 * 60;
+
+/// Describes a detected backward clock jump relative to the newest backup.
+#[derive(Debug, Serialize, Clone)]
+pub struct ClockSkewWarning {
+    /// Folder name of the newest backup.
+    pub newest_backup: String,
+    /// Parsed timestamp of the newest backup (ISO 8601).
+    pub newest_timestamp: String,
+    /// The current system time (ISO 8601).
+    pub now: String,
+    /// How many seconds the current time trails the newest backup.
+    pub skew_seconds: i64,
+}
+
+/// Returns the skew in seconds when `now` trails `newest` by more than the threshold.
+pub(crate) fn detect_clock_skew(
+    newest: DateTime<Local>,
+    now: DateTime<Local>,
+    threshold_secs: i64,
+) -> Option<i64> {
+    let skew = (newest - now).num_seconds();
+    if skew > threshold_secs {
+        Some(skew)
+    } else {
+        None
+    }
+}
+
+/// Compares the newest backup timestamp against the current time.
+///
+/// Returns a warning when the system clock appears to have jumped backward, which
+/// would make new backups sort before older ones and confuse `keep_latest` logic.
+pub fn check_clock_skew(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Option<ClockSkewWarning>, String> {
+    let newest = get_backups(save_dir, false, None, settings)?
+        .into_iter()
+        .filter_map(|b| {
+            DateTime::parse_from_rfc3339(&b.modified)
+                .ok()
+                .map(|dt| (b.filename, dt.with_timezone(&Local)))
+        })
+        .max_by_key(|(_, timestamp)| *timestamp);
+
+    let Some((folder_name, timestamp)) = newest else {
+        return Ok(None);
+    };
+
+    let now = Local::now();
+    Ok(
+        detect_clock_skew(timestamp, now, CLOCK_SKEW_THRESHOLD_SECS).map(|skew_seconds| {
+            log::warn!(
+                "System clock trails newest backup {} by {}s",
+                folder_name,
+                skew_seconds
+            );
+            ClockSkewWarning {
+                newest_backup: folder_name,
+                newest_timestamp: timestamp.to_rfc3339(),
+                now: now.to_rfc3339(),
+                skew_seconds,
+            }
+        }),
+    )
+}
@@ -1,4 +1,18 @@
+pub const ARCHIVED_FILE_NAME: &str = ".archived";
 pub const BACKUP_DIR_NAME: &str = ".backups";
+pub const DELTA_BASE_FILE_NAME: &str = "delta_base.txt";
 pub const HASH_FILE_NAME: &str = ".hash";
 pub const INDEX_FILE_NAME: &str = "index.json";
 pub const LOCKED_FILE_NAME: &str = ".locked";
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+pub const NOTE_FILE_NAME: &str = "note.txt";
+pub const OWNER_FILE_NAME: &str = ".owner";
+pub const QUARANTINE_DIR_NAME: &str = ".quarantine";
+pub const QUARANTINE_FILE_NAME: &str = "quarantine.json";
+pub const SAVE_DIR_FILE_NAME: &str = ".save_dir";
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+pub const TRASH_DIR_NAME: &str = ".trash";
+pub const TRASH_FILE_NAME: &str = "trash.json";
+
+/// Error prefix reported when the configured save path is a file instead of a directory.
+pub const SAVE_PATH_NOT_A_DIRECTORY: &str = "SavePathNotADirectory";
@@ -0,0 +1,239 @@
+use super::delta::{delta_target_len, stored_content, DELTA_SUFFIX};
+use super::fileutil::DEFAULT_COPY_BUFFER_SIZE;
+use super::hashing::{calculate_hash, calculate_hash_reader};
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Suffix appended to save files stored gzip-compressed inside a backup folder.
+pub const COMPRESSED_SUFFIX: &str = ".gz";
+
+/// How a save file is stored inside a backup folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StoredForm {
+    /// A plain copy of the save.
+    Raw,
+    /// A gzip-compressed copy.
+    Compressed,
+    /// A delta against the same file in the backup named by the folder's base marker.
+    Delta,
+}
+
+/// A save file stored inside a backup folder, in raw, compressed, or delta form.
+#[derive(Debug, Clone)]
+pub(crate) struct StoredSaveFile {
+    /// Path of the stored file on disk.
+    pub(crate) path: PathBuf,
+    /// The original save file name (e.g., "gamesave_0.sav"), without storage suffix.
+    pub(crate) file_name: String,
+    /// How the stored file encodes the original content.
+    pub(crate) form: StoredForm,
+}
+
+/// Classifies a file in a backup folder as a stored save file, if it is one.
+pub(crate) fn stored_save_file(path: &Path) -> Option<StoredSaveFile> {
+    let name = path.file_name()?.to_str()?;
+    let (file_name, form) = if let Some(stripped) = name.strip_suffix(COMPRESSED_SUFFIX) {
+        (stripped, StoredForm::Compressed)
+    } else if let Some(stripped) = name.strip_suffix(DELTA_SUFFIX) {
+        (stripped, StoredForm::Delta)
+    } else {
+        (name, StoredForm::Raw)
+    };
+    filename_utils::parse_filename(file_name)?;
+    Some(StoredSaveFile {
+        path: path.to_path_buf(),
+        file_name: file_name.to_string(),
+        form,
+    })
+}
+
+/// Lists the stored save files in a backup folder, sorted by original name.
+pub(crate) fn list_stored_save_files(folder: &Path) -> Result<Vec<StoredSaveFile>, String> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(folder).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() {
+            if let Some(stored) = stored_save_file(&path) {
+                files.push(stored);
+            }
+        }
+    }
+    files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(files)
+}
+
+/// Locates a save file inside a backup folder, preferring the raw form.
+pub(crate) fn find_stored_save_file(folder: &Path, file_name: &str) -> Option<StoredSaveFile> {
+    let raw = folder.join(file_name);
+    if raw.is_file() {
+        return stored_save_file(&raw);
+    }
+    [COMPRESSED_SUFFIX, DELTA_SUFFIX]
+        .iter()
+        .map(|suffix| folder.join(format!("{}{}", file_name, suffix)))
+        .find(|path| path.is_file())
+        .and_then(|path| stored_save_file(&path))
+}
+
+impl StoredSaveFile {
+    /// Returns the uncompressed size of the stored file in bytes.
+    ///
+    /// For compressed files this reads the gzip trailer, which records the size modulo 4 GiB;
+    /// for deltas, the length recorded in the delta header.
+    pub(crate) fn original_size(&self) -> Result<u64, String> {
+        match self.form {
+            StoredForm::Raw => {
+                return fs::metadata(&self.path)
+                    .map(|m| m.len())
+                    .map_err(|e| e.to_string())
+            }
+            StoredForm::Delta => return delta_target_len(&self.path),
+            StoredForm::Compressed => {}
+        }
+        let mut file = fs::File::open(&self.path).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::End(-4)).map_err(|e| e.to_string())?;
+        let mut trailer = [0_u8; 4];
+        file.read_exact(&mut trailer).map_err(|e| e.to_string())?;
+        Ok(u64::from(u32::from_le_bytes(trailer)))
+    }
+
+    /// Calculates the hash of the original (uncompressed) content.
+    pub(crate) fn content_hash(&self, settings: &BackupSettings) -> Result<String, String> {
+        match self.form {
+            StoredForm::Raw => calculate_hash(&self.path, settings),
+            StoredForm::Compressed | StoredForm::Delta => {
+                calculate_hash_reader(self.open_content()?, settings)
+            }
+        }
+    }
+
+    /// Opens a buffered reader over the original (uncompressed) content.
+    ///
+    /// Deltas are reconstructed in memory from their chain first.
+    pub(crate) fn open_content(&self) -> Result<Box<dyn Read>, String> {
+        if self.form == StoredForm::Delta {
+            return Ok(Box::new(Cursor::new(stored_content(self, 0)?)));
+        }
+        let file = fs::File::open(&self.path).map_err(|e| e.to_string())?;
+        let reader = BufReader::with_capacity(DEFAULT_COPY_BUFFER_SIZE, file);
+        if self.form == StoredForm::Compressed {
+            Ok(Box::new(GzDecoder::new(reader)))
+        } else {
+            Ok(Box::new(reader))
+        }
+    }
+
+    /// Writes the original (uncompressed) content to `dst`.
+    pub(crate) fn extract_to(&self, dst: &Path, buffer_size: usize) -> Result<(), String> {
+        match self.form {
+            StoredForm::Raw => super::fileutil::copy_file(&self.path, dst, buffer_size).map(|_| ()),
+            StoredForm::Compressed => decompress_file(&self.path, dst, buffer_size),
+            StoredForm::Delta => {
+                fs::write(dst, stored_content(self, 0)?).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// Compresses `src` into a gzip file at `dst`.
+pub(crate) fn compress_file(src: &Path, dst: &Path, buffer_size: usize) -> Result<(), String> {
+    let reader = fs::File::open(src).map_err(|e| e.to_string())?;
+    let writer = fs::File::create(dst).map_err(|e| e.to_string())?;
+    let mut reader = BufReader::with_capacity(buffer_size, reader);
+    let mut encoder = GzEncoder::new(
+        BufWriter::with_capacity(buffer_size, writer),
+        Compression::default(),
+    );
+    io::copy(&mut reader, &mut encoder).map_err(|e| e.to_string())?;
+    encoder
+        .finish()
+        .and_then(|mut w| w.flush())
+        .map_err(|e| e.to_string())
+}
+
+/// Decompresses the gzip file at `src` into `dst`.
+pub(crate) fn decompress_file(src: &Path, dst: &Path, buffer_size: usize) -> Result<(), String> {
+    let reader = fs::File::open(src).map_err(|e| e.to_string())?;
+    let writer = fs::File::create(dst).map_err(|e| e.to_string())?;
+    let mut decoder = GzDecoder::new(BufReader::with_capacity(buffer_size, reader));
+    let mut writer = BufWriter::with_capacity(buffer_size, writer);
+    io::copy(&mut decoder, &mut writer).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Converts a backup folder's save files to compressed storage in place.
+///
+/// The `.hash`, lock marker, and index metadata are untouched because the folder
+/// name and original content do not change. Already-compressed files and deltas
+/// are skipped.
+pub fn compress_backup(backup_path: &Path, settings: &BackupSettings) -> Result<(), String> {
+    convert_backup(backup_path, true, settings.copy_buffer_size())
+}
+
+/// Converts a backup folder's save files back to raw storage in place.
+pub fn decompress_backup(backup_path: &Path, settings: &BackupSettings) -> Result<(), String> {
+    convert_backup(backup_path, false, settings.copy_buffer_size())
+}
+
+/// Rewrites every stored save file in the requested form.
+///
+/// All new files are written before any original is removed, so a failure part way
+/// leaves the original form intact and the partial output is cleaned up.
+fn convert_backup(backup_path: &Path, compress: bool, buffer_size: usize) -> Result<(), String> {
+    if !backup_path.is_dir() {
+        return Err("Backup folder does not exist".to_string());
+    }
+
+    let from = if compress {
+        StoredForm::Raw
+    } else {
+        StoredForm::Compressed
+    };
+    let to_convert: Vec<StoredSaveFile> = list_stored_save_files(backup_path)?
+        .into_iter()
+        .filter(|f| f.form == from)
+        .collect();
+
+    let mut written = Vec::new();
+    for stored in &to_convert {
+        let target = if compress {
+            backup_path.join(format!("{}{}", stored.file_name, COMPRESSED_SUFFIX))
+        } else {
+            backup_path.join(&stored.file_name)
+        };
+        let result = if compress {
+            compress_file(&stored.path, &target, buffer_size)
+        } else {
+            decompress_file(&stored.path, &target, buffer_size)
+        };
+        if let Err(e) = result {
+            for path in written.iter().chain(std::iter::once(&target)) {
+                let _ = fs::remove_file(path);
+            }
+            return Err(format!("Failed to convert {}: {}", stored.file_name, e));
+        }
+        written.push(target);
+    }
+
+    for stored in &to_convert {
+        fs::remove_file(&stored.path).map_err(|e| e.to_string())?;
+    }
+
+    log::info!(
+        "{} {} file(s) in {:?}",
+        if compress {
+            "Compressed"
+        } else {
+            "Decompressed"
+        },
+        to_convert.len(),
+        backup_path
+    );
+    Ok(())
+}
@@ -1,15 +1,169 @@
-use super::cleanup::enforce_backup_limit;
+use super::cleanup::{enforce_retention, prune_by_total_size};
 use super::common::HASH_FILE_NAME;
-use super::data::{build_save_paths, read_source_metadata, BackupInfo, SavePaths, SourceMetadata};
-use super::hashing::calculate_hash;
-#[cfg(test)]
-use super::index::BackupStore;
-use super::index::{BackupIndex, IndexEntry};
-#[cfg(test)]
+use super::compression::{compress_file, find_stored_save_file, StoredForm, COMPRESSED_SUFFIX};
+use super::data::{
+    build_save_paths, discover_save_slots, read_settled_source_metadata, read_source_metadata,
+    BackupInfo, SavePaths, SourceMetadata,
+};
+use super::delta::{select_delta_base, write_delta_main_save};
+use super::fileutil::{copy_file_with_progress, ProgressReader};
+use super::hashing::{calculate_hash, calculate_hash_like, calculate_hash_reader, hashes_match};
+use super::index::{BackupIndex, BackupStore, IndexEntry};
 use super::listing::get_backups;
-use crate::filename_utils;
+use super::manifest::{write_manifest, BackupManifest};
+use super::mirror::mirror_new_backup;
+use super::notes::read_note_sidecar;
+use super::settings::{load_directory_settings, BackupSettings};
+use super::trash::purge_expired_trash;
+use crate::filename_utils::{self, BackupNameFormat};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Stage of a single backup that progress is being reported for.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupPhase {
+    /// Hashing the main save file for duplicate detection.
+    Hashing,
+    /// Copying save files into the backup folder.
+    Copying,
+}
+
+/// Byte-level progress of a single backup.
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupProgress {
+    /// The game slot being backed up.
+    pub game_number: u32,
+    /// The stage the byte counts refer to.
+    pub phase: BackupPhase,
+    /// Bytes processed so far in this phase.
+    pub bytes_processed: u64,
+    /// Total bytes this phase will process.
+    pub total_bytes: u64,
+}
+
+/// How new backup folders are named.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingScheme {
+    /// "Game N - dd-MMM-yyyy hh-mm-ss AM", from the save's modification time.
+    #[default]
+    Timestamp,
+    /// "Game N - #0007", from a per-slot counter that only ever increases.
+    Counter,
+}
+
+/// How long the main save must stay unchanged before it is backed up.
+const SOURCE_SETTLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Backup attempts per slot after which a metadata match is rehashed anyway.
+pub const DEFAULT_HASH_RECHECK_INTERVAL: u32 = 20;
+
+/// Returns true when the slot has a `.bak` file and `.bak` files are backed up.
+fn should_store_bak(paths: &SavePaths, settings: &BackupSettings) -> bool {
+    settings.include_bak_files && paths.bak_path.exists()
+}
+
+/// Resolves the folder name for a new backup using the configured naming scheme.
+///
+/// A `naming_scheme` in the save directory's settings file takes precedence.
+pub(crate) fn next_folder_name(
+    backup_root: &Path,
+    index: &mut BackupIndex,
+    game_number: u32,
+    timestamp: DateTime<Local>,
+    settings: &BackupSettings,
+) -> String {
+    let scheme = load_directory_settings(backup_root)
+        .naming_scheme
+        .unwrap_or(settings.naming_scheme);
+    next_folder_name_with(
+        scheme,
+        settings.backup_name_format,
+        backup_root,
+        index,
+        game_number,
+        timestamp,
+    )
+}
+
+/// Resolves the folder name for a new backup using an explicit naming scheme.
+///
+/// Counter names advance the slot's counter in the index, so the caller must save
+/// the index afterwards. Counters are never reused, even after backups are deleted.
+pub(crate) fn next_folder_name_with(
+    scheme: NamingScheme,
+    format: BackupNameFormat,
+    backup_root: &Path,
+    index: &mut BackupIndex,
+    game_number: u32,
+    timestamp: DateTime<Local>,
+) -> String {
+    match scheme {
+        NamingScheme::Timestamp => unique_folder_name(backup_root, game_number, timestamp, format),
+        NamingScheme::Counter => {
+            let mut counter = index
+                .counters
+                .get(&game_number)
+                .copied()
+                .unwrap_or_else(|| highest_existing_counter(backup_root, game_number));
+            loop {
+                counter += 1;
+                let folder_name =
+                    filename_utils::format_counter_backup_folder_name(game_number, counter);
+                if !backup_root.join(&folder_name).exists() {
+                    index.counters.insert(game_number, counter);
+                    return folder_name;
+                }
+            }
+        }
+    }
+}
+
+/// Finds the highest counter already used by a slot's backup folders.
+///
+/// Seeds the counter when the index has no record of it, e.g. after the index
+/// was rebuilt, so new names continue after the existing ones.
+fn highest_existing_counter(backup_root: &Path, game_number: u32) -> u32 {
+    fs::read_dir(backup_root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    filename_utils::parse_backup_folder_name(&entry.file_name().to_string_lossy())
+                })
+                .filter(|info| info.game_number == game_number)
+                .filter_map(|info| info.counter)
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+}
+
+/// Resolves a backup folder name that does not collide with an existing folder.
+///
+/// Forced backups of an unchanged save share the source timestamp with the previous
+/// backup, so the timestamp is advanced one second at a time until the name is free.
+pub(crate) fn unique_folder_name(
+    backup_root: &Path,
+    game_number: u32,
+    timestamp: DateTime<Local>,
+    format: BackupNameFormat,
+) -> String {
+    let mut candidate = timestamp;
+    loop {
+        let folder_name =
+            filename_utils::format_backup_folder_name_with(format, game_number, candidate);
+        if !backup_root.join(&folder_name).exists() {
+            return folder_name;
+        }
+        candidate += chrono::Duration::seconds(1);
+    }
+}
 
 /// Creates the target backup directory and returns its path.
 fn create_target_dir(backup_root: &Path, folder_name: &str) -> Result<PathBuf, String> {
@@ -18,18 +172,162 @@ fn create_target_dir(backup_root: &Path, folder_name: &str) -> Result<PathBuf, S
     Ok(target_dir)
 }
 
+/// Finds an existing uncompressed main save with the given hash, if cross-slot dedup is on.
+///
+/// Backups from any slot qualify, so byte-identical saves in different slots can share
+/// one file on disk while each slot keeps its own backup folder.
+fn find_identical_main_save(
+    backup_root: &Path,
+    hash: &str,
+    settings: &BackupSettings,
+) -> Option<PathBuf> {
+    if !settings.cross_slot_dedup {
+        return None;
+    }
+    fs::read_dir(backup_root).ok()?.flatten().find_map(|entry| {
+        let folder = entry.path();
+        let info = filename_utils::parse_backup_folder_name(&entry.file_name().to_string_lossy())?;
+        let stored_hash = fs::read_to_string(folder.join(HASH_FILE_NAME)).ok()?;
+        if !hashes_match(stored_hash.trim(), hash) {
+            return None;
+        }
+        let stored = find_stored_save_file(&folder, &format!("gamesave_{}.sav", info.game_number))?;
+        (stored.form == StoredForm::Raw).then_some(stored.path)
+    })
+}
+
+/// Hardlinks the main save to an identical stored file, returning whether it succeeded.
+///
+/// Failures (e.g. across file systems) are logged so the caller can fall back to a copy.
+fn link_main_save(link_source: &Path, target: &Path) -> bool {
+    match fs::hard_link(link_source, target) {
+        Ok(()) => {
+            log::info!(
+                "Hardlinked {:?} to identical backup {:?}",
+                target,
+                link_source
+            );
+            true
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to hardlink {:?} to {:?}, copying instead: {}",
+                target,
+                link_source,
+                e
+            );
+            false
+        }
+    }
+}
+
 /// Copies the relevant save files into the target directory.
-fn copy_save_files(paths: &SavePaths, target_dir: &Path) -> Result<(), String> {
-    fs::copy(&paths.main_path, target_dir.join(&paths.main_filename)).map_err(|e| e.to_string())?;
-    if paths.bak_path.exists() {
-        fs::copy(&paths.bak_path, target_dir.join(&paths.bak_filename))
-            .map_err(|e| e.to_string())?;
+///
+/// With `compress` set the files are stored gzip-compressed instead. Otherwise the
+/// main save is hardlinked to `link_source` when one is given. The `.bak` file is
+/// skipped while `.bak` files are excluded. `on_copied` receives the bytes of each
+/// uncompressed copy as they are read.
+pub(crate) fn copy_save_files(
+    paths: &SavePaths,
+    target_dir: &Path,
+    link_source: Option<&Path>,
+    compress: bool,
+    settings: &BackupSettings,
+    on_copied: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    if compress {
+        return compress_save_files(paths, target_dir, settings);
+    }
+    let buffer_size = settings.copy_buffer_size();
+    let main_target = target_dir.join(&paths.main_filename);
+    if !link_source.is_some_and(|source| link_main_save(source, &main_target)) {
+        copy_file_with_progress(&paths.main_path, &main_target, buffer_size, &mut *on_copied)?;
+    }
+    if should_store_bak(paths, settings) {
+        copy_file_with_progress(
+            &paths.bak_path,
+            &target_dir.join(&paths.bak_filename),
+            buffer_size,
+            on_copied,
+        )?;
+    }
+    Ok(())
+}
+
+/// Stores the main save as a delta against `delta_base` when that is smaller.
+///
+/// The `.bak` file is still stored in full. Falls back to `copy_save_files` when no
+/// base is given, the delta would not save space, or the base cannot be read.
+fn store_save_files(
+    paths: &SavePaths,
+    target_dir: &Path,
+    link_source: Option<&Path>,
+    delta_base: Option<&Path>,
+    compress: bool,
+    settings: &BackupSettings,
+    on_copied: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    let stored_as_delta = delta_base.is_some_and(|base| {
+        write_delta_main_save(paths, target_dir, base).unwrap_or_else(|e| {
+            log::warn!("Storing a full copy instead of a delta: {}", e);
+            false
+        })
+    });
+    if !stored_as_delta {
+        return copy_save_files(
+            paths,
+            target_dir,
+            link_source,
+            compress,
+            settings,
+            on_copied,
+        );
+    }
+    if !should_store_bak(paths, settings) {
+        return Ok(());
+    }
+    let buffer_size = settings.copy_buffer_size();
+    if compress {
+        compress_file(
+            &paths.bak_path,
+            &target_dir.join(format!("{}{}", paths.bak_filename, COMPRESSED_SUFFIX)),
+            buffer_size,
+        )
+    } else {
+        copy_file_with_progress(
+            &paths.bak_path,
+            &target_dir.join(&paths.bak_filename),
+            buffer_size,
+            on_copied,
+        )
+        .map(|_| ())
+    }
+}
+
+/// Stores the relevant save files gzip-compressed in the target directory.
+fn compress_save_files(
+    paths: &SavePaths,
+    target_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    let buffer_size = settings.copy_buffer_size();
+    compress_file(
+        &paths.main_path,
+        &target_dir.join(format!("{}{}", paths.main_filename, COMPRESSED_SUFFIX)),
+        buffer_size,
+    )?;
+    if should_store_bak(paths, settings) {
+        compress_file(
+            &paths.bak_path,
+            &target_dir.join(format!("{}{}", paths.bak_filename, COMPRESSED_SUFFIX)),
+            buffer_size,
+        )?;
     }
     Ok(())
 }
 
 /// Writes the hash marker file into the target directory.
-fn write_hash_file(target_dir: &Path, hash: &str) -> Result<(), String> {
+pub(crate) fn write_hash_file(target_dir: &Path, hash: &str) -> Result<(), String> {
     fs::write(target_dir.join(HASH_FILE_NAME), hash).map_err(|e| e.to_string())
 }
 
@@ -48,30 +346,159 @@ fn update_index_after_backup(
             last_source_size: source.size,
             last_source_modified: source.modified_nanos,
             last_backup_path: folder_name,
+            last_verified: 0,
         },
     );
 }
 
+/// Warms the dedup index from existing backup folders without creating backups.
+///
+/// For every slot whose index entry is missing or points to a deleted folder, records
+/// the newest backup's hash. When the live save still matches that backup, its size
+/// and modification time are cached too so the next backup skips hashing entirely.
+/// Returns the number of slots primed.
+pub fn prime_index(save_dir: &Path, settings: &BackupSettings) -> Result<usize, String> {
+    let mut store = BackupStore::new(save_dir, settings)?;
+    // get_backups is sorted newest first, so the first backup seen per slot is the newest.
+    let backups = get_backups(save_dir, true, None, settings)?;
+    let mut primed = 0;
+
+    for backup in &backups {
+        let current = store.index.games.get(&backup.game_number);
+        if current.is_some_and(|entry| store.root.join(&entry.last_backup_path).is_dir()) {
+            continue;
+        }
+
+        let hash = if backup.hash.is_empty() {
+            find_stored_save_file(Path::new(&backup.path), &backup.original_filename)
+                .ok_or_else(|| format!("Backup {} has no main save file", backup.filename))?
+                .content_hash(&BackupSettings::default())?
+        } else {
+            backup.hash.clone()
+        };
+
+        let live_path = Path::new(&backup.original_path);
+        let live_matches =
+            live_path.exists() && hashes_match(&calculate_hash_like(live_path, &hash)?, &hash);
+        let (last_source_size, last_source_modified) = if live_matches {
+            let source = read_source_metadata(live_path)?;
+            (source.size, source.modified_nanos)
+        } else {
+            (backup.size, 0)
+        };
+
+        store.index.games.insert(
+            backup.game_number,
+            IndexEntry {
+                last_hash: hash,
+                last_source_size,
+                last_source_modified,
+                last_backup_path: backup.filename.clone(),
+                last_verified: 0,
+            },
+        );
+        primed += 1;
+    }
+
+    store.save()?;
+    log::info!("Primed backup index for {} slot(s)", primed);
+    Ok(primed)
+}
+
+/// Records the hash of each slot's live save in the index without creating backups.
+///
+/// Used by the index-only initial scan: the entries carry no backup folder, so the
+/// current content counts as already seen and only later changes get backed up.
+/// Slots whose index already matches the live save are left alone. Returns the
+/// number of slots indexed.
+pub(crate) fn index_live_saves(
+    save_dir: &Path,
+    backup_root: &Path,
+    game_numbers: &HashSet<u32>,
+    index: &mut BackupIndex,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    let mut indexed = 0;
+    for &game_number in game_numbers {
+        let paths = build_save_paths(save_dir, game_number);
+        if !paths.main_path.exists() {
+            continue;
+        }
+        let source = read_source_metadata(&paths.main_path)?;
+        let (hash, calculated) =
+            resolve_hash(index, game_number, &source, &paths.main_path, settings)?;
+        if is_duplicate_by_index(index, backup_root, game_number, &hash, calculated, &source) {
+            continue;
+        }
+        update_index_after_backup(index, game_number, hash, &source, String::new());
+        indexed += 1;
+    }
+    Ok(indexed)
+}
+
+/// Discards the index and rebuilds it from the backup folders on disk.
+///
+/// Unlike `prime_index`, nothing from an existing `index.json` is kept: the dedup
+/// entries are recomputed from the newest backup of each slot and notes are recovered
+/// from `note.txt` sidecars. Sessions and tags only live in the index and are lost.
+/// Returns the number of slots whose dedup entry was restored.
+pub fn rebuild_index(save_dir: &Path, settings: &BackupSettings) -> Result<usize, String> {
+    let mut store = BackupStore::new(save_dir, settings)?;
+    store.index = BackupIndex::default();
+
+    let mut notes = 0;
+    for backup in get_backups(save_dir, false, None, settings)? {
+        if let Some(note) = read_note_sidecar(Path::new(&backup.path)) {
+            let key = BackupIndex::metadata_key(&backup.filename, backup.id.as_deref());
+            store.index.notes.insert(key, note);
+            notes += 1;
+        }
+    }
+    store.save()?;
+
+    let primed = prime_index(save_dir, settings)?;
+    log::info!(
+        "Rebuilt backup index with {} slot(s) and {} note(s)",
+        primed,
+        notes
+    );
+    Ok(primed)
+}
+
 /// Resolves the content hash, short circuiting when index metadata matches.
+///
+/// Every `hash_recheck_interval`th metadata match is hashed anyway, catching
+/// different saves of equal size written within the filesystem's mtime granularity.
 fn resolve_hash(
-    index: &BackupIndex,
+    index: &mut BackupIndex,
     game_number: u32,
     source: &SourceMetadata,
     main_path: &Path,
+    settings: &BackupSettings,
 ) -> Result<(String, bool), String> {
-    if let Some(entry) = index.games.get(&game_number) {
+    if let Some(entry) = index.games.get_mut(&game_number) {
         if entry.last_source_size == source.size
             && entry.last_source_modified == source.modified_nanos
         {
+            let interval = settings.hash_recheck_interval;
+            if interval == 0 || entry.last_verified.saturating_add(1) < interval {
+                entry.last_verified = entry.last_verified.saturating_add(1);
+                log::debug!(
+                    "Metadata match for game {}: skipping hash calculation.",
+                    game_number
+                );
+                return Ok((entry.last_hash.clone(), false));
+            }
             log::debug!(
-                "Metadata match for game {}: skipping hash calculation.",
-                game_number
+                "Metadata match for game {}: rechecking hash after {} skipped attempts.",
+                game_number,
+                entry.last_verified
             );
-            return Ok((entry.last_hash.clone(), false));
+            entry.last_verified = 0;
         }
     }
 
-    let hash = calculate_hash(main_path)?;
+    let hash = calculate_hash(main_path, settings)?;
     Ok((hash, true))
 }
 
@@ -85,9 +512,10 @@ fn is_duplicate_by_index(
     source: &SourceMetadata,
 ) -> bool {
     if let Some(entry) = index.games.get(&game_number).cloned() {
-        if entry.last_hash == hash {
+        if hashes_match(&entry.last_hash, hash) {
             let last_backup_full_path = backup_root.join(&entry.last_backup_path);
-            if last_backup_full_path.exists() {
+            // An empty path means the save was indexed without a backup being taken.
+            if entry.last_backup_path.is_empty() || last_backup_full_path.exists() {
                 if calculated
                     && (entry.last_source_size != source.size
                         || entry.last_source_modified != source.modified_nanos)
@@ -99,6 +527,7 @@ fn is_duplicate_by_index(
                             last_source_size: source.size,
                             last_source_modified: source.modified_nanos,
                             last_backup_path: entry.last_backup_path.clone(),
+                            last_verified: 0,
                         },
                     );
                 }
@@ -128,7 +557,7 @@ fn is_duplicate_by_content(
     backups: &[BackupInfo],
 ) -> bool {
     for backup in backups {
-        if backup.game_number == game_number && backup.hash == hash {
+        if backup.game_number == game_number && hashes_match(&backup.hash, hash) {
             log::info!(
                 "Duplicate backup found for game {} in existing backup {}, skipping.",
                 game_number,
@@ -143,6 +572,7 @@ fn is_duplicate_by_content(
                     last_source_size: source.size,
                     last_source_modified: source.modified_nanos,
                     last_backup_path: backup.filename.clone(),
+                    last_verified: 0,
                 },
             );
             return true;
@@ -151,15 +581,93 @@ fn is_duplicate_by_content(
     false
 }
 
+/// Prunes old backups once a new one pushes the backup root over the size cap, if set.
+fn enforce_total_size_cap(
+    save_dir: &Path,
+    index: &mut BackupIndex,
+    new_backup: &Path,
+    settings: &BackupSettings,
+) {
+    let Some(max_bytes) = settings.max_total_backup_bytes else {
+        return;
+    };
+    if let Err(e) = prune_by_total_size(
+        save_dir,
+        max_bytes,
+        index,
+        new_backup,
+        settings.never_delete_last_backup,
+        settings,
+    ) {
+        log::error!("Failed to enforce the backup storage cap: {}", e);
+    }
+}
+
+/// What a single backup attempt did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupOutcome {
+    /// A new backup folder was written.
+    Created(PathBuf),
+    /// The save matches an existing backup, so nothing was written.
+    SkippedDuplicate,
+    /// The slot has no main save to back up.
+    SkippedNoSave,
+    /// The main save was still being written, so it should be retried later.
+    SkippedUnstable,
+}
+
+impl BackupOutcome {
+    /// Returns the new backup folder, if one was created.
+    pub fn created(self) -> Option<PathBuf> {
+        match self {
+            Self::Created(path) => Some(path),
+            Self::SkippedDuplicate | Self::SkippedNoSave | Self::SkippedUnstable => None,
+        }
+    }
+}
+
+/// Per-call options for `perform_backup_for_game_internal`.
+#[derive(Default)]
+pub(crate) struct BackupOptions<'a> {
+    /// Backups kept per slot, counting the new one; 0 means unlimited.
+    pub(crate) limit: usize,
+    /// Skips retention entirely, so neither the limits nor the size cap delete anything.
+    pub(crate) keep_existing: bool,
+    /// Bypasses both duplicate checks so a backup is written even if unchanged.
+    pub(crate) force: bool,
+    /// Waits for the main save to stop changing first, skipping it as unstable otherwise.
+    ///
+    /// Set for watcher-driven backups, which fire while the game may still be writing,
+    /// and for the safety backup taken before a restore overwrites the live save.
+    pub(crate) wait_until_settled: bool,
+    /// Receives byte-level progress while hashing and copying, if given.
+    ///
+    /// The main save is then always hashed from disk so progress covers the full read.
+    pub(crate) on_progress: Option<&'a mut dyn FnMut(BackupProgress)>,
+}
+
 /// Internal implementation of perform_backup_for_game that accepts a mutable index.
+///
+/// When `options.force` is set, both duplicate checks are bypassed so a snapshot is
+/// written even if the content matches an existing backup. The limit and `.hash` file
+/// still apply. An open dedup override window in `settings` has the same effect as `force`.
+/// The save directory's settings file may override the limit and compression.
 pub(crate) fn perform_backup_for_game_internal(
     save_dir: &Path,
     backup_root: &Path,
     game_number: u32,
     index: &mut BackupIndex,
-    limit: usize,
     backups: &[BackupInfo],
-) -> Result<Option<PathBuf>, String> {
+    settings: &BackupSettings,
+    options: BackupOptions,
+) -> Result<BackupOutcome, String> {
+    let BackupOptions {
+        limit,
+        keep_existing,
+        force,
+        wait_until_settled,
+        mut on_progress,
+    } = options;
     let paths = build_save_paths(save_dir, game_number);
     if !paths.main_path.exists() {
         if paths.bak_path.exists() {
@@ -173,66 +681,315 @@ pub(crate) fn perform_backup_for_game_internal(
                 game_number
             );
         }
-        return Ok(None);
+        return Ok(BackupOutcome::SkippedNoSave);
     }
 
-    let source = read_source_metadata(&paths.main_path)?;
-    let (hash, calculated) = resolve_hash(index, game_number, &source, &paths.main_path)?;
+    let source = if wait_until_settled {
+        let Some(source) = read_settled_source_metadata(&paths.main_path, SOURCE_SETTLE_INTERVAL)?
+        else {
+            log::info!(
+                "Save file for game {} is still being written, skipping backup.",
+                game_number
+            );
+            return Ok(BackupOutcome::SkippedUnstable);
+        };
+        source
+    } else {
+        read_source_metadata(&paths.main_path)?
+    };
+    let reports_progress = on_progress.is_some();
+    let mut report = |phase: BackupPhase, bytes_processed: u64, total_bytes: u64| {
+        if let Some(on_progress) = on_progress.as_deref_mut() {
+            on_progress(BackupProgress {
+                game_number,
+                phase,
+                bytes_processed,
+                total_bytes,
+            });
+        }
+    };
+
+    let (hash, calculated) = if reports_progress {
+        let mut hashed = 0_u64;
+        report(BackupPhase::Hashing, 0, source.size);
+        let file = fs::File::open(&paths.main_path).map_err(|e| e.to_string())?;
+        let hash = calculate_hash_reader(
+            ProgressReader::new(file, |n| {
+                hashed += n;
+                report(BackupPhase::Hashing, hashed, source.size);
+            }),
+            settings,
+        )?;
+        (hash, true)
+    } else {
+        resolve_hash(index, game_number, &source, &paths.main_path, settings)?
+    };
 
-    // 1. Optimistic duplicate check (Index only)
-    if is_duplicate_by_index(index, backup_root, game_number, &hash, calculated, &source) {
-        return Ok(None);
-    }
+    if !force && !settings.dedup_disabled() {
+        // 1. Optimistic duplicate check (Index only)
+        if is_duplicate_by_index(index, backup_root, game_number, &hash, calculated, &source) {
+            return Ok(BackupOutcome::SkippedDuplicate);
+        }
 
-    // 2. Backups are now passed in (backups list fetched once by caller)
+        // 2. Backups are now passed in (backups list fetched once by caller)
 
-    // 3. Fallback duplicate check (Content scan)
-    if is_duplicate_by_content(index, game_number, &hash, &source, backups) {
-        return Ok(None);
+        // 3. Fallback duplicate check (Content scan)
+        if is_duplicate_by_content(index, game_number, &hash, &source, backups) {
+            return Ok(BackupOutcome::SkippedDuplicate);
+        }
     }
 
     // 4. Enforce limit
-    if let Err(e) = enforce_backup_limit(game_number, limit, backups, index) {
-        log::error!(
-            "Failed to enforce backup limit for game {}: {}",
-            game_number,
-            e
-        );
+    let directory = load_directory_settings(backup_root);
+    if !keep_existing {
+        // The directory's limit replaces the caller's, but never turns retention on.
+        let limit = directory.limit.unwrap_or(limit);
+        if let Err(e) = enforce_retention(game_number, limit, backups, index, settings) {
+            log::error!(
+                "Failed to enforce backup retention for game {}: {}",
+                game_number,
+                e
+            );
+        }
+    }
+    if let Err(e) = purge_expired_trash(backup_root, settings.trash_retention_days) {
+        log::error!("Failed to empty expired trash: {}", e);
     }
 
-    let folder_name = filename_utils::format_backup_folder_name(game_number, source.modified_dt);
+    let link_source = find_identical_main_save(backup_root, &hash, settings);
+    // A hardlink to an identical save costs nothing, so it beats any delta.
+    let delta_base = (link_source.is_none() && settings.delta_backups)
+        .then(|| select_delta_base(backup_root, index, game_number, &paths.main_filename))
+        .flatten();
+    let folder_name = next_folder_name(
+        backup_root,
+        index,
+        game_number,
+        source.modified_dt,
+        settings,
+    );
     let target_dir = create_target_dir(backup_root, &folder_name)?;
-    copy_save_files(&paths, &target_dir)?;
+    let copy_total = if reports_progress && should_store_bak(&paths, settings) {
+        let bak_size = fs::metadata(&paths.bak_path)
+            .map_err(|e| e.to_string())?
+            .len();
+        source.size + bak_size
+    } else {
+        source.size
+    };
+    let mut copied = 0_u64;
+    report(BackupPhase::Copying, 0, copy_total);
+    store_save_files(
+        &paths,
+        &target_dir,
+        link_source.as_deref(),
+        delta_base.as_deref(),
+        directory
+            .compress_backups
+            .unwrap_or(settings.compress_backups),
+        settings,
+        &mut |n| {
+            copied += n;
+            report(BackupPhase::Copying, copied, copy_total);
+        },
+    )?;
+    // Compressed, delta and hardlinked saves report no bytes, so jump to done.
+    if copied < copy_total {
+        report(BackupPhase::Copying, copy_total, copy_total);
+    }
     write_hash_file(&target_dir, &hash)?;
+    write_manifest(&target_dir, &BackupManifest::new(Some(source.modified_dt)))?;
     update_index_after_backup(index, game_number, hash, &source, folder_name);
+    if !keep_existing {
+        enforce_total_size_cap(save_dir, index, &target_dir, settings);
+    }
+    mirror_new_backup(&target_dir, save_dir, settings);
 
-    Ok(Some(target_dir))
+    Ok(BackupOutcome::Created(target_dir))
 }
 
-/// Backs up a specific game slot by directory and game number.
+/// Backs up a single game slot, reporting byte-level progress while hashing and copying.
+///
+/// The main save is always hashed from disk so progress covers the full read. Duplicate
+/// detection and the backup limit apply exactly as for watcher-driven backups.
+pub fn perform_backup_with_progress(
+    save_dir: &Path,
+    game_number: u32,
+    limit: usize,
+    settings: &BackupSettings,
+    mut on_progress: impl FnMut(BackupProgress),
+) -> Result<Option<PathBuf>, String> {
+    if !save_dir.exists() {
+        return Err(format!("Save directory does not exist: {:?}", save_dir));
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let backups = get_backups(save_dir, true, Some(game_number), settings)?;
+    let outcome = perform_backup_for_game_internal(
+        save_dir,
+        &store.root,
+        game_number,
+        &mut store.index,
+        &backups,
+        settings,
+        BackupOptions {
+            limit,
+            on_progress: Some(&mut on_progress),
+            ..BackupOptions::default()
+        },
+    )?;
+    store.save()?;
+
+    Ok(outcome.created())
+}
+
+/// Takes a forced checkpoint backup of one slot, or of every slot when `game_number` is `None`.
+///
+/// Duplicate detection is bypassed so a backup is written even if the content is
+/// unchanged since the last one; the backup limit still applies. Returns the folder
+/// names of the backups created.
+pub fn trigger_manual_backup(
+    save_dir: &Path,
+    game_number: Option<u32>,
+    limit: usize,
+    settings: &BackupSettings,
+) -> Result<Vec<String>, String> {
+    if !save_dir.exists() {
+        return Err(format!("Save directory does not exist: {:?}", save_dir));
+    }
+
+    let slots = match game_number {
+        Some(game_number) => vec![game_number],
+        None => discover_save_slots(save_dir)?,
+    };
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut folders = Vec::new();
+    for game_number in slots {
+        let backups = get_backups(save_dir, true, Some(game_number), settings).unwrap_or_default();
+        let created = perform_backup_for_game_internal(
+            save_dir,
+            &store.root,
+            game_number,
+            &mut store.index,
+            &backups,
+            settings,
+            BackupOptions {
+                limit,
+                force: true,
+                ..BackupOptions::default()
+            },
+        )?
+        .created();
+        if let Some(folder_name) = created
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|n| n.to_string_lossy().into_owned())
+        {
+            folders.push(folder_name);
+        }
+    }
+
+    store.save()?;
+    Ok(folders)
+}
+
+/// Per-slot results of backing up every slot at once.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct BackupAllSummary {
+    /// Slots a new backup was written for.
+    pub created: Vec<u32>,
+    /// Slots left alone because the save was unchanged or still being written.
+    pub skipped: Vec<u32>,
+    /// Slots whose backup failed, with the error.
+    pub errors: Vec<(u32, String)>,
+}
+
+/// Backs up every slot with a main save in `save_dir`, sharing one index load and save.
+///
+/// Slots are found the same way as the watcher's initial scan, and duplicate detection
+/// and the backup limit apply as for watcher backups. A failing slot does not stop the
+/// others; its error is reported in the summary instead.
+pub fn backup_all_now(
+    save_dir: &Path,
+    limit: usize,
+    settings: &BackupSettings,
+) -> Result<BackupAllSummary, String> {
+    if !save_dir.exists() {
+        return Err(format!("Save directory does not exist: {:?}", save_dir));
+    }
+
+    let slots = discover_save_slots(save_dir)?;
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut summary = BackupAllSummary::default();
+    for game_number in slots {
+        let backups = get_backups(save_dir, true, Some(game_number), settings).unwrap_or_default();
+        match perform_backup_for_game_internal(
+            save_dir,
+            &store.root,
+            game_number,
+            &mut store.index,
+            &backups,
+            settings,
+            BackupOptions {
+                limit,
+                ..BackupOptions::default()
+            },
+        ) {
+            Ok(BackupOutcome::Created(_)) => summary.created.push(game_number),
+            Ok(_) => summary.skipped.push(game_number),
+            Err(e) => {
+                log::error!("Backup failed for game {}: {}", game_number, e);
+                summary.errors.push((game_number, e));
+            }
+        }
+    }
+
+    store.save()?;
+    Ok(summary)
+}
+
+/// Backs up a specific game slot by directory and game number with default settings.
 #[cfg(test)]
 pub fn perform_backup_for_game(
     save_dir: &Path,
     game_number: u32,
     limit: usize,
+) -> Result<Option<PathBuf>, String> {
+    perform_backup_for_game_with(save_dir, game_number, limit, &BackupSettings::default())
+}
+
+/// Backs up a specific game slot by directory and game number.
+///
+/// Thin wrapper over `perform_backup_for_game_internal` returning the new folder, if any.
+#[cfg(test)]
+pub fn perform_backup_for_game_with(
+    save_dir: &Path,
+    game_number: u32,
+    limit: usize,
+    settings: &BackupSettings,
 ) -> Result<Option<PathBuf>, String> {
     if !save_dir.exists() {
         return Err(format!("Save directory does not exist: {:?}", save_dir));
     }
 
-    let mut store = BackupStore::new(save_dir)?;
-    let backups = get_backups(save_dir, true, Some(game_number)).unwrap_or_default();
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let backups = get_backups(save_dir, true, Some(game_number), settings).unwrap_or_default();
 
     let result = perform_backup_for_game_internal(
         save_dir,
         &store.root,
         game_number,
         &mut store.index,
-        limit,
         &backups,
+        settings,
+        BackupOptions {
+            limit,
+            ..BackupOptions::default()
+        },
     )?;
 
     store.save()?;
 
-    Ok(result)
+    Ok(result.created())
 }
@@ -1,14 +1,19 @@
+use super::save_format::SaveMetadata;
+use crate::filename_utils;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 /// Represents metadata for a backup entry.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BackupInfo {
     /// The absolute path to the backup folder.
     pub path: String,
+    /// Stable unique ID from the backup manifest; `None` for legacy backups without one.
+    pub id: Option<String>,
     /// The display name of the backup folder (e.g., "Game 1 - ...").
     pub filename: String,
     /// The name of the original save file (e.g., "gamesave_0.sav").
@@ -21,12 +26,26 @@ pub struct BackupInfo {
     pub modified: String,
     /// The game number (0-based for internal logic).
     pub game_number: u32,
-    /// Whether the backup is locked (preventing auto-deletion).
+    /// Whether the backup is locked (preventing auto-deletion). Always true when archived.
     pub locked: bool,
+    /// Whether the backup is archived and left out of all automated cleanup.
+    #[serde(default)]
+    pub archived: bool,
     /// The SHA-256 hash of the main save file.
     pub hash: String,
+    /// The algorithm that produced `hash`, e.g. "sha256".
+    pub hash_algorithm: String,
     /// An optional user-provided note.
     pub note: Option<String>,
+    /// Normalized user tags, in the order they were added.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// In-game details read from the save, only filled in when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SaveMetadata>,
+    /// Whether the backup is marked as a favorite (no effect on auto-deletion).
+    #[serde(default)]
+    pub favorite: bool,
 }
 
 /// Conventional save file paths for a specific game slot.
@@ -50,6 +69,22 @@ pub(crate) fn build_save_paths(save_dir: &Path, game_number: u32) -> SavePaths {
     }
 }
 
+/// Discovers game slots that currently have a main save file in the save directory.
+///
+/// Returned slot numbers are sorted ascending. `.bak` files alone do not count as a slot.
+pub(crate) fn discover_save_slots(save_dir: &Path) -> Result<Vec<u32>, String> {
+    let mut slots = Vec::new();
+    for entry in fs::read_dir(save_dir).map_err(|e| e.to_string())?.flatten() {
+        if let Some(info) = filename_utils::parse_path(&entry.path()) {
+            if !info.is_bak && !slots.contains(&info.game_number) {
+                slots.push(info.game_number);
+            }
+        }
+    }
+    slots.sort_unstable();
+    Ok(slots)
+}
+
 /// Metadata needed for backup naming and deduplication.
 #[derive(Debug, Clone)]
 pub(crate) struct SourceMetadata {
@@ -73,3 +108,18 @@ pub(crate) fn read_source_metadata(main_path: &Path) -> Result<SourceMetadata, S
         modified_dt: modified_time.into(),
     })
 }
+
+/// Reads the save's metadata twice, `interval` apart, to tell if it is still being written.
+///
+/// Returns `None` when the size or modification time changed in between, so a save the
+/// game is still writing is not hashed and backed up half-written.
+pub(crate) fn read_settled_source_metadata(
+    main_path: &Path,
+    interval: Duration,
+) -> Result<Option<SourceMetadata>, String> {
+    let before = read_source_metadata(main_path)?;
+    thread::sleep(interval);
+    let after = read_source_metadata(main_path)?;
+    let settled = before.size == after.size && before.modified_nanos == after.modified_nanos;
+    Ok(settled.then_some(after))
+}
@@ -0,0 +1,323 @@
+use super::common::DELTA_BASE_FILE_NAME;
+use super::compression::{
+    find_stored_save_file, list_stored_save_files, StoredForm, StoredSaveFile,
+};
+use super::data::SavePaths;
+use super::index::BackupIndex;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Suffix appended to a main save stored as a delta against an earlier backup.
+pub const DELTA_SUFFIX: &str = ".delta";
+
+/// Most deltas in a row before a full copy is stored again, so one corrupt delta
+/// only breaks the backups up to the next full copy.
+pub const DELTA_CHAIN_LIMIT: usize = 10;
+
+/// Deepest chain reconstruction follows before treating the markers as corrupt.
+const MAX_CHAIN_DEPTH: usize = 64;
+
+const DELTA_MAGIC: &[u8; 8] = b"ITDDELTA";
+/// Magic, then the reconstructed length as a little-endian `u64`.
+const DELTA_HEADER_LEN: usize = DELTA_MAGIC.len() + 8;
+pub(crate) const DELTA_BLOCK_SIZE: usize = 4096;
+/// Copy the next `len` bytes from the same offset of the base.
+const OP_COPY: u8 = 0;
+/// Take the next `len` bytes from the delta itself.
+const OP_LITERAL: u8 = 1;
+
+/// Returns the backup folder name a delta backup is based on, if it is one.
+pub(crate) fn delta_base_of(backup_folder: &Path) -> Option<String> {
+    fs::read_to_string(backup_folder.join(DELTA_BASE_FILE_NAME))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Counts the deltas between a backup and the full copy its chain starts from.
+///
+/// `None` when a base in the chain is missing or the chain is longer than allowed.
+fn chain_depth(backup_root: &Path, folder_name: &str) -> Option<usize> {
+    let mut folder = backup_root.join(folder_name);
+    for depth in 0..=MAX_CHAIN_DEPTH {
+        if !folder.is_dir() {
+            return None;
+        }
+        match delta_base_of(&folder) {
+            Some(base) => folder = backup_root.join(base),
+            None => return Some(depth),
+        }
+    }
+    None
+}
+
+/// Picks the backup the next backup of a slot should be stored as a delta against.
+///
+/// This is the slot's previous backup from the index, unless that backup is gone
+/// or its chain already holds `DELTA_CHAIN_LIMIT` deltas.
+pub(crate) fn select_delta_base(
+    backup_root: &Path,
+    index: &BackupIndex,
+    game_number: u32,
+    main_filename: &str,
+) -> Option<PathBuf> {
+    let previous = &index.games.get(&game_number)?.last_backup_path;
+    let base_folder = backup_root.join(previous);
+    find_stored_save_file(&base_folder, main_filename)?;
+    let depth = chain_depth(backup_root, previous)?;
+    (depth < DELTA_CHAIN_LIMIT).then_some(base_folder)
+}
+
+/// Stores the main save in `target_dir` as a delta against the base backup's main save.
+///
+/// Returns `false` without writing anything when the delta would not be smaller than
+/// the save itself, so the caller stores a full copy instead.
+pub(crate) fn write_delta_main_save(
+    paths: &SavePaths,
+    target_dir: &Path,
+    base_folder: &Path,
+) -> Result<bool, String> {
+    let base_name = base_folder
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Delta base folder name is invalid".to_string())?;
+    let base = find_stored_save_file(base_folder, &paths.main_filename)
+        .ok_or_else(|| format!("Delta base {} has no main save", base_name))?;
+    let base_content = stored_content(&base, 0)?;
+    let content = fs::read(&paths.main_path).map_err(|e| e.to_string())?;
+
+    let delta = encode_delta(&base_content, &content);
+    if delta.len() >= content.len() {
+        return Ok(false);
+    }
+
+    let delta_path = target_dir.join(format!("{}{}", paths.main_filename, DELTA_SUFFIX));
+    let marker_path = target_dir.join(DELTA_BASE_FILE_NAME);
+    let written = fs::write(&delta_path, &delta).and_then(|_| fs::write(&marker_path, base_name));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&delta_path);
+        let _ = fs::remove_file(&marker_path);
+        return Err(e.to_string());
+    }
+    Ok(true)
+}
+
+/// Reads the full original content of a stored save file, applying delta chains.
+pub(crate) fn stored_content(stored: &StoredSaveFile, depth: usize) -> Result<Vec<u8>, String> {
+    if stored.form != StoredForm::Delta {
+        let mut content = Vec::new();
+        stored
+            .open_content()?
+            .read_to_end(&mut content)
+            .map_err(|e| e.to_string())?;
+        return Ok(content);
+    }
+    if depth >= MAX_CHAIN_DEPTH {
+        return Err(format!("Delta chain of {:?} is too long", stored.path));
+    }
+
+    let folder = stored
+        .path
+        .parent()
+        .ok_or_else(|| "Delta file path is invalid".to_string())?;
+    let base_name = delta_base_of(folder)
+        .ok_or_else(|| format!("Delta backup {:?} does not name its base", folder))?;
+    let base_folder = folder
+        .parent()
+        .map(|root| root.join(&base_name))
+        .ok_or_else(|| "Delta file path is invalid".to_string())?;
+    let base = find_stored_save_file(&base_folder, &stored.file_name)
+        .ok_or_else(|| format!("Delta base {} is missing", base_name))?;
+    let base_content = stored_content(&base, depth + 1)?;
+    let delta = fs::read(&stored.path).map_err(|e| e.to_string())?;
+    apply_delta(&base_content, &delta)
+}
+
+/// Reads the reconstructed length recorded in a delta file's header.
+pub(crate) fn delta_target_len(delta_path: &Path) -> Result<u64, String> {
+    let mut header = [0_u8; DELTA_HEADER_LEN];
+    fs::File::open(delta_path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map_err(|e| e.to_string())?;
+    parse_header(&header)
+}
+
+/// Makes a backup folder safe to delete or move out of `.backups`.
+///
+/// Deltas based on the folder are turned into full copies so none loses its base.
+/// With `keeps_contents` set (trash, quarantine) a delta folder is also turned into a
+/// full copy itself, so it can be brought back after its own base is gone. Deltas
+/// that cannot be reconstructed are already broken and are left as they are.
+pub(crate) fn release_delta_links(
+    backup_folder: &Path,
+    keeps_contents: bool,
+) -> Result<(), String> {
+    let detached = detach_delta_dependents(backup_folder)?;
+    if detached > 0 {
+        log::info!(
+            "Stored {} delta backup(s) based on {:?} in full",
+            detached,
+            backup_folder
+        );
+    }
+    if keeps_contents && delta_base_of(backup_folder).is_some() {
+        if let Err(e) = materialize_delta_backup(backup_folder) {
+            log::warn!("Keeping {:?} as a delta: {}", backup_folder, e);
+        }
+    }
+    Ok(())
+}
+
+/// Points the delta backups based on a renamed folder at its new name.
+///
+/// Returns how many base markers were rewritten.
+pub(crate) fn rebase_delta_dependents(
+    backup_root: &Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<usize, String> {
+    let mut rebased = 0;
+    for entry in fs::read_dir(backup_root).map_err(|e| e.to_string())? {
+        let folder = entry.map_err(|e| e.to_string())?.path();
+        if folder.is_dir() && delta_base_of(&folder).as_deref() == Some(old_name) {
+            fs::write(folder.join(DELTA_BASE_FILE_NAME), new_name).map_err(|e| e.to_string())?;
+            rebased += 1;
+        }
+    }
+    Ok(rebased)
+}
+
+/// Turns every delta backup based on `backup_folder` into a full copy.
+///
+/// Returns how many backups were converted.
+fn detach_delta_dependents(backup_folder: &Path) -> Result<usize, String> {
+    let (Some(backup_root), Some(folder_name)) = (
+        backup_folder.parent(),
+        backup_folder.file_name().and_then(|n| n.to_str()),
+    ) else {
+        return Ok(0);
+    };
+
+    let mut detached = 0;
+    for entry in fs::read_dir(backup_root).map_err(|e| e.to_string())? {
+        let folder = entry.map_err(|e| e.to_string())?.path();
+        if !folder.is_dir() || delta_base_of(&folder).as_deref() != Some(folder_name) {
+            continue;
+        }
+        match materialize_delta_backup(&folder) {
+            Ok(()) => detached += 1,
+            Err(e) => log::warn!("Could not store {:?} in full: {}", folder, e),
+        }
+    }
+    Ok(detached)
+}
+
+/// Replaces the delta files of a backup folder with full copies and drops its base marker.
+fn materialize_delta_backup(folder: &Path) -> Result<(), String> {
+    for stored in list_stored_save_files(folder)? {
+        if stored.form != StoredForm::Delta {
+            continue;
+        }
+        let content = stored_content(&stored, 0)?;
+        // Written aside first: a partial raw file would shadow the intact delta.
+        let temp_path = folder.join(format!(".{}.tmp", stored.file_name));
+        fs::write(&temp_path, content)
+            .and_then(|_| fs::rename(&temp_path, folder.join(&stored.file_name)))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                e.to_string()
+            })?;
+        fs::remove_file(&stored.path).map_err(|e| e.to_string())?;
+    }
+    fs::remove_file(folder.join(DELTA_BASE_FILE_NAME)).map_err(|e| e.to_string())?;
+    log::info!("Stored delta backup {:?} as a full copy", folder);
+    Ok(())
+}
+
+/// Encodes `target` as block copies from the same offsets of `base` plus literal bytes.
+///
+/// Saves that are rewritten in place keep most blocks where they were, which this
+/// captures without the cost of a general-purpose diff.
+pub(crate) fn encode_delta(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut delta = Vec::with_capacity(DELTA_HEADER_LEN);
+    delta.extend_from_slice(DELTA_MAGIC);
+    delta.extend_from_slice(&(target.len() as u64).to_le_bytes());
+
+    let mut pending: Option<(u8, usize, usize)> = None;
+    for (block_index, block) in target.chunks(DELTA_BLOCK_SIZE).enumerate() {
+        let start = block_index * DELTA_BLOCK_SIZE;
+        let op = if base.get(start..start + block.len()) == Some(block) {
+            OP_COPY
+        } else {
+            OP_LITERAL
+        };
+        pending = match pending {
+            Some((pending_op, from, len)) if pending_op == op => {
+                Some((op, from, len + block.len()))
+            }
+            Some(run) => {
+                push_op(&mut delta, run, target);
+                Some((op, start, block.len()))
+            }
+            None => Some((op, start, block.len())),
+        };
+    }
+    if let Some(run) = pending {
+        push_op(&mut delta, run, target);
+    }
+    delta
+}
+
+/// Appends one run of blocks to a delta.
+fn push_op(delta: &mut Vec<u8>, (op, from, len): (u8, usize, usize), target: &[u8]) {
+    delta.push(op);
+    delta.extend_from_slice(&(len as u64).to_le_bytes());
+    if op == OP_LITERAL {
+        delta.extend_from_slice(&target[from..from + len]);
+    }
+}
+
+/// Rebuilds the target content from its base and a delta made by `encode_delta`.
+pub(crate) fn apply_delta(base: &[u8], delta: &[u8]) -> Result<Vec<u8>, String> {
+    let corrupt = || "Delta file is corrupt".to_string();
+    let header = delta.get(..DELTA_HEADER_LEN).ok_or_else(corrupt)?;
+    let target_len = usize::try_from(parse_header(header)?).map_err(|_| corrupt())?;
+
+    // Copies come from `base` and literals from `delta`, so a valid target is never
+    // longer than both together; a larger header length is corrupt, not an allocation.
+    let mut target = Vec::with_capacity(target_len.min(base.len().saturating_add(delta.len())));
+    let mut rest = &delta[DELTA_HEADER_LEN..];
+    while let Some((&op, after_op)) = rest.split_first() {
+        let len_bytes = after_op.get(..8).ok_or_else(corrupt)?;
+        let len = u64::from_le_bytes(len_bytes.try_into().map_err(|_| corrupt())?);
+        let len = usize::try_from(len).map_err(|_| corrupt())?;
+        rest = &after_op[8..];
+        let start = target.len();
+        let bytes = match op {
+            OP_COPY => base.get(start..start.checked_add(len).ok_or_else(corrupt)?),
+            OP_LITERAL => {
+                let bytes = rest.get(..len);
+                rest = rest.get(len..).unwrap_or_default();
+                bytes
+            }
+            _ => None,
+        }
+        .ok_or_else(corrupt)?;
+        target.extend_from_slice(bytes);
+    }
+
+    if target.len() != target_len {
+        return Err(corrupt());
+    }
+    Ok(target)
+}
+
+/// Validates a delta header and returns the reconstructed length it records.
+fn parse_header(header: &[u8]) -> Result<u64, String> {
+    let length = header
+        .strip_prefix(DELTA_MAGIC.as_slice())
+        .and_then(|length| <[u8; 8]>::try_from(length).ok())
+        .ok_or_else(|| "Delta file is corrupt".to_string())?;
+    Ok(u64::from_le_bytes(length))
+}
@@ -0,0 +1,147 @@
+use super::common::HASH_FILE_NAME;
+use super::compression::find_stored_save_file;
+use super::hashing::{hash_algorithm_of, hashes_match};
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// A backup identified by slot and content, as compared between two save directories.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct BackupSetEntry {
+    /// The game slot the backup belongs to.
+    pub game_number: u32,
+    /// Content hash of the backed-up main save.
+    pub hash: String,
+    /// Folder name of the newest backup with this content (from set A for shared entries).
+    pub filename: String,
+}
+
+/// Which backups exist in one save directory but not the other.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct BackupSetDiff {
+    /// Backups whose content exists only under the first directory.
+    pub only_in_a: Vec<BackupSetEntry>,
+    /// Backups whose content exists only under the second directory.
+    pub only_in_b: Vec<BackupSetEntry>,
+    /// Backups whose content exists under both directories.
+    pub in_both: Vec<BackupSetEntry>,
+}
+
+/// Collects one entry per `(game_number, hash)`, keeping the newest backup's folder name.
+fn backup_set(save_dir: &Path, settings: &BackupSettings) -> Result<Vec<BackupSetEntry>, String> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for backup in get_backups(save_dir, true, None, settings)? {
+        if backup.hash.is_empty() {
+            log::warn!("Skipping backup {} without a stored hash", backup.filename);
+            continue;
+        }
+        if seen.insert((backup.game_number, backup.hash.clone())) {
+            entries.push(BackupSetEntry {
+                game_number: backup.game_number,
+                hash: backup.hash,
+                filename: backup.filename,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Compares the backups of two save directories by slot and content hash.
+///
+/// Folder names and timestamps are ignored, so the same save backed up separately in
+/// each location counts as shared. Each bucket keeps newest-first order.
+pub fn diff_backup_sets(
+    dir_a: &Path,
+    dir_b: &Path,
+    settings: &BackupSettings,
+) -> Result<BackupSetDiff, String> {
+    let set_a = backup_set(dir_a, settings)?;
+    let set_b = backup_set(dir_b, settings)?;
+    let keys_a: HashSet<(u32, &str)> = set_a
+        .iter()
+        .map(|e| (e.game_number, e.hash.as_str()))
+        .collect();
+    let keys_b: HashSet<(u32, &str)> = set_b
+        .iter()
+        .map(|e| (e.game_number, e.hash.as_str()))
+        .collect();
+
+    let mut diff = BackupSetDiff::default();
+    for entry in &set_a {
+        if keys_b.contains(&(entry.game_number, entry.hash.as_str())) {
+            diff.in_both.push(entry.clone());
+        } else {
+            diff.only_in_a.push(entry.clone());
+        }
+    }
+    diff.only_in_b = set_b
+        .iter()
+        .filter(|e| !keys_a.contains(&(e.game_number, e.hash.as_str())))
+        .cloned()
+        .collect();
+    Ok(diff)
+}
+
+/// Whether two backups hold the same save.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupComparison {
+    /// Both main saves have the same content.
+    Identical,
+    /// The main saves differ.
+    Different,
+    /// The backups belong to different game slots, so they are not compared.
+    DifferentSlots,
+}
+
+/// Reads a backup's slot and the hash stored in its `.hash` file, if any.
+fn slot_and_stored_hash(backup_folder: &Path) -> Result<(u32, Option<String>), String> {
+    let folder_name = backup_folder
+        .file_name()
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?
+        .to_string_lossy();
+    let info = filename_utils::parse_backup_folder_name(&folder_name)
+        .ok_or_else(|| format!("Not a backup folder: {}", folder_name))?;
+    let stored_hash = fs::read_to_string(backup_folder.join(HASH_FILE_NAME))
+        .map(|hash| hash.trim().to_string())
+        .ok()
+        .filter(|hash| !hash.is_empty());
+    Ok((info.game_number, stored_hash))
+}
+
+/// Hashes a backup's main save from disk with the default algorithm.
+fn main_save_hash(backup_folder: &Path, game_number: u32) -> Result<String, String> {
+    find_stored_save_file(backup_folder, &format!("gamesave_{}.sav", game_number))
+        .ok_or_else(|| format!("Backup {:?} has no main save file", backup_folder))?
+        .content_hash(&BackupSettings::default())
+}
+
+/// Compares the main saves of two backups by content hash.
+///
+/// Stored `.hash` files are used when both exist and share an algorithm; otherwise
+/// both saves are hashed from disk so a missing or older hash cannot cause a mismatch.
+pub fn compare_backups(path_a: &Path, path_b: &Path) -> Result<BackupComparison, String> {
+    let (game_a, stored_a) = slot_and_stored_hash(path_a)?;
+    let (game_b, stored_b) = slot_and_stored_hash(path_b)?;
+    if game_a != game_b {
+        return Ok(BackupComparison::DifferentSlots);
+    }
+
+    let (hash_a, hash_b) = match (stored_a, stored_b) {
+        (Some(a), Some(b)) if hash_algorithm_of(&a) == hash_algorithm_of(&b) => (a, b),
+        _ => (
+            main_save_hash(path_a, game_a)?,
+            main_save_hash(path_b, game_b)?,
+        ),
+    };
+    if hashes_match(&hash_a, &hash_b) {
+        Ok(BackupComparison::Identical)
+    } else {
+        Ok(BackupComparison::Different)
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Default I/O buffer size in KiB, matching the standard library's copy buffer.
+pub const DEFAULT_COPY_BUFFER_KB: usize = 8;
+/// Upper bound for the configurable buffer to keep per-copy allocations reasonable.
+pub(crate) const MAX_COPY_BUFFER_KB: usize = 16 * 1024;
+/// Buffer size in bytes for reads that do not follow the configured copy buffer.
+pub(crate) const DEFAULT_COPY_BUFFER_SIZE: usize = DEFAULT_COPY_BUFFER_KB * 1024;
+
+/// Returns the total size in bytes of all files under a directory, recursively.
+pub(crate) fn folder_size(path: &Path) -> Result<u64, String> {
+    let mut total = 0_u64;
+    for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        if metadata.is_dir() {
+            total += folder_size(&entry.path())?;
+        } else {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Sets a file's modification time.
+pub(crate) fn set_file_modified(path: &Path, modified: SystemTime) -> Result<(), String> {
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(modified))
+        .map_err(|e| e.to_string())
+}
+
+/// Reader adapter that reports the number of bytes read in each chunk.
+pub(crate) struct ProgressReader<R, F> {
+    inner: R,
+    on_read: F,
+}
+
+impl<R: Read, F: FnMut(u64)> ProgressReader<R, F> {
+    /// Wraps `inner`, invoking `on_read` with the size of every non-empty read.
+    pub(crate) fn new(inner: R, on_read: F) -> Self {
+        Self { inner, on_read }
+    }
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        if bytes_read > 0 {
+            (self.on_read)(bytes_read as u64);
+        }
+        Ok(bytes_read)
+    }
+}
+
+/// Copies a file through a manual read/write loop with the given buffer size.
+pub(crate) fn copy_file(src: &Path, dst: &Path, buffer_size: usize) -> Result<u64, String> {
+    let reader = fs::File::open(src).map_err(|e| e.to_string())?;
+    copy_reader_to_file(reader, dst, buffer_size)
+}
+
+/// Copies a file with the given buffer size, reporting bytes as they are read.
+pub(crate) fn copy_file_with_progress(
+    src: &Path,
+    dst: &Path,
+    buffer_size: usize,
+    on_read: impl FnMut(u64),
+) -> Result<u64, String> {
+    let reader = fs::File::open(src).map_err(|e| e.to_string())?;
+    copy_reader_to_file(ProgressReader::new(reader, on_read), dst, buffer_size)
+}
+
+/// Writes everything readable from `reader` into a new file at `dst`.
+fn copy_reader_to_file(
+    mut reader: impl Read,
+    dst: &Path,
+    buffer_size: usize,
+) -> Result<u64, String> {
+    let mut writer = fs::File::create(dst).map_err(|e| e.to_string())?;
+    let mut buffer = vec![0_u8; buffer_size.max(1)];
+    let mut total = 0_u64;
+
+    loop {
+        let bytes_read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+        if bytes_read == 0 {
+            break;
+        }
+        writer
+            .write_all(&buffer[..bytes_read])
+            .map_err(|e| e.to_string())?;
+        total += bytes_read as u64;
+    }
+
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(total)
+}
@@ -1,16 +1,174 @@
+use super::compression::find_stored_save_file;
+use super::fileutil::DEFAULT_COPY_BUFFER_SIZE;
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Read;
 use std::path::Path;
+use xxhash_rust::xxh3::Xxh3;
 
-/// Calculates the SHA-256 hash of a file.
-pub(crate) fn calculate_hash(path: &Path) -> Result<String, String> {
-    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
-    let mut hasher = Sha256::new();
-    let mut buffer = [0_u8; 8 * 1024];
+/// Algorithm assumed for stored hashes written without an `algorithm:` prefix.
+pub(crate) const DEFAULT_HASH_ALGORITHM: &str = "sha256";
+
+/// Content hash used to detect duplicate saves.
+///
+/// SHA-256 hashes are stored bare for compatibility with existing `.hash` files;
+/// the others carry an `algorithm:` prefix so they never compare equal to SHA-256.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// BLAKE3, much faster than SHA-256 and still cryptographic.
+    Blake3,
+    /// 128-bit XXH3, the fastest option; only suitable for dedup.
+    #[serde(rename = "xxhash")]
+    XxHash,
+}
+
+impl HashAlgorithm {
+    const ALL: [Self; 3] = [Self::Sha256, Self::Blake3, Self::XxHash];
+
+    /// Returns the tag recorded in front of hashes made with this algorithm.
+    pub(crate) fn tag(self) -> &'static str {
+        match self {
+            Self::Sha256 => DEFAULT_HASH_ALGORITHM,
+            Self::Blake3 => "blake3",
+            Self::XxHash => "xxh3",
+        }
+    }
+
+    /// Looks up the algorithm for a stored hash tag.
+    pub(crate) fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.tag() == tag)
+    }
+}
+
+/// Streaming state for whichever algorithm is being computed.
+enum ContentHasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    XxHash(Box<Xxh3>),
+}
+
+impl ContentHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::XxHash => Self::XxHash(Box::new(Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(bytes),
+            Self::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+            Self::XxHash(hasher) => hasher.update(bytes),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(hasher) => hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Self::XxHash(hasher) => format!("{:032x}", hasher.digest128()),
+        }
+    }
+}
+
+/// Returns the algorithm named by a stored hash's `algorithm:` prefix.
+///
+/// Legacy hashes have no prefix and were always SHA-256.
+pub(crate) fn hash_algorithm_of(stored_hash: &str) -> &str {
+    match stored_hash.split_once(':') {
+        Some((algorithm, _))
+            if !algorithm.is_empty() && algorithm.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            algorithm
+        }
+        _ => DEFAULT_HASH_ALGORITHM,
+    }
+}
+
+/// Returns the part of a stored hash after its `algorithm:` prefix, if any.
+pub(crate) fn hash_digest_of(stored_hash: &str) -> &str {
+    stored_hash
+        .strip_prefix(hash_algorithm_of(stored_hash))
+        .and_then(|rest| rest.strip_prefix(':'))
+        .unwrap_or(stored_hash)
+}
+
+/// Joins an algorithm tag and digest into the form stored in `.hash` files and the index.
+///
+/// SHA-256 stays unprefixed so hashes written before other algorithms existed still match.
+pub(crate) fn tagged_hash(tag: &str, digest: &str) -> String {
+    if tag == DEFAULT_HASH_ALGORITHM {
+        digest.to_string()
+    } else {
+        format!("{}:{}", tag, digest)
+    }
+}
+
+/// Returns true when two stored hashes use the same algorithm and digest.
+///
+/// Hashes from different algorithms never match, so switching algorithms cannot
+/// produce a false duplicate.
+pub(crate) fn hashes_match(a: &str, b: &str) -> bool {
+    hash_algorithm_of(a) == hash_algorithm_of(b) && hash_digest_of(a) == hash_digest_of(b)
+}
+
+/// Calculates the tagged hash of a file with the configured algorithm.
+pub(crate) fn calculate_hash(path: &Path, settings: &BackupSettings) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    calculate_hash_reader(file, settings)
+}
+
+/// Calculates the tagged hash of everything readable from `reader` with the configured
+/// algorithm.
+pub(crate) fn calculate_hash_reader(
+    reader: impl Read,
+    settings: &BackupSettings,
+) -> Result<String, String> {
+    let (tag, digest) =
+        hash_reader_with(settings.hash_algorithm, settings.copy_buffer_size(), reader)?;
+    Ok(tagged_hash(tag, &digest))
+}
+
+/// Calculates the tagged hash of a file with the algorithm `reference` was made with.
+///
+/// Lets a live save be compared with a stored hash whatever algorithm is configured
+/// now. Unknown algorithms fall back to SHA-256, which never matches them.
+pub(crate) fn calculate_hash_like(path: &Path, reference: &str) -> Result<String, String> {
+    let algorithm = HashAlgorithm::from_tag(hash_algorithm_of(reference)).unwrap_or_default();
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let (tag, digest) = hash_reader_with(algorithm, DEFAULT_COPY_BUFFER_SIZE, file)?;
+    Ok(tagged_hash(tag, &digest))
+}
+
+/// Hashes everything readable from `reader`, returning the algorithm tag and hex digest.
+///
+/// Reads in chunks of `buffer_size` bytes.
+pub(crate) fn hash_reader_with(
+    algorithm: HashAlgorithm,
+    buffer_size: usize,
+    mut reader: impl Read,
+) -> Result<(&'static str, String), String> {
+    let mut hasher = ContentHasher::new(algorithm);
+    let mut buffer = vec![0_u8; buffer_size.max(1)];
 
     loop {
-        let bytes_read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        let bytes_read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
         if bytes_read == 0 {
             break;
         }
@@ -18,6 +176,34 @@ pub(crate) fn calculate_hash(path: &Path) -> Result<String, String> {
         hasher.update(&buffer[..bytes_read]);
     }
 
-    let hash = hasher.finalize();
-    Ok(hash.iter().map(|byte| format!("{byte:02x}")).collect())
+    Ok((algorithm.tag(), hasher.finalize_hex()))
+}
+
+/// Combines the content hashes of every backup of a slot into a single digest.
+///
+/// Hashes are taken in folder-name order, so two machines holding the same backup
+/// set produce the same value. Backups missing a `.hash` file are hashed from disk.
+pub fn aggregate_backups_hash(
+    save_dir: &Path,
+    game_number: u32,
+    settings: &BackupSettings,
+) -> Result<String, String> {
+    let mut backups = get_backups(save_dir, true, Some(game_number), settings)?;
+    backups.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    let mut hasher = Sha256::new();
+    for backup in &backups {
+        let hash = if backup.hash.is_empty() {
+            find_stored_save_file(Path::new(&backup.path), &backup.original_filename)
+                .ok_or_else(|| format!("Backup {} has no main save file", backup.filename))?
+                .content_hash(&BackupSettings::default())?
+        } else {
+            backup.hash.clone()
+        };
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    let digest = hasher.finalize();
+    Ok(digest.iter().map(|byte| format!("{byte:02x}")).collect())
 }
@@ -0,0 +1,91 @@
+use super::create::{next_folder_name, write_hash_file};
+use super::data::read_source_metadata;
+use super::fileutil::copy_file;
+use super::hashing::calculate_hash;
+use super::index::BackupStore;
+use super::listing::get_backups;
+use super::manifest::{write_manifest, BackupManifest};
+use super::mirror::mirror_new_backup;
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Imports loose legacy save files from a flat folder as regular backups.
+///
+/// Each `gamesave_N*.sav` file becomes its own backup folder named per the configured
+/// naming scheme, with a computed `.hash`. Files whose content already exists
+/// as a backup for the same slot are skipped. Returns the number of backups created.
+pub fn import_legacy_saves(
+    source_dir: &Path,
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    if !source_dir.is_dir() {
+        return Err(format!("Source directory does not exist: {:?}", source_dir));
+    }
+    if !save_dir.is_dir() {
+        return Err(format!("Save directory does not exist: {:?}", save_dir));
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut known: HashSet<(u32, String)> = get_backups(save_dir, true, None, settings)?
+        .into_iter()
+        .map(|b| (b.game_number, b.hash))
+        .collect();
+
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(source_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(game_number) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(filename_utils::parse_legacy_save_filename)
+        else {
+            continue;
+        };
+        candidates.push((game_number, path));
+    }
+    candidates.sort();
+
+    let mut imported = 0;
+    for (game_number, path) in candidates {
+        let hash = calculate_hash(&path, settings)?;
+        if !known.insert((game_number, hash.clone())) {
+            log::info!("Skipping legacy save {:?}: duplicate content", path);
+            continue;
+        }
+
+        let source = read_source_metadata(&path)?;
+        let folder_name = next_folder_name(
+            &store.root,
+            &mut store.index,
+            game_number,
+            source.modified_dt,
+            settings,
+        );
+        let target_dir = store.root.join(&folder_name);
+        fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+        copy_file(
+            &path,
+            &target_dir.join(format!("gamesave_{}.sav", game_number)),
+            settings.copy_buffer_size(),
+        )?;
+        write_hash_file(&target_dir, &hash)?;
+        write_manifest(&target_dir, &BackupManifest::new(Some(source.modified_dt)))?;
+        mirror_new_backup(&target_dir, save_dir, settings);
+
+        log::info!("Imported legacy save {:?} as {}", path, folder_name);
+        imported += 1;
+    }
+
+    store.save()?;
+    Ok(imported)
+}
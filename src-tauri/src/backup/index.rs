@@ -1,6 +1,10 @@
-use super::common::{BACKUP_DIR_NAME, INDEX_FILE_NAME};
+use super::common::{
+    BACKUP_DIR_NAME, INDEX_FILE_NAME, SAVE_DIR_FILE_NAME, SAVE_PATH_NOT_A_DIRECTORY,
+};
+use super::mirror::save_key;
+use super::settings::BackupSettings;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -9,14 +13,117 @@ pub(crate) struct BackupIndex {
     pub(crate) games: HashMap<u32, IndexEntry>,
     #[serde(default)]
     pub(crate) notes: HashMap<String, String>,
+    /// Normalized tags per backup, keyed like `notes`.
+    #[serde(default)]
+    pub(crate) tags: HashMap<String, Vec<String>>,
+    /// Labeled session snapshots mapping a label to the backup folders it groups.
+    #[serde(default)]
+    pub(crate) sessions: HashMap<String, Vec<String>>,
+    /// Last counter handed out per game slot for counter-named backup folders.
+    #[serde(default)]
+    pub(crate) counters: HashMap<u32, u32>,
+    /// Backups marked as favorites, keyed like `notes`; unlike a lock this does not affect cleanup.
+    #[serde(default)]
+    pub(crate) favorites: HashSet<String>,
 }
 
 impl BackupIndex {
+    /// Resolves the key under which a backup's metadata is stored.
+    ///
+    /// Backups with a manifest are keyed by their stable ID so metadata survives
+    /// folder renames; legacy backups fall back to their folder name.
+    pub(crate) fn metadata_key(folder_name: &str, id: Option<&str>) -> String {
+        id.unwrap_or(folder_name).to_string()
+    }
+
+    /// Returns the note for a backup, checking its ID before the legacy folder-name key.
+    pub(crate) fn note_for(&self, folder_name: &str, id: Option<&str>) -> Option<&String> {
+        id.and_then(|id| self.notes.get(id))
+            .or_else(|| self.notes.get(folder_name))
+    }
+
+    /// Returns the tags for a backup, checking its ID before the legacy folder-name key.
+    pub(crate) fn tags_for(&self, folder_name: &str, id: Option<&str>) -> Option<&Vec<String>> {
+        id.and_then(|id| self.tags.get(id))
+            .or_else(|| self.tags.get(folder_name))
+    }
+
+    /// Returns true if the backup is a favorite, checking its ID before the legacy folder-name key.
+    pub(crate) fn is_favorite(&self, folder_name: &str, id: Option<&str>) -> bool {
+        id.is_some_and(|id| self.favorites.contains(id)) || self.favorites.contains(folder_name)
+    }
+
+    /// Moves every index entry keyed by a backup's folder name to its new name.
+    ///
+    /// Entries keyed by the backup's stable ID need no change.
+    pub(crate) fn rename_folder(&mut self, old_name: &str, new_name: &str) {
+        if let Some(note) = self.notes.remove(old_name) {
+            self.notes.insert(new_name.to_string(), note);
+        }
+        if let Some(tags) = self.tags.remove(old_name) {
+            self.tags.insert(new_name.to_string(), tags);
+        }
+        if self.favorites.remove(old_name) {
+            self.favorites.insert(new_name.to_string());
+        }
+        for entry in self.games.values_mut() {
+            if entry.last_backup_path == old_name {
+                entry.last_backup_path = new_name.to_string();
+            }
+        }
+        for folders in self.sessions.values_mut() {
+            for folder in folders.iter_mut().filter(|folder| *folder == old_name) {
+                *folder = new_name.to_string();
+            }
+        }
+    }
+
+    /// Drops entries for backup folders that no longer exist, returning how many went.
+    ///
+    /// Notes, tags, favorites, and session members survive when their key is in `kept`,
+    /// which holds the folder names and IDs of every backup still on disk. A slot's dedup
+    /// entry survives only when its last backup is in `listed`, the folders that are
+    /// still listed; entries that only record an indexed save are kept.
+    pub(crate) fn compact(&mut self, kept: &HashSet<String>, listed: &HashSet<String>) -> usize {
+        let before = self.entry_count();
+        self.notes.retain(|key, _| kept.contains(key));
+        self.tags.retain(|key, _| kept.contains(key));
+        self.favorites.retain(|key| kept.contains(key));
+        for folders in self.sessions.values_mut() {
+            folders.retain(|folder| kept.contains(folder));
+        }
+        self.sessions.retain(|_, folders| !folders.is_empty());
+        self.games.retain(|_, entry| {
+            entry.last_backup_path.is_empty() || listed.contains(&entry.last_backup_path)
+        });
+        before - self.entry_count()
+    }
+
+    /// Counts the entries `compact` can remove.
+    fn entry_count(&self) -> usize {
+        self.notes.len()
+            + self.tags.len()
+            + self.favorites.len()
+            + self.sessions.values().map(Vec::len).sum::<usize>()
+            + self.games.len()
+    }
+
     /// Removes all index entries associated with a deleted backup folder.
-    pub(crate) fn prune_deleted(&mut self, folder_name: &str) {
+    pub(crate) fn prune_deleted(&mut self, folder_name: &str, id: Option<&str>) {
+        if let Some(id) = id {
+            self.notes.remove(id);
+            self.tags.remove(id);
+            self.favorites.remove(id);
+        }
         self.notes.remove(folder_name);
+        self.tags.remove(folder_name);
+        self.favorites.remove(folder_name);
         self.games
             .retain(|_, entry| entry.last_backup_path != folder_name);
+        for folders in self.sessions.values_mut() {
+            folders.retain(|folder| folder != folder_name);
+        }
+        self.sessions.retain(|_, folders| !folders.is_empty());
     }
 }
 
@@ -25,18 +132,71 @@ pub(crate) struct IndexEntry {
     pub(crate) last_hash: String,
     pub(crate) last_source_size: u64,
     pub(crate) last_source_modified: u128, // Unix timestamp in nanoseconds
-    pub(crate) last_backup_path: String,   // Relative folder name of the last backup
+    pub(crate) last_backup_path: String, // Relative folder name of the last backup; empty if only indexed
+    #[serde(default)]
+    pub(crate) last_verified: u32, // Metadata-only matches since the hash was last recomputed
+}
+
+/// Fails with a `SavePathNotADirectory` error when the save path exists but is not a directory.
+///
+/// Sync tools can replace a save folder with a file of the same name; catching it here
+/// avoids a confusing failure from creating the backup directory underneath it.
+pub(crate) fn ensure_save_dir_is_directory(save_dir: &Path) -> Result<(), String> {
+    if save_dir.exists() && !save_dir.is_dir() {
+        return Err(format!(
+            "{}: save path {:?} exists but is not a directory",
+            SAVE_PATH_NOT_A_DIRECTORY, save_dir
+        ));
+    }
+    Ok(())
+}
+
+/// Resolves where the backups of `save_dir` live given an optional override directory.
+///
+/// With an override, each save directory gets its own `<override>/<save-key>` folder
+/// so several save paths can share one override without mixing their backups.
+pub(crate) fn resolve_backup_root(save_dir: &Path, override_root: Option<&Path>) -> PathBuf {
+    match override_root {
+        Some(root) => root.join(save_key(save_dir)),
+        None => save_dir.join(BACKUP_DIR_NAME),
+    }
+}
+
+/// Returns the backup root for `save_dir` under `settings`, without creating it.
+pub(crate) fn backup_root_for(save_dir: &Path, settings: &BackupSettings) -> PathBuf {
+    resolve_backup_root(save_dir, settings.backup_root_override.as_deref())
 }
 
 /// Ensures the backup root directory exists and returns its path.
-pub(crate) fn ensure_backup_root(save_dir: &Path) -> Result<PathBuf, String> {
-    let backup_root = save_dir.join(BACKUP_DIR_NAME);
+///
+/// A root outside the save directory records the save path in `.save_dir` so the
+/// index can still be rebuilt from it.
+pub(crate) fn ensure_backup_root(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<PathBuf, String> {
+    ensure_save_dir_is_directory(save_dir)?;
+    let backup_root = backup_root_for(save_dir, settings);
     if !backup_root.exists() {
         fs::create_dir_all(&backup_root).map_err(|e| e.to_string())?;
     }
+    if backup_root != save_dir.join(BACKUP_DIR_NAME) {
+        let marker = backup_root.join(SAVE_DIR_FILE_NAME);
+        if !marker.exists() {
+            fs::write(&marker, save_dir.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+        }
+    }
     Ok(backup_root)
 }
 
+/// Returns the save directory whose backups are stored in `backup_root`.
+fn save_dir_of(backup_root: &Path) -> Option<PathBuf> {
+    if let Ok(recorded) = fs::read_to_string(backup_root.join(SAVE_DIR_FILE_NAME)) {
+        return Some(PathBuf::from(recorded.trim()));
+    }
+    backup_root.parent().map(Path::to_path_buf)
+}
+
 /// Wrapper for backup index operations.
 pub(crate) struct BackupStore {
     pub(crate) root: PathBuf,
@@ -45,16 +205,19 @@ pub(crate) struct BackupStore {
 
 impl BackupStore {
     /// Initializes the backup store, creating the backup directory if it does not exist.
-    pub(crate) fn new(save_dir: &Path) -> Result<Self, String> {
-        let root = ensure_backup_root(save_dir)?;
+    pub(crate) fn new(save_dir: &Path, settings: &BackupSettings) -> Result<Self, String> {
+        let root = ensure_backup_root(save_dir, settings)?;
         let index = load_index(&root);
         Ok(Self { root, index })
     }
 
     /// Loads the backup store if the backup directory exists.
     /// Does NOT create the directory if it's missing.
-    pub(crate) fn load_if_exists(save_dir: &Path) -> Result<Option<Self>, String> {
-        let root = save_dir.join(BACKUP_DIR_NAME);
+    pub(crate) fn load_if_exists(
+        save_dir: &Path,
+        settings: &BackupSettings,
+    ) -> Result<Option<Self>, String> {
+        let root = backup_root_for(save_dir, settings);
         if !root.exists() {
             return Ok(None);
         }
@@ -69,22 +232,70 @@ impl BackupStore {
 }
 
 /// Loads the backup index from the given backup root directory.
-/// Returns a default index if the file is missing or invalid.
+/// Returns a default index if the file is missing or unreadable.
+///
+/// An index that is not valid JSON (e.g. truncated by a crash) is moved aside to
+/// `index.json.corrupt` and rebuilt from the backup folders, so dedup keeps working
+/// instead of every save looking new.
 pub(crate) fn load_index(backup_root: &Path) -> BackupIndex {
     let index_path = backup_root.join(INDEX_FILE_NAME);
     if index_path.exists() {
         if let Ok(content) = fs::read_to_string(&index_path) {
-            if let Ok(index) = serde_json::from_str(&content) {
-                return index;
+            match serde_json::from_str(&content) {
+                Ok(index) => return index,
+                Err(e) => {
+                    log::warn!(
+                        "Backup index {:?} is invalid ({}), rebuilding.",
+                        index_path,
+                        e
+                    );
+                    return recover_index(backup_root);
+                }
             }
         }
     }
     BackupIndex::default()
 }
 
+/// Moves an invalid index aside and rebuilds it from the backup folders.
+///
+/// Falls back to an empty index if the rebuild fails.
+fn recover_index(backup_root: &Path) -> BackupIndex {
+    let index_path = backup_root.join(INDEX_FILE_NAME);
+    let corrupt_path = backup_root.join(format!("{}.corrupt", INDEX_FILE_NAME));
+    if let Err(e) = fs::rename(&index_path, &corrupt_path) {
+        log::error!("Failed to move invalid index aside: {}", e);
+        return BackupIndex::default();
+    }
+    let Some(save_dir) = save_dir_of(backup_root) else {
+        return BackupIndex::default();
+    };
+    // Rebuild against this exact root, which sits under an override unless it is
+    // the save directory's own `.backups` folder.
+    let settings = BackupSettings {
+        backup_root_override: (backup_root != save_dir.join(BACKUP_DIR_NAME))
+            .then(|| backup_root.parent().map(Path::to_path_buf))
+            .flatten(),
+        ..BackupSettings::default()
+    };
+    if let Err(e) = super::create::rebuild_index(&save_dir, &settings) {
+        log::error!("Failed to rebuild backup index: {}", e);
+        return BackupIndex::default();
+    }
+    fs::read_to_string(&index_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
 /// Saves the given index to the backup root directory.
+///
+/// Writes `index.json.tmp` and renames it over the index, so a crash mid-write leaves
+/// the previous index intact rather than a truncated file.
 pub(crate) fn save_index(backup_root: &Path, index: &BackupIndex) -> Result<(), String> {
     let index_path = backup_root.join(INDEX_FILE_NAME);
+    let temp_path = backup_root.join(format!("{}.tmp", INDEX_FILE_NAME));
     let content = serde_json::to_string(index).map_err(|e| e.to_string())?;
-    fs::write(index_path, content).map_err(|e| e.to_string())
+    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
+    fs::rename(&temp_path, &index_path).map_err(|e| e.to_string())
 }
@@ -1,44 +1,239 @@
-use super::common::{HASH_FILE_NAME, LOCKED_FILE_NAME};
+use super::common::{ARCHIVED_FILE_NAME, HASH_FILE_NAME, LOCKED_FILE_NAME, TRASH_DIR_NAME};
+use super::compression::find_stored_save_file;
 use super::data::BackupInfo;
+use super::fileutil::folder_size;
+use super::hashing::hash_algorithm_of;
 use super::index::BackupStore;
-use crate::filename_utils;
+use super::manifest::{read_manifest, BackupManifest};
+use super::settings::BackupSettings;
+use crate::filename_utils::{self, BackupFolderInfo};
+use chrono::{DateTime, Local};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
+use std::time::SystemTime;
 
 /// Lists all backups available in the .backups directory.
 pub fn get_backups(
     save_dir: &Path,
     include_hash: bool,
     game_filter: Option<u32>,
+    settings: &BackupSettings,
 ) -> Result<Vec<BackupInfo>, String> {
-    let store = match BackupStore::load_if_exists(save_dir)? {
+    let mut backups = Vec::new();
+    scan_backups_streaming(save_dir, include_hash, game_filter, settings, |info| {
+        backups.push(info)
+    })?;
+
+    // Sort by modified desc; counter-named backups of the same save tie on time,
+    // so fall back to the zero-padded folder name to keep the newer counter first.
+    backups.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| b.filename.cmp(&a.filename))
+    });
+
+    Ok(backups)
+}
+
+/// Walks the .backups directory, handing each backup to `on_backup` as soon as it is parsed.
+///
+/// Backups arrive in directory order, not sorted. With `game_filter` set, folders of
+/// other games are skipped by name before anything inside them is read.
+/// Returns the number of backups found.
+pub fn scan_backups_streaming(
+    save_dir: &Path,
+    include_hash: bool,
+    game_filter: Option<u32>,
+    settings: &BackupSettings,
+    mut on_backup: impl FnMut(BackupInfo),
+) -> Result<usize, String> {
+    let store = match BackupStore::load_if_exists(save_dir, settings)? {
         Some(s) => s,
-        None => return Ok(Vec::new()),
+        None => return Ok(0),
     };
 
-    let mut backups = Vec::new();
-
+    let mut count = 0;
     for entry in fs::read_dir(&store.root).map_err(|e| e.to_string())? {
         let entry = entry.map_err(|e| e.to_string())?;
+        let folder_name = entry.file_name().to_string_lossy().to_string();
+        // Deleted backups keep parseable names inside the trash; never list them.
+        if folder_name == TRASH_DIR_NAME || !matches_game_filter(&folder_name, game_filter) {
+            continue;
+        }
         let path = entry.path();
 
         if path.is_dir() {
-            let folder_name = entry.file_name().to_string_lossy().to_string();
             if let Some(mut info) =
                 backup_info_from_folder(&path, &folder_name, save_dir, include_hash, game_filter)?
             {
-                if let Some(note) = store.index.notes.get(&info.filename) {
+                if let Some(note) = store.index.note_for(&info.filename, info.id.as_deref()) {
                     info.note = Some(note.clone());
                 }
-                backups.push(info);
+                if let Some(tags) = store.index.tags_for(&info.filename, info.id.as_deref()) {
+                    info.tags = tags.clone();
+                }
+                info.favorite = store.index.is_favorite(&info.filename, info.id.as_deref());
+                count += 1;
+                on_backup(info);
             }
         }
     }
 
-    // Sort by modified desc
-    backups.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(count)
+}
 
-    Ok(backups)
+/// Everything the details panel shows for a single backup.
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupDetails {
+    /// The display name of the backup folder.
+    pub filename: String,
+    /// The game number (0-based).
+    pub game_number: u32,
+    /// An optional user-provided note.
+    pub note: Option<String>,
+    /// Whether the backup is locked against auto-deletion.
+    pub locked: bool,
+    /// Normalized user tags.
+    pub tags: Vec<String>,
+    /// Whether the backup is marked as a favorite.
+    pub favorite: bool,
+    /// The size of the main save file in bytes.
+    pub size: u64,
+    /// The content hash of the main save file.
+    pub hash: String,
+    /// The algorithm that produced `hash`.
+    pub hash_algorithm: String,
+    /// The backup timestamp (ISO 8601).
+    pub modified: String,
+}
+
+/// Reads the details of one backup folder directly, without listing the whole directory.
+pub fn get_backup_details(
+    save_dir: &Path,
+    backup_folder: &Path,
+    settings: &BackupSettings,
+) -> Result<BackupDetails, String> {
+    let folder_name = backup_folder
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?;
+    let info = backup_info_from_folder(backup_folder, &folder_name, save_dir, true, None)?
+        .ok_or_else(|| format!("Not a valid backup folder: {}", folder_name))?;
+
+    let store = BackupStore::load_if_exists(save_dir, settings)?;
+    let index = store.as_ref().map(|s| &s.index);
+    let note = index.and_then(|i| i.note_for(&info.filename, info.id.as_deref()).cloned());
+    let tags = index
+        .and_then(|i| i.tags_for(&info.filename, info.id.as_deref()).cloned())
+        .unwrap_or_default();
+
+    Ok(BackupDetails {
+        favorite: index.is_some_and(|i| i.is_favorite(&info.filename, info.id.as_deref())),
+        filename: info.filename,
+        game_number: info.game_number,
+        note,
+        locked: info.locked,
+        tags,
+        size: info.size,
+        hash: info.hash,
+        hash_algorithm: info.hash_algorithm,
+        modified: info.modified,
+    })
+}
+
+/// Lists backups that carry no user annotation, as candidates for cleanup.
+///
+/// A backup counts as annotated when it is locked, a favorite, has a note or tags, or
+/// belongs to a labeled session snapshot. Results keep the newest-first order of `get_backups`.
+pub fn get_unannotated_backups(
+    save_dir: &Path,
+    game_filter: Option<u32>,
+    settings: &BackupSettings,
+) -> Result<Vec<BackupInfo>, String> {
+    let Some(store) = BackupStore::load_if_exists(save_dir, settings)? else {
+        return Ok(Vec::new());
+    };
+    let in_session = |folder_name: &str| {
+        store
+            .index
+            .sessions
+            .values()
+            .any(|folders| folders.iter().any(|f| f == folder_name))
+    };
+
+    Ok(get_backups(save_dir, false, game_filter, settings)?
+        .into_iter()
+        .filter(|b| {
+            !b.locked
+                && !b.favorite
+                && b.note.is_none()
+                && b.tags.is_empty()
+                && !in_session(&b.filename)
+        })
+        .collect())
+}
+
+/// A page of backups with the totals needed for "showing X of Y" displays.
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupPage {
+    /// Backups within the requested window, newest first.
+    pub backups: Vec<BackupInfo>,
+    /// Total number of backups matching the filter across all pages.
+    pub total: usize,
+    /// Summed on-disk size in bytes of the backup folders on this page.
+    pub page_size_bytes: u64,
+}
+
+/// Lists one page of backups along with the total count and the page's disk usage.
+pub fn get_backups_page(
+    save_dir: &Path,
+    offset: usize,
+    limit: usize,
+    game_filter: Option<u32>,
+    settings: &BackupSettings,
+) -> Result<BackupPage, String> {
+    let all = get_backups(save_dir, false, game_filter, settings)?;
+    let total = all.len();
+    let backups: Vec<BackupInfo> = all.into_iter().skip(offset).take(limit).collect();
+
+    let mut page_size_bytes = 0_u64;
+    for backup in &backups {
+        page_size_bytes += folder_size(Path::new(&backup.path))?;
+    }
+
+    Ok(BackupPage {
+        backups,
+        total,
+        page_size_bytes,
+    })
+}
+
+/// Resolves when a backup was taken.
+///
+/// Timestamp-named folders carry it in their name. Counter-named folders use the
+/// time recorded in their manifest, falling back to the folder's modification time.
+pub(crate) fn resolve_backup_timestamp(
+    path: &Path,
+    info: &BackupFolderInfo,
+    manifest: Option<&BackupManifest>,
+) -> DateTime<Local> {
+    info.timestamp
+        .or_else(|| manifest.and_then(BackupManifest::created_at))
+        .unwrap_or_else(|| {
+            let modified = fs::metadata(path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            DateTime::<Local>::from(modified)
+        })
+}
+
+/// Returns whether a folder name belongs to the filtered game, or any game without a filter.
+fn matches_game_filter(folder_name: &str, game_filter: Option<u32>) -> bool {
+    game_filter.is_none_or(|filter| {
+        filename_utils::parse_backup_folder_name(folder_name)
+            .is_some_and(|info| info.game_number == filter)
+    })
 }
 
 /// Builds a BackupInfo from a backup folder if it matches the naming contract.
@@ -60,30 +255,35 @@ pub(crate) fn backup_info_from_folder(
     }
 
     let main_filename = format!("gamesave_{}.sav", info.game_number);
-    let main_file_path = path.join(&main_filename);
-    if !main_file_path.exists() {
+    let Some(main_file) = find_stored_save_file(path, &main_filename) else {
         log::warn!(
             "Skipping backup folder {:?} because main save is missing.",
             path
         );
         return Ok(None);
-    }
+    };
 
-    let size = fs::metadata(&main_file_path)
-        .map_err(|e| e.to_string())?
-        .len();
+    let size = main_file.original_size()?;
 
-    let locked = path.join(LOCKED_FILE_NAME).exists();
+    // Archived implies locked for every cleanup path.
+    let archived = path.join(ARCHIVED_FILE_NAME).exists();
+    let locked = archived || path.join(LOCKED_FILE_NAME).exists();
+    let stored_hash = fs::read_to_string(path.join(HASH_FILE_NAME))
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default();
+    let hash_algorithm = hash_algorithm_of(&stored_hash).to_string();
     let hash = if include_hash {
-        fs::read_to_string(path.join(HASH_FILE_NAME))
-            .map(|h| h.trim().to_string())
-            .unwrap_or_default()
+        stored_hash
     } else {
         String::new()
     };
 
+    let manifest = read_manifest(path);
+    let modified = resolve_backup_timestamp(path, &info, manifest.as_ref());
+
     Ok(Some(BackupInfo {
         path: path.to_string_lossy().to_string(),
+        id: manifest.map(|m| m.id),
         filename: folder_name.to_string(),
         original_filename: main_filename.clone(),
         original_path: save_dir
@@ -91,10 +291,15 @@ pub(crate) fn backup_info_from_folder(
             .to_string_lossy()
             .to_string(),
         size,
-        modified: info.timestamp.to_rfc3339(),
+        modified: modified.to_rfc3339(),
         game_number: info.game_number,
         locked,
+        archived,
         hash,
+        hash_algorithm,
         note: None,
+        tags: Vec::new(),
+        favorite: false,
+        metadata: None,
     }))
 }
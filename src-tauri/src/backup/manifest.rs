@@ -0,0 +1,63 @@
+use super::common::MANIFEST_FILE_NAME;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Per-backup metadata stored alongside the save files.
+///
+/// Lives inside the backup folder so it follows the folder through renames.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct BackupManifest {
+    /// Stable unique identifier assigned when the backup is created.
+    pub(crate) id: String,
+    /// When the backed-up save was last modified (RFC 3339).
+    ///
+    /// Counter-named folders carry no timestamp, so this is their only source of
+    /// chronological order. Absent in manifests written before it was introduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) created_at: Option<String>,
+}
+
+impl BackupManifest {
+    /// Creates a manifest with a freshly generated ID and optional creation time.
+    pub(crate) fn new(created_at: Option<DateTime<Local>>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: created_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+
+    /// Parses the recorded creation time, if present and valid.
+    pub(crate) fn created_at(&self) -> Option<DateTime<Local>> {
+        let raw = self.created_at.as_deref()?;
+        DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|dt| dt.with_timezone(&Local))
+    }
+}
+
+/// Reads the manifest from a backup folder, if present and valid.
+pub(crate) fn read_manifest(backup_folder: &Path) -> Option<BackupManifest> {
+    let content = fs::read_to_string(backup_folder.join(MANIFEST_FILE_NAME)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Writes the manifest into a backup folder.
+pub(crate) fn write_manifest(
+    backup_folder: &Path,
+    manifest: &BackupManifest,
+) -> Result<(), String> {
+    let content = serde_json::to_string(manifest).map_err(|e| e.to_string())?;
+    fs::write(backup_folder.join(MANIFEST_FILE_NAME), content).map_err(|e| e.to_string())
+}
+
+/// Returns the folder's manifest, creating one for legacy backups that lack it.
+pub(crate) fn ensure_manifest(backup_folder: &Path) -> Result<BackupManifest, String> {
+    if let Some(manifest) = read_manifest(backup_folder) {
+        return Ok(manifest);
+    }
+    let manifest = BackupManifest::new(None);
+    write_manifest(backup_folder, &manifest)?;
+    Ok(manifest)
+}
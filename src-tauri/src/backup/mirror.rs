@@ -0,0 +1,178 @@
+use super::common::SAVE_DIR_FILE_NAME;
+use super::fileutil::copy_file;
+use super::settings::BackupSettings;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Derives the per-save-directory subfolder name used inside the mirror and backup root override.
+///
+/// Combines the save directory's name with a short hash of its full path so
+/// different save directories never share a mirror folder.
+pub(crate) fn save_key(save_dir: &Path) -> String {
+    let name = save_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "saves".to_string());
+    let digest = Sha256::digest(save_dir.to_string_lossy().as_bytes());
+    let short: String = digest[..4].iter().map(|b| format!("{b:02x}")).collect();
+    format!("{}-{}", name, short)
+}
+
+/// Copies a backup folder into `<mirror_root>/<save-key>/<folder>`.
+pub(crate) fn mirror_backup_to(
+    backup_folder: &Path,
+    save_dir: &Path,
+    mirror_root: &Path,
+    buffer_size: usize,
+) -> Result<PathBuf, String> {
+    let folder_name = backup_folder
+        .file_name()
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?;
+    let target = mirror_root.join(save_key(save_dir)).join(folder_name);
+    fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+
+    for entry in fs::read_dir(backup_folder).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() {
+            if let Some(name) = path.file_name() {
+                copy_file(&path, &target.join(name), buffer_size)?;
+            }
+        }
+    }
+    Ok(target)
+}
+
+/// Mirrors a newly created backup when a mirror directory is configured.
+///
+/// Failures are logged as warnings only; the primary backup is already complete.
+pub(crate) fn mirror_new_backup(backup_folder: &Path, save_dir: &Path, settings: &BackupSettings) {
+    let Some(mirror_root) = settings.mirror_directory.as_deref() else {
+        return;
+    };
+    if let Err(e) = mirror_backup_to(
+        backup_folder,
+        save_dir,
+        mirror_root,
+        settings.copy_buffer_size(),
+    ) {
+        log::warn!(
+            "Failed to mirror backup {:?} to {:?}: {}",
+            backup_folder,
+            mirror_root,
+            e
+        );
+    }
+}
+
+/// Moves backups from one save directory's key folder under `root` to another's.
+///
+/// Used when the save path changes so existing mirrored or overridden backups follow it.
+/// If the new key folder already exists the backups are merged into it; entries already
+/// present there are left under the old key. A `.save_dir` marker in the new key folder
+/// is rewritten to the new save path. Returns the number of backup folders moved.
+pub(crate) fn remap_central_key_in(
+    mirror_root: &Path,
+    old_save_dir: &Path,
+    new_save_dir: &Path,
+) -> Result<usize, String> {
+    let old_dir = mirror_root.join(save_key(old_save_dir));
+    let new_dir = mirror_root.join(save_key(new_save_dir));
+    if old_dir == new_dir {
+        return Ok(0);
+    }
+    if !old_dir.is_dir() {
+        return Err(format!("No mirrored backups found for {:?}", old_save_dir));
+    }
+
+    if !new_dir.exists() {
+        let moved = fs::read_dir(&old_dir)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .filter(|entry| is_backup_folder(&entry.path()))
+            .count();
+        fs::rename(&old_dir, &new_dir).map_err(|e| e.to_string())?;
+        rewrite_save_dir_marker(&new_dir, new_save_dir)?;
+        return Ok(moved);
+    }
+
+    let mut moved = 0;
+    for entry in fs::read_dir(&old_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let target = new_dir.join(name);
+        if target.exists() {
+            log::warn!("Keeping {:?}: {:?} already exists", path, target);
+            continue;
+        }
+        fs::rename(&path, &target).map_err(|e| e.to_string())?;
+        if is_backup_folder(&target) {
+            moved += 1;
+        }
+    }
+    // Only succeeds once every entry has moved, leaving conflicts under the old key.
+    let _ = fs::remove_dir(&old_dir);
+    rewrite_save_dir_marker(&new_dir, new_save_dir)?;
+    Ok(moved)
+}
+
+/// Returns true for a backup folder, as opposed to the index, markers, trash or quarantine.
+fn is_backup_folder(path: &Path) -> bool {
+    path.is_dir()
+        && !path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Points the `.save_dir` marker of a key folder at its new save directory, if it has one.
+fn rewrite_save_dir_marker(key_dir: &Path, save_dir: &Path) -> Result<(), String> {
+    let marker = key_dir.join(SAVE_DIR_FILE_NAME);
+    if marker.exists() {
+        fs::write(&marker, save_dir.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Moves keyed backups to follow a save path change.
+///
+/// Covers both the mirror directory and the backup root override; a root without a
+/// folder for the old save path is skipped. Fails when neither is configured or
+/// neither holds backups of the old save path.
+pub fn remap_central_key(
+    old_save_dir: &Path,
+    new_save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    let mut roots: Vec<&Path> = Vec::new();
+    for root in [
+        settings.mirror_directory.as_deref(),
+        settings.backup_root_override.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    if roots.is_empty() {
+        return Err("No mirror directory or backup root override is configured".to_string());
+    }
+
+    let old_key = save_key(old_save_dir);
+    let mut found = false;
+    let mut moved = 0;
+    for root in roots {
+        if !root.join(&old_key).is_dir() {
+            continue;
+        }
+        found = true;
+        moved += remap_central_key_in(root, old_save_dir, new_save_dir)?;
+    }
+    if !found {
+        return Err(format!("No keyed backups found for {:?}", old_save_dir));
+    }
+    Ok(moved)
+}
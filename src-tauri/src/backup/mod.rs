@@ -1,23 +1,94 @@
+pub mod activity;
+pub mod catalog;
 pub mod cleanup;
+pub mod clock;
 pub mod common;
+pub mod compression;
 pub mod create;
 pub mod data;
+pub mod delta;
+pub mod diff;
+pub mod fileutil;
 pub mod hashing;
+pub mod import;
 pub mod index;
 pub mod listing;
+pub mod manifest;
+pub mod mirror;
 pub mod notes;
+pub mod owner;
+pub mod quarantine;
+pub mod rename;
 pub mod restore;
+pub mod retention;
+pub mod save_format;
+pub mod session;
+pub mod settings;
+pub mod similarity;
+pub mod storage;
+pub mod trash;
+pub mod verify;
 
 #[cfg(test)]
 mod tests;
 
 // Re-export public API to maintain compatibility or ease of use
-pub use cleanup::{delete_backup_folder, delete_backups_batch};
+pub use activity::{backup_activity_histogram, get_backup_time_bounds, DayCount, TimeBounds};
+pub use catalog::{export_backup_catalog, CatalogFormat};
+pub use cleanup::{
+    compact_index, delete_backup_folder, delete_backups_batch, find_dangling_locks,
+    find_incomplete_backups, preview_delete_backups_batch, remove_dangling_locks,
+    remove_incomplete_backups, BatchDeleteProgress,
+};
+pub use clock::{check_clock_skew, ClockSkewWarning};
+pub use compression::{compress_backup, decompress_backup};
+pub use create::{
+    backup_all_now, perform_backup_with_progress, prime_index, rebuild_index,
+    trigger_manual_backup, BackupAllSummary, BackupOutcome, NamingScheme,
+};
 pub use data::BackupInfo;
-pub use listing::get_backups;
-pub use notes::{set_backup_lock, set_backup_note};
-pub use restore::restore_backup;
+pub use diff::{compare_backups, diff_backup_sets, BackupComparison, BackupSetDiff};
+pub use hashing::{aggregate_backups_hash, HashAlgorithm};
+pub use import::import_legacy_saves;
+pub use listing::{
+    get_backup_details, get_backups, get_backups_page, get_unannotated_backups,
+    scan_backups_streaming, BackupDetails, BackupPage,
+};
+pub use mirror::remap_central_key;
+pub use notes::{
+    add_tag_batch, remove_tag_batch, set_backup_archived, set_backup_lock, set_backup_note,
+    set_backup_tags, toggle_backup_favorite,
+};
+pub use quarantine::{
+    list_quarantined, quarantine_backup, release_from_quarantine, QuarantinedBackup,
+};
+pub use rename::rename_backup;
+pub use restore::{
+    plan_restore_full, prepare_restore, prepare_restore_to_slot, restore_backup_cancellable,
+    restore_backup_to_slot, FullRestorePlan, PendingRestores, PreparedRestore, RestoreCancelToken,
+    RestoreConfirmation, RestoreRequest,
+};
+pub use retention::{suggest_retention, RetentionSuggestion};
+pub use save_format::{attach_save_metadata, read_save_metadata, SaveMetadata};
+pub use session::{
+    create_session_snapshot, prepare_session_restore, restore_session, session_restore_request,
+};
+pub use settings::BackupSettings;
+pub use similarity::{
+    byte_diff_count, diff_backup_against_current, rank_backups_by_similarity, ByteDiff,
+    CurrentSaveDiff, RankedBackup,
+};
+pub use storage::{get_backup_storage_stats, BackupStorageStats};
+pub use trash::{empty_trash, list_trash, restore_from_trash, TrashedBackup};
+pub use verify::{
+    audit_timestamp_consistency, verify_all_backups, verify_backup, BackupVerification,
+    TimestampAudit, VerifyStatus,
+};
 
 // Internal exports needed for other modules
-pub(crate) use create::perform_backup_for_game_internal;
-pub(crate) use index::{ensure_backup_root, load_index, save_index};
+pub(crate) use create::{index_live_saves, perform_backup_for_game_internal, BackupOptions};
+pub(crate) use data::discover_save_slots;
+pub(crate) use index::{
+    backup_root_for, ensure_backup_root, ensure_save_dir_is_directory, load_index, save_index,
+};
+pub(crate) use owner::{claim_backup_dir, release_backup_dir};
@@ -1,5 +1,7 @@
-use super::common::LOCKED_FILE_NAME;
-use super::index::BackupStore;
+use super::common::{ARCHIVED_FILE_NAME, LOCKED_FILE_NAME, NOTE_FILE_NAME};
+use super::index::{BackupIndex, BackupStore};
+use super::manifest::ensure_manifest;
+use super::settings::BackupSettings;
 use std::fs;
 use std::path::Path;
 
@@ -22,27 +24,231 @@ pub fn set_backup_lock(backup_folder_path: &Path, locked: bool) -> Result<(), St
     Ok(())
 }
 
+/// Sets or unsets the archived status for a backup folder.
+///
+/// Archived implies locked: cleanup treats an archived backup as locked whatever its
+/// `.locked` marker says. Beyond that, it is invisible to the backup limit, age and
+/// size pruning, and batch deletes, even ones that delete locked backups, so only an
+/// explicit single-folder delete can remove it.
+pub fn set_backup_archived(backup_folder_path: &Path, archived: bool) -> Result<(), String> {
+    if !backup_folder_path.exists() {
+        return Err("Backup folder does not exist".to_string());
+    }
+
+    let archived_file = backup_folder_path.join(ARCHIVED_FILE_NAME);
+
+    if archived {
+        if !archived_file.exists() {
+            fs::write(&archived_file, "").map_err(|e| e.to_string())?;
+        }
+    } else if archived_file.exists() {
+        fs::remove_file(&archived_file).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Marks a backup as a favorite, or clears the mark if it already is one.
+///
+/// Favorites are keyed like notes, so they survive folder renames and, unlike a lock,
+/// do not protect the backup from cleanup. Returns the new state.
+pub fn toggle_backup_favorite(
+    save_dir: &Path,
+    folder_name: &str,
+    settings: &BackupSettings,
+) -> Result<bool, String> {
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let backup_folder = store.root.join(folder_name);
+    if !backup_folder.is_dir() {
+        return Err(format!("Backup {} does not exist", folder_name));
+    }
+
+    let id = ensure_manifest(&backup_folder)?.id;
+    let favorite = !store.index.is_favorite(folder_name, Some(&id));
+    if favorite {
+        store
+            .index
+            .favorites
+            .insert(BackupIndex::metadata_key(folder_name, Some(&id)));
+    } else {
+        store.index.favorites.remove(folder_name);
+        store.index.favorites.remove(&id);
+    }
+
+    store.save()?;
+    Ok(favorite)
+}
+
+/// Normalizes a tag to lowercase with single spaces, rejecting empty tags.
+pub(crate) fn normalize_tag(tag: &str) -> Result<String, String> {
+    let normalized = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if normalized.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    Ok(normalized)
+}
+
+/// Adds a tag to each listed backup, returning how many backups gained it.
+///
+/// Backups that already carry the tag or whose folder is missing are skipped.
+pub fn add_tag_batch(
+    save_dir: &Path,
+    folder_names: &[String],
+    tag: String,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    update_tags(save_dir, folder_names, &tag, settings, |tags, tag| {
+        if tags.iter().any(|t| t == tag) {
+            return false;
+        }
+        tags.push(tag.to_string());
+        true
+    })
+}
+
+/// Removes a tag from each listed backup, returning how many backups lost it.
+pub fn remove_tag_batch(
+    save_dir: &Path,
+    folder_names: &[String],
+    tag: String,
+    settings: &BackupSettings,
+) -> Result<usize, String> {
+    update_tags(save_dir, folder_names, &tag, settings, |tags, tag| {
+        let before = tags.len();
+        tags.retain(|t| t != tag);
+        tags.len() != before
+    })
+}
+
+/// Replaces the tags of a single backup with `tags`.
+///
+/// Tags are normalized and deduplicated, keeping their order. An empty list removes
+/// the backup's tags from the index.
+pub fn set_backup_tags(
+    save_dir: &Path,
+    folder_name: &str,
+    tags: Vec<String>,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = normalize_tag(&tag)?;
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+
+    // Key on the stable ID when the folder exists, migrating any legacy folder-name entry.
+    let backup_folder = store.root.join(folder_name);
+    let key = if backup_folder.is_dir() {
+        let manifest = ensure_manifest(&backup_folder)?;
+        store.index.tags.remove(folder_name);
+        BackupIndex::metadata_key(folder_name, Some(&manifest.id))
+    } else {
+        folder_name.to_string()
+    };
+
+    if normalized.is_empty() {
+        store.index.tags.remove(&key);
+    } else {
+        store.index.tags.insert(key, normalized);
+    }
+
+    store.save()
+}
+
+/// Applies `update` to the tag list of each listed backup and saves the index once.
+fn update_tags(
+    save_dir: &Path,
+    folder_names: &[String],
+    tag: &str,
+    settings: &BackupSettings,
+    update: impl Fn(&mut Vec<String>, &str) -> bool,
+) -> Result<usize, String> {
+    let tag = normalize_tag(tag)?;
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut updated = 0;
+
+    for folder_name in folder_names {
+        let backup_folder = store.root.join(folder_name);
+        if !backup_folder.is_dir() {
+            log::warn!("Skipping tag update for missing backup {}", folder_name);
+            continue;
+        }
+        let manifest = ensure_manifest(&backup_folder)?;
+        let key = BackupIndex::metadata_key(folder_name, Some(&manifest.id));
+        let mut tags = store
+            .index
+            .tags
+            .remove(folder_name)
+            .or_else(|| store.index.tags.remove(&key))
+            .unwrap_or_default();
+        if update(&mut tags, &tag) {
+            updated += 1;
+        }
+        if !tags.is_empty() {
+            store.index.tags.insert(key, tags);
+        }
+    }
+
+    store.save()?;
+    Ok(updated)
+}
+
 /// Sets or updates a note for a specific backup.
+///
+/// The note is also written to a `note.txt` sidecar in the backup folder so an index
+/// rebuilt from disk can recover it.
 pub fn set_backup_note(
     save_dir: &Path,
     folder_name: &str,
     note: Option<String>,
+    settings: &BackupSettings,
 ) -> Result<(), String> {
-    let mut store = BackupStore::new(save_dir)?;
-
-    if let Some(n) = note {
-        let trimmed = n.trim();
-        if trimmed.is_empty() {
-            store.index.notes.remove(folder_name);
-        } else {
-            store
-                .index
-                .notes
-                .insert(folder_name.to_string(), trimmed.to_string());
-        }
-    } else {
+    let mut store = BackupStore::new(save_dir, settings)?;
+
+    // Key on the stable ID when the folder exists, migrating any legacy folder-name entry.
+    let backup_folder = store.root.join(folder_name);
+    let key = if backup_folder.is_dir() {
+        let manifest = ensure_manifest(&backup_folder)?;
         store.index.notes.remove(folder_name);
+        BackupIndex::metadata_key(folder_name, Some(&manifest.id))
+    } else {
+        folder_name.to_string()
+    };
+
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if backup_folder.is_dir() {
+        write_note_sidecar(&backup_folder, note.as_deref())?;
     }
+    match note {
+        Some(n) => store.index.notes.insert(key, n),
+        None => store.index.notes.remove(&key),
+    };
 
     store.save()
 }
+
+/// Writes or removes the `note.txt` sidecar of a backup folder.
+fn write_note_sidecar(backup_folder: &Path, note: Option<&str>) -> Result<(), String> {
+    let note_file = backup_folder.join(NOTE_FILE_NAME);
+    match note {
+        Some(n) => fs::write(&note_file, n).map_err(|e| e.to_string()),
+        None if note_file.exists() => fs::remove_file(&note_file).map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Reads the note stored in a backup folder's `note.txt` sidecar, if any.
+pub(crate) fn read_note_sidecar(backup_folder: &Path) -> Option<String> {
+    fs::read_to_string(backup_folder.join(NOTE_FILE_NAME))
+        .ok()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+}
@@ -0,0 +1,94 @@
+use super::common::OWNER_FILE_NAME;
+use super::index::{backup_root_for, ensure_backup_root};
+use super::settings::BackupSettings;
+use std::fs;
+use std::path::Path;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// Ownership state of a backup directory's `.owner` marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OwnerStatus {
+    /// No marker exists, or it could not be parsed.
+    Unowned,
+    /// The marker names this process.
+    OwnedBySelf,
+    /// The marker names a process that is no longer running.
+    Stale(u32),
+    /// The marker names another live instance of the app.
+    Conflict(u32),
+}
+
+/// Classifies an owner marker given the recorded PID and a liveness check.
+pub(crate) fn classify_owner(
+    recorded_pid: Option<u32>,
+    own_pid: u32,
+    is_alive: impl Fn(u32) -> bool,
+) -> OwnerStatus {
+    match recorded_pid {
+        None => OwnerStatus::Unowned,
+        Some(pid) if pid == own_pid => OwnerStatus::OwnedBySelf,
+        Some(pid) if is_alive(pid) => OwnerStatus::Conflict(pid),
+        Some(pid) => OwnerStatus::Stale(pid),
+    }
+}
+
+/// Reads the PID recorded in the backup directory's owner marker.
+fn read_owner_pid(save_dir: &Path, settings: &BackupSettings) -> Option<u32> {
+    let root = ensure_backup_root(save_dir, settings).ok()?;
+    fs::read_to_string(root.join(OWNER_FILE_NAME))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Returns true when `pid` is a running process with the same executable name as this one.
+///
+/// Matching the name guards against a recycled PID being mistaken for another instance.
+fn is_app_instance_alive(pid: u32) -> bool {
+    let own_pid = Pid::from_u32(std::process::id());
+    let other_pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[own_pid, other_pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    match (sys.process(own_pid), sys.process(other_pid)) {
+        (Some(own), Some(other)) => own.name() == other.name(),
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Claims the backup directory for this process by writing its PID to `.owner`.
+///
+/// Returns `Some(pid)` without overwriting the marker when another live instance
+/// already owns the directory; stale or missing markers are replaced.
+pub(crate) fn claim_backup_dir(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Option<u32>, String> {
+    let own_pid = std::process::id();
+    match classify_owner(
+        read_owner_pid(save_dir, settings),
+        own_pid,
+        is_app_instance_alive,
+    ) {
+        OwnerStatus::Conflict(pid) => Ok(Some(pid)),
+        OwnerStatus::OwnedBySelf => Ok(None),
+        OwnerStatus::Unowned | OwnerStatus::Stale(_) => {
+            let root = ensure_backup_root(save_dir, settings)?;
+            fs::write(root.join(OWNER_FILE_NAME), own_pid.to_string())
+                .map_err(|e| e.to_string())?;
+            Ok(None)
+        }
+    }
+}
+
+/// Removes the owner marker if it still names this process.
+pub(crate) fn release_backup_dir(save_dir: &Path, settings: &BackupSettings) {
+    if read_owner_pid(save_dir, settings) == Some(std::process::id()) {
+        let _ = fs::remove_file(backup_root_for(save_dir, settings).join(OWNER_FILE_NAME));
+    }
+}
@@ -0,0 +1,143 @@
+use super::common::{QUARANTINE_DIR_NAME, QUARANTINE_FILE_NAME};
+use super::delta::release_delta_links;
+use super::index::backup_root_for;
+use super::settings::BackupSettings;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Why and when a backup was quarantined, stored inside the quarantined folder.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct QuarantineRecord {
+    reason: String,
+    /// When the backup was quarantined (RFC 3339).
+    quarantined_at: String,
+}
+
+/// A backup folder held in quarantine.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct QuarantinedBackup {
+    pub filename: String,
+    pub path: String,
+    /// Reason given when quarantining; empty if the record is missing.
+    pub reason: String,
+    /// When the backup was quarantined (RFC 3339), if recorded.
+    pub quarantined_at: Option<String>,
+}
+
+/// Returns the quarantine directory inside a backup root.
+fn quarantine_root(backup_root: &Path) -> PathBuf {
+    backup_root.join(QUARANTINE_DIR_NAME)
+}
+
+/// Moves a backup folder into `.backups/.quarantine/`, recording the reason.
+///
+/// Quarantined backups drop out of listings, cleanup, and dedup but keep their files,
+/// notes, and tags, so a partially recoverable backup is not lost outright.
+pub fn quarantine_backup(backup_folder_path: &Path, reason: &str) -> Result<PathBuf, String> {
+    if !backup_folder_path.is_dir() {
+        return Err("Backup folder does not exist".to_string());
+    }
+    let (Some(backup_root), Some(folder_name)) =
+        (backup_folder_path.parent(), backup_folder_path.file_name())
+    else {
+        return Err("Backup folder path is invalid".to_string());
+    };
+
+    let quarantine_dir = quarantine_root(backup_root);
+    fs::create_dir_all(&quarantine_dir).map_err(|e| e.to_string())?;
+    let target = quarantine_dir.join(folder_name);
+    if target.exists() {
+        return Err(format!(
+            "A quarantined backup named {} already exists",
+            folder_name.to_string_lossy()
+        ));
+    }
+
+    release_delta_links(backup_folder_path, true)?;
+    fs::rename(backup_folder_path, &target).map_err(|e| e.to_string())?;
+    let record = QuarantineRecord {
+        reason: reason.trim().to_string(),
+        quarantined_at: Local::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+    fs::write(target.join(QUARANTINE_FILE_NAME), content).map_err(|e| e.to_string())?;
+
+    log::warn!("Quarantined backup {:?}: {}", folder_name, record.reason);
+    Ok(target)
+}
+
+/// Lists quarantined backups, most recently quarantined first.
+pub fn list_quarantined(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<QuarantinedBackup>, String> {
+    let quarantine_dir = quarantine_root(&backup_root_for(save_dir, settings));
+    if !quarantine_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut quarantined = Vec::new();
+    for entry in fs::read_dir(&quarantine_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let record = fs::read_to_string(path.join(QUARANTINE_FILE_NAME))
+            .ok()
+            .and_then(|content| serde_json::from_str::<QuarantineRecord>(&content).ok());
+        quarantined.push(QuarantinedBackup {
+            filename: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: path.to_string_lossy().to_string(),
+            reason: record
+                .as_ref()
+                .map(|r| r.reason.clone())
+                .unwrap_or_default(),
+            quarantined_at: record.map(|r| r.quarantined_at),
+        });
+    }
+
+    quarantined.sort_by(|a, b| {
+        let parse = |at: &Option<String>| {
+            at.as_deref()
+                .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        };
+        parse(&b.quarantined_at).cmp(&parse(&a.quarantined_at))
+    });
+    Ok(quarantined)
+}
+
+/// Moves a quarantined backup back into `.backups`, dropping its quarantine record.
+///
+/// Fails if the name is not a plain folder name, is not quarantined, or a backup
+/// with the same name already exists. Returns the restored folder path.
+pub fn release_from_quarantine(
+    save_dir: &Path,
+    folder_name: &str,
+    settings: &BackupSettings,
+) -> Result<PathBuf, String> {
+    if Path::new(folder_name).file_name().and_then(|n| n.to_str()) != Some(folder_name) {
+        return Err("Invalid backup folder name".to_string());
+    }
+    let backup_root = backup_root_for(save_dir, settings);
+    let source = quarantine_root(&backup_root).join(folder_name);
+    if !source.is_dir() {
+        return Err(format!("No quarantined backup named {}", folder_name));
+    }
+    let target = backup_root.join(folder_name);
+    if target.exists() {
+        return Err(format!("A backup named {} already exists", folder_name));
+    }
+
+    let record = source.join(QUARANTINE_FILE_NAME);
+    if record.exists() {
+        fs::remove_file(&record).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&source, &target).map_err(|e| e.to_string())?;
+    log::info!("Released backup {} from quarantine", folder_name);
+    Ok(target)
+}
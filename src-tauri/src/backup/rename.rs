@@ -0,0 +1,83 @@
+use super::delta::rebase_delta_dependents;
+use super::index::BackupStore;
+use super::settings::BackupSettings;
+use crate::filename_utils::{self, split_backup_label, with_backup_label};
+use std::fs;
+use std::path::Path;
+
+/// Longest label a backup folder can be given, in characters.
+const MAX_LABEL_CHARS: usize = 64;
+/// Brackets would break parsing the label back off; the rest are invalid in Windows names.
+const INVALID_LABEL_CHARS: &[char] = &['[', ']', '/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Gives a backup folder a label, keeping the parseable name in front of it.
+///
+/// "Game 2 - 14-Jun-2024 ..." becomes "Game 2 - 14-Jun-2024 ... [Final Boss]". An
+/// existing label is replaced, and `None` or a blank label removes it. Notes, tags,
+/// favorites, sessions, and the slot's last backup keyed by the old name move to the
+/// new one, and delta backups based on the folder follow it. Returns the new name.
+pub fn rename_backup(
+    save_dir: &Path,
+    folder_name: &str,
+    label: Option<String>,
+    settings: &BackupSettings,
+) -> Result<String, String> {
+    if Path::new(folder_name).file_name().and_then(|n| n.to_str()) != Some(folder_name) {
+        return Err("Invalid backup folder name".to_string());
+    }
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if let Some(label) = &label {
+        validate_label(label)?;
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let old_path = store.root.join(folder_name);
+    if !old_path.is_dir() || filename_utils::parse_backup_folder_name(folder_name).is_none() {
+        return Err(format!("No backup named {}", folder_name));
+    }
+    let (base_name, _) = split_backup_label(folder_name);
+    let new_name = match &label {
+        Some(label) => with_backup_label(base_name, label),
+        None => base_name.to_string(),
+    };
+    if new_name == folder_name {
+        return Ok(new_name);
+    }
+    let new_path = store.root.join(&new_name);
+    if new_path.exists() {
+        return Err(format!("A backup named {} already exists", new_name));
+    }
+
+    fs::rename(&old_path, &new_path).map_err(|e| e.to_string())?;
+    store.index.rename_folder(folder_name, &new_name);
+    let updated =
+        rebase_delta_dependents(&store.root, folder_name, &new_name).and_then(|_| store.save());
+    if let Err(e) = updated {
+        // Put the folder back so its name still matches the saved index and delta markers.
+        let _ = rebase_delta_dependents(&store.root, &new_name, folder_name);
+        let _ = fs::rename(&new_path, &old_path);
+        return Err(e);
+    }
+
+    log::info!("Renamed backup {} to {}", folder_name, new_name);
+    Ok(new_name)
+}
+
+/// Rejects labels that are too long or would not survive in a folder name.
+fn validate_label(label: &str) -> Result<(), String> {
+    if label.chars().count() > MAX_LABEL_CHARS {
+        return Err(format!(
+            "Backup label cannot be longer than {} characters",
+            MAX_LABEL_CHARS
+        ));
+    }
+    if label
+        .chars()
+        .any(|c| c.is_control() || INVALID_LABEL_CHARS.contains(&c))
+    {
+        return Err("Backup label contains characters not allowed in a folder name".to_string());
+    }
+    Ok(())
+}
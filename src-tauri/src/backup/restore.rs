@@ -1,13 +1,393 @@
-use super::common::{BACKUP_DIR_NAME, HASH_FILE_NAME};
+use super::common::HASH_FILE_NAME;
+use super::compression::{list_stored_save_files, StoredSaveFile};
+use super::create::{perform_backup_for_game_internal, BackupOptions, BackupOutcome};
 use super::data::{build_save_paths, read_source_metadata};
-use super::hashing::calculate_hash;
-use super::index::{BackupStore, IndexEntry};
+use super::fileutil::set_file_modified;
+use super::hashing::{calculate_hash, hashes_match};
+use super::index::{backup_root_for, BackupStore, IndexEntry};
+use super::listing::{get_backups, resolve_backup_timestamp};
+use super::manifest::read_manifest;
+use super::notes::set_backup_note;
+use super::settings::BackupSettings;
 use crate::filename_utils;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Note attached to the safety backup taken before a restore.
+pub const PRE_RESTORE_NOTE: &str = "Auto-saved before restore";
+
+/// Suffix appended to staged restore files before they are renamed into place.
+const RESTORE_TEMP_SUFFIX: &str = ".restore-tmp";
+
+/// Suffix of the live files set aside while a restore renames files into place.
+const RESTORE_ORIGINAL_SUFFIX: &str = ".restore-orig";
+
+/// How long a restore confirmation token stays valid after it is issued.
+pub const RESTORE_TOKEN_TTL: Duration = Duration::from_secs(60);
+
+/// Shared flag used to cancel an in-progress restore.
+///
+/// Checked between files while staging; a cancel observed before the final
+/// renames discards the staged copies and leaves the target untouched.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreCancelToken(Arc<AtomicBool>);
+
+impl RestoreCancelToken {
+    /// Requests cancellation of the current restore.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Clears any pending cancellation before a new restore starts.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The restore a confirmation token was issued for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreRequest {
+    /// A backup restored into its own slot of `target_dir`.
+    Backup {
+        backup_path: PathBuf,
+        target_dir: PathBuf,
+    },
+    /// A backup restored into slot `game_number` of `target_dir`.
+    BackupToSlot {
+        backup_path: PathBuf,
+        target_dir: PathBuf,
+        game_number: u32,
+    },
+    /// Every backup recorded under a session label, restored into `save_dir`.
+    Session { save_dir: PathBuf, label: String },
+}
+
+/// A restore the user was shown and has not confirmed yet.
+#[derive(Debug, Clone)]
+pub struct PendingRestore {
+    request: RestoreRequest,
+    issued_at: Instant,
+}
+
+/// Restores awaiting confirmation, keyed by the token handed to the frontend.
+///
+/// Each token confirms one restore of the backup and target it was issued for,
+/// so a stale or repeated restore request cannot overwrite the live save.
+#[derive(Debug, Clone)]
+pub struct PendingRestores {
+    pending: Arc<Mutex<HashMap<String, PendingRestore>>>,
+    ttl: Duration,
+}
+
+impl Default for PendingRestores {
+    fn default() -> Self {
+        Self::with_ttl(RESTORE_TOKEN_TTL)
+    }
+}
+
+impl PendingRestores {
+    /// Creates an empty set whose tokens expire after `ttl`.
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Records a restore awaiting confirmation and returns its token.
+    ///
+    /// Expired tokens are dropped at the same time.
+    pub fn issue(&self, request: RestoreRequest) -> Result<String, String> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock pending restores: {}", e))?;
+        pending.retain(|_, restore| restore.issued_at.elapsed() < self.ttl);
+
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        pending.insert(
+            token.clone(),
+            PendingRestore {
+                request,
+                issued_at: Instant::now(),
+            },
+        );
+        Ok(token)
+    }
+
+    /// Consumes a token, checking it is unexpired and was issued for this restore.
+    ///
+    /// The token is spent even when the check fails.
+    pub fn redeem(&self, token: &str, request: &RestoreRequest) -> Result<(), String> {
+        let restore = self
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock pending restores: {}", e))?
+            .remove(token)
+            .ok_or_else(|| "Restore was not confirmed or was already used".to_string())?;
+        if restore.issued_at.elapsed() >= self.ttl {
+            return Err("Restore confirmation expired; please confirm again".to_string());
+        }
+        if restore.request != *request {
+            return Err("Restore confirmation was issued for a different restore".to_string());
+        }
+        Ok(())
+    }
+
+    /// Issues a token for a restore that has no file-level preview.
+    pub fn confirmation(&self, request: RestoreRequest) -> Result<RestoreConfirmation, String> {
+        Ok(RestoreConfirmation {
+            token: self.issue(request)?,
+            expires_in_seconds: self.ttl.as_secs(),
+        })
+    }
+}
+
+/// What a restore will do to a single file in the target directory.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreFileAction {
+    /// The file does not exist in the target and will be created.
+    Create,
+    /// The file exists with different content and will be overwritten.
+    Overwrite,
+    /// The file exists with identical content.
+    Unchanged,
+}
+
+/// Planned effect of a restore on one file.
+#[derive(Debug, Serialize, Clone)]
+pub struct RestoreFilePlan {
+    /// The save file name (e.g., "gamesave_0.sav").
+    pub file_name: String,
+    /// The size of the file in the backup, in bytes.
+    pub size: u64,
+    /// What the restore will do to the target file.
+    pub action: RestoreFileAction,
+}
+
+/// Full preview of a restore's impact on the target save directory.
+#[derive(Debug, Serialize, Clone)]
+pub struct FullRestorePlan {
+    /// The game slot being restored.
+    pub game_number: u32,
+    /// Per-file actions for every save file in the backup.
+    pub files: Vec<RestoreFilePlan>,
+    /// True when safety backups before a restore are enabled and the live save holds
+    /// content not captured by any backup, so one would be created before overwriting it.
+    pub safety_backup_pending: bool,
+    /// True when the live main save was modified after the backup was taken.
+    pub live_save_newer: bool,
+}
+
+/// A restore plan plus the token that confirms it.
+#[derive(Debug, Serialize, Clone)]
+pub struct PreparedRestore {
+    /// Token `restore_backup_command` requires to carry out this restore.
+    pub token: String,
+    /// Seconds until the token expires.
+    pub expires_in_seconds: u64,
+    /// What the restore will do to each file in the target directory.
+    pub plan: FullRestorePlan,
+}
+
+/// A token that confirms a restore into another slot or of a whole session.
+#[derive(Debug, Serialize, Clone)]
+pub struct RestoreConfirmation {
+    /// Token the matching restore command requires to carry out this restore.
+    pub token: String,
+    /// Seconds until the token expires.
+    pub expires_in_seconds: u64,
+}
 
 /// Restores a backup folder to the save directory.
-pub fn restore_backup(backup_folder_path: &Path, target_save_dir: &Path) -> Result<(), String> {
+pub fn restore_backup(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    restore_backup_cancellable(
+        backup_folder_path,
+        target_save_dir,
+        &RestoreCancelToken::default(),
+        false,
+        settings,
+    )
+}
+
+/// Restores a backup folder to the save directory, honoring a cancellation token.
+///
+/// With `snapshot_before` set, the live save is backed up first and the restore is
+/// aborted if that safety backup fails.
+pub fn restore_backup_cancellable(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    token: &RestoreCancelToken,
+    snapshot_before: bool,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    if snapshot_before {
+        create_pre_restore_snapshot(backup_folder_path, target_save_dir, settings)
+            .map_err(|e| format!("Restore aborted: safety backup failed: {}", e))?;
+    }
+    restore_backup_with(backup_folder_path, target_save_dir, settings, || {
+        token.is_cancelled()
+    })
+}
+
+/// Backs up the live save of the slot a backup belongs to, noting it as a safety backup.
+///
+/// Nothing is created when the live save is missing or already matches a backup.
+/// Retention is skipped here, so the backup about to be restored can never be
+/// pruned by its own safety backup, not even when it is past the age limit. A live
+/// save that is still being written is an error, since the restore would overwrite
+/// it unprotected.
+pub(crate) fn create_pre_restore_snapshot(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Option<PathBuf>, String> {
+    let Some(game_number) = list_stored_save_files(backup_folder_path)?
+        .iter()
+        .filter_map(|stored| filename_utils::parse_filename(&stored.file_name))
+        .find(|info| !info.is_bak)
+        .map(|info| info.game_number)
+    else {
+        return Ok(None);
+    };
+    snapshot_slot_before_restore(target_save_dir, game_number, settings)
+}
+
+/// Backs up the live save of one slot as a safety backup before it is overwritten.
+fn snapshot_slot_before_restore(
+    target_save_dir: &Path,
+    game_number: u32,
+    settings: &BackupSettings,
+) -> Result<Option<PathBuf>, String> {
+    if !build_save_paths(target_save_dir, game_number)
+        .main_path
+        .exists()
+    {
+        return Ok(None);
+    }
+
+    let mut store = BackupStore::new(target_save_dir, settings)?;
+    let backups = get_backups(target_save_dir, true, Some(game_number), settings)?;
+    let outcome = perform_backup_for_game_internal(
+        target_save_dir,
+        &store.root,
+        game_number,
+        &mut store.index,
+        &backups,
+        settings,
+        BackupOptions {
+            keep_existing: true,
+            wait_until_settled: true,
+            ..BackupOptions::default()
+        },
+    )?;
+    store.save()?;
+    let created = match outcome {
+        BackupOutcome::Created(path) => Some(path),
+        BackupOutcome::SkippedDuplicate | BackupOutcome::SkippedNoSave => None,
+        BackupOutcome::SkippedUnstable => {
+            return Err(format!(
+                "Save file for game {} is still being written",
+                game_number
+            ))
+        }
+    };
+
+    if let Some(folder_name) = created
+        .as_deref()
+        .and_then(Path::file_name)
+        .map(|n| n.to_string_lossy().into_owned())
+    {
+        set_backup_note(
+            target_save_dir,
+            &folder_name,
+            Some(PRE_RESTORE_NOTE.to_string()),
+            settings,
+        )?;
+        log::info!("Created safety backup {} before restore", folder_name);
+    }
+    Ok(created)
+}
+
+/// Transactional restore: stages every file to a temp name, then renames them into place.
+///
+/// `is_cancelled` is polled before each file is staged and once more before the
+/// renames begin. On cancellation or error all staged temps are removed, so the
+/// target's original files are only replaced once every file staged successfully.
+/// If a rename then fails, the files already replaced are rolled back to the live
+/// versions set aside just before, so the main save and `.bak` stay a matching pair.
+pub(crate) fn restore_backup_with(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    settings: &BackupSettings,
+    is_cancelled: impl Fn() -> bool,
+) -> Result<(), String> {
+    restore_into_slot(
+        backup_folder_path,
+        target_save_dir,
+        None,
+        settings,
+        is_cancelled,
+    )
+}
+
+/// Restores a backup into another game slot, renaming its save files to match.
+///
+/// Refuses to replace an existing save in the target slot unless `overwrite` is set.
+/// When it does replace one, that save is backed up first and the restore is aborted
+/// if the safety backup fails.
+pub fn restore_backup_to_slot(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    target_game_number: u32,
+    overwrite: bool,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    if build_save_paths(target_save_dir, target_game_number)
+        .main_path
+        .exists()
+    {
+        if !overwrite {
+            return Err(format!(
+                "Game {} already has a save; enable overwrite to replace it",
+                target_game_number + 1
+            ));
+        }
+        snapshot_slot_before_restore(target_save_dir, target_game_number, settings)
+            .map_err(|e| format!("Restore aborted: safety backup failed: {}", e))?;
+    }
+    restore_into_slot(
+        backup_folder_path,
+        target_save_dir,
+        Some(target_game_number),
+        settings,
+        || false,
+    )
+}
+
+/// Shared restore implementation; `target_game_number` renames files into another slot.
+fn restore_into_slot(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    target_game_number: Option<u32>,
+    settings: &BackupSettings,
+    is_cancelled: impl Fn() -> bool,
+) -> Result<(), String> {
     if !backup_folder_path.exists() {
         return Err("Backup folder does not exist".to_string());
     }
@@ -15,39 +395,332 @@ pub fn restore_backup(backup_folder_path: &Path, target_save_dir: &Path) -> Resu
         return Err("Target save directory does not exist".to_string());
     }
 
-    let mut restored_any = false;
+    let stored_files = restorable_files(backup_folder_path, settings)?;
+    if stored_files.is_empty() {
+        return Err("No valid save files found in backup folder to restore".to_string());
+    }
+    let restored_modified = backup_modified_time(backup_folder_path);
+
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let staging = stored_files.iter().try_for_each(|stored| {
+        if is_cancelled() {
+            return Err("Restore cancelled".to_string());
+        }
+        let file_name = target_file_name(&stored.file_name, target_game_number);
+        let final_path = target_save_dir.join(&file_name);
+        let temp_path = target_save_dir.join(format!(".{}{}", file_name, RESTORE_TEMP_SUFFIX));
+        staged.push((temp_path.clone(), final_path));
+        stored
+            .extract_to(&temp_path, settings.copy_buffer_size())
+            .map_err(|e| format!("Failed to stage {}: {}", file_name, e))?;
+        if let Some(modified) = restored_modified {
+            // Keeps the restored save's mtime stable so the index fast-path still matches it.
+            if let Err(e) = set_file_modified(&temp_path, modified) {
+                log::warn!("Could not set modification time of {:?}: {}", temp_path, e);
+            }
+        }
+        Ok(())
+    });
+    let staging = staging.and_then(|_| {
+        if is_cancelled() {
+            Err("Restore cancelled".to_string())
+        } else {
+            Ok(())
+        }
+    });
+
+    if let Err(e) = staging {
+        for (temp_path, _) in &staged {
+            let _ = fs::remove_file(temp_path);
+        }
+        log::info!("Restore from {:?} aborted: {}", backup_folder_path, e);
+        return Err(e);
+    }
+
+    if let Err(e) = swap_staged_files(&staged) {
+        for (temp_path, _) in &staged {
+            let _ = fs::remove_file(temp_path);
+        }
+        log::error!("Restore from {:?} rolled back: {}", backup_folder_path, e);
+        return Err(e);
+    }
+
+    log::info!(
+        "Restored backup from {:?} to {:?}",
+        backup_folder_path,
+        target_save_dir
+    );
+    update_index_after_restore(
+        backup_folder_path,
+        target_save_dir,
+        target_game_number,
+        settings,
+    )
+}
 
-    for entry in fs::read_dir(backup_folder_path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(_info) = filename_utils::parse_path(&path) {
-                // It is a valid save file or bak
-                let file_name = path.file_name().unwrap();
-                let target_file = target_save_dir.join(file_name);
+/// Renames staged files over their targets, undoing every replacement if one fails.
+///
+/// Each live file is first moved aside, which keeps a pre-restore copy in the target
+/// directory; the copies are deleted only after every staged file is in place.
+fn swap_staged_files(staged: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+    // (final path, set-aside original) for each file already renamed into place.
+    let mut swapped: Vec<(&Path, Option<PathBuf>)> = Vec::new();
+    let mut result = Ok(());
+    for (temp_path, final_path) in staged {
+        let file_name = final_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let original = if final_path.exists() {
+            let aside =
+                final_path.with_file_name(format!(".{}{}", file_name, RESTORE_ORIGINAL_SUFFIX));
+            if let Err(e) = fs::rename(final_path, &aside) {
+                result = Err(format!("Failed to set aside the live {}: {}", file_name, e));
+                break;
+            }
+            Some(aside)
+        } else {
+            None
+        };
+        if let Err(e) = fs::rename(temp_path, final_path) {
+            if let Some(aside) = &original {
+                let _ = fs::rename(aside, final_path);
+            }
+            result = Err(format!("Failed to replace {}: {}", file_name, e));
+            break;
+        }
+        swapped.push((final_path, original));
+    }
 
-                fs::copy(&path, &target_file).map_err(|e| e.to_string())?;
-                restored_any = true;
+    match result {
+        Ok(()) => {
+            for (_, original) in swapped {
+                if let Some(aside) = original {
+                    let _ = fs::remove_file(aside);
+                }
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let mut unrestored = Vec::new();
+            for (final_path, original) in swapped.into_iter().rev() {
+                let rolled_back = match &original {
+                    Some(aside) => fs::rename(aside, final_path),
+                    None => fs::remove_file(final_path),
+                };
+                if rolled_back.is_err() {
+                    unrestored.push(final_path.to_string_lossy().to_string());
+                }
+            }
+            if unrestored.is_empty() {
+                Err(format!("{}; the live save was left unchanged", e))
+            } else {
+                Err(format!(
+                    "{}; could not roll back {}, use the safety backup to recover",
+                    e,
+                    unrestored.join(", ")
+                ))
             }
         }
     }
+}
 
-    if restored_any {
-        log::info!(
-            "Restored backup from {:?} to {:?}",
+/// Returns the time a backup was taken, used as the restored files' modification time.
+///
+/// `None` when the folder name does not follow the backup naming contract.
+fn backup_modified_time(backup_folder_path: &Path) -> Option<SystemTime> {
+    let info = backup_folder_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(filename_utils::parse_backup_folder_name)?;
+    Some(
+        resolve_backup_timestamp(
             backup_folder_path,
-            target_save_dir
-        );
-        update_index_after_restore(backup_folder_path, target_save_dir)
+            &info,
+            read_manifest(backup_folder_path).as_ref(),
+        )
+        .into(),
+    )
+}
+
+/// Lists the save files of a backup that a restore writes back.
+///
+/// `.bak` files are left out while `.bak` files are excluded, so the live `.bak` stays.
+fn restorable_files(
+    backup_folder_path: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<StoredSaveFile>, String> {
+    let mut files = list_stored_save_files(backup_folder_path)?;
+    if !settings.include_bak_files {
+        files.retain(|stored| {
+            !filename_utils::parse_filename(&stored.file_name).is_some_and(|info| info.is_bak)
+        });
+    }
+    Ok(files)
+}
+
+/// Maps a stored save file name to its name in the target slot.
+fn target_file_name(file_name: &str, target_game_number: Option<u32>) -> String {
+    let (Some(game_number), Some(info)) = (
+        target_game_number,
+        filename_utils::parse_filename(file_name),
+    ) else {
+        return file_name.to_string();
+    };
+    let paths = build_save_paths(Path::new(""), game_number);
+    if info.is_bak {
+        paths.bak_filename
     } else {
-        Err("No valid save files found in backup folder to restore".to_string())
+        paths.main_filename
     }
 }
 
+/// Plans a single file restore by comparing the backup copy with the target.
+fn plan_file(
+    source: &StoredSaveFile,
+    target: &Path,
+    settings: &BackupSettings,
+) -> Result<RestoreFileAction, String> {
+    if !target.exists() {
+        return Ok(RestoreFileAction::Create);
+    }
+    let target_len = fs::metadata(target).map_err(|e| e.to_string())?.len();
+    if source.original_size()? == target_len
+        && source.content_hash(settings)? == calculate_hash(target, settings)?
+    {
+        Ok(RestoreFileAction::Unchanged)
+    } else {
+        Ok(RestoreFileAction::Overwrite)
+    }
+}
+
+/// Previews everything a restore would change in the target save directory.
+///
+/// Combines the per-file plan with whether the live save still needs a safety
+/// backup and whether it is newer than the backup being restored. No safety backup is
+/// reported pending unless `snapshot_before` is set. Read-only.
+pub fn plan_restore_full(
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    snapshot_before: bool,
+    settings: &BackupSettings,
+) -> Result<FullRestorePlan, String> {
+    if !backup_folder_path.is_dir() {
+        return Err("Backup folder does not exist".to_string());
+    }
+    if !target_save_dir.is_dir() {
+        return Err("Target save directory does not exist".to_string());
+    }
+
+    let folder_name = backup_folder_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?;
+    let info = filename_utils::parse_backup_folder_name(folder_name)
+        .ok_or_else(|| "Backup folder name did not match expected format".to_string())?;
+
+    let mut files = Vec::new();
+    for stored in restorable_files(backup_folder_path, settings)? {
+        let action = plan_file(&stored, &target_save_dir.join(&stored.file_name), settings)?;
+        files.push(RestoreFilePlan {
+            size: stored.original_size()?,
+            action,
+            file_name: stored.file_name,
+        });
+    }
+    if files.is_empty() {
+        return Err("No valid save files found in backup folder to restore".to_string());
+    }
+
+    let live = build_save_paths(target_save_dir, info.game_number);
+    let (safety_backup_pending, live_save_newer) = if live.main_path.exists() {
+        let already_backed_up = !snapshot_before || {
+            let live_hash = calculate_hash(&live.main_path, settings)?;
+            get_backups(target_save_dir, true, Some(info.game_number), settings)?
+                .iter()
+                .any(|b| hashes_match(&b.hash, &live_hash))
+        };
+        let live_modified = fs::metadata(&live.main_path)
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        (
+            !already_backed_up,
+            live_modified
+                > SystemTime::from(resolve_backup_timestamp(
+                    backup_folder_path,
+                    &info,
+                    read_manifest(backup_folder_path).as_ref(),
+                )),
+        )
+    } else {
+        (false, false)
+    };
+
+    Ok(FullRestorePlan {
+        game_number: info.game_number,
+        files,
+        safety_backup_pending,
+        live_save_newer,
+    })
+}
+
+/// Plans a restore and issues the token that confirms it.
+pub fn prepare_restore(
+    pending: &PendingRestores,
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    snapshot_before: bool,
+    settings: &BackupSettings,
+) -> Result<PreparedRestore, String> {
+    let plan = plan_restore_full(
+        backup_folder_path,
+        target_save_dir,
+        snapshot_before,
+        settings,
+    )?;
+    let token = pending.issue(RestoreRequest::Backup {
+        backup_path: backup_folder_path.to_path_buf(),
+        target_dir: target_save_dir.to_path_buf(),
+    })?;
+    Ok(PreparedRestore {
+        token,
+        expires_in_seconds: pending.ttl.as_secs(),
+        plan,
+    })
+}
+
+/// Issues the token that confirms restoring a backup into another slot.
+pub fn prepare_restore_to_slot(
+    pending: &PendingRestores,
+    backup_folder_path: &Path,
+    target_save_dir: &Path,
+    target_game_number: u32,
+) -> Result<RestoreConfirmation, String> {
+    if !backup_folder_path.is_dir() {
+        return Err("Backup folder does not exist".to_string());
+    }
+    if !target_save_dir.is_dir() {
+        return Err("Target save directory does not exist".to_string());
+    }
+    pending.confirmation(RestoreRequest::BackupToSlot {
+        backup_path: backup_folder_path.to_path_buf(),
+        target_dir: target_save_dir.to_path_buf(),
+        game_number: target_game_number,
+    })
+}
+
 /// Updates the backup index after a successful restore when possible.
+///
+/// The entry goes to `target_game_number` when given, else to the backup's own slot.
+/// A restore into another slot drops that slot's entry instead, since the backup
+/// folder belongs to a different slot; the next scan then backs the save up into
+/// its own slot. Size and modification time are read back from the restored main save, which
+/// carries the backup's timestamp, so the next scan can skip rehashing it.
 fn update_index_after_restore(
     backup_folder_path: &Path,
     target_save_dir: &Path,
+    target_game_number: Option<u32>,
+    settings: &BackupSettings,
 ) -> Result<(), String> {
     let folder_name = backup_folder_path
         .file_name()
@@ -55,15 +728,21 @@ fn update_index_after_restore(
         .ok_or_else(|| "Backup folder name is invalid".to_string())?;
     let info = filename_utils::parse_backup_folder_name(folder_name)
         .ok_or_else(|| "Backup folder name did not match expected format".to_string())?;
-    let backup_root = target_save_dir.join(BACKUP_DIR_NAME);
+    let backup_root = backup_root_for(target_save_dir, settings);
     if !backup_folder_path.starts_with(&backup_root) {
-        return Err("Backup folder is not under the target .backups directory".to_string());
+        return Err("Backup folder is not under the target backup directory".to_string());
     }
 
-    let paths = build_save_paths(target_save_dir, info.game_number);
+    let game_number = target_game_number.unwrap_or(info.game_number);
+    let paths = build_save_paths(target_save_dir, game_number);
     if !paths.main_path.exists() {
         return Err("Restored main save file was not found after restore".to_string());
     }
+    let mut store = BackupStore::new(target_save_dir, settings)?;
+    if game_number != info.game_number {
+        store.index.games.remove(&game_number);
+        return store.save();
+    }
     let source = read_source_metadata(&paths.main_path)?;
 
     let hash_path = backup_folder_path.join(HASH_FILE_NAME);
@@ -76,19 +755,19 @@ fn update_index_after_restore(
         String::new()
     };
     let hash = if hash.is_empty() {
-        calculate_hash(&paths.main_path)?
+        calculate_hash(&paths.main_path, settings)?
     } else {
         hash
     };
 
-    let mut store = BackupStore::new(target_save_dir)?;
     store.index.games.insert(
-        info.game_number,
+        game_number,
         IndexEntry {
             last_hash: hash,
             last_source_size: source.size,
             last_source_modified: source.modified_nanos,
             last_backup_path: folder_name.to_string(),
+            last_verified: 0,
         },
     );
     store.save()
@@ -0,0 +1,67 @@
+use super::compression::find_stored_save_file;
+use super::fileutil::folder_size;
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Which backups of a slot are redundant copies of content that is kept elsewhere.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct RetentionSuggestion {
+    /// The game slot the suggestion applies to.
+    pub game_number: u32,
+    /// Folder names kept: every locked backup and the newest backup of each unique save.
+    pub keep: Vec<String>,
+    /// Folder names that duplicate a kept backup and can be deleted safely.
+    pub delete: Vec<String>,
+    /// Bytes on disk the suggested deletions would free.
+    pub bytes_freed: u64,
+}
+
+/// Suggests the smallest set of backups that still holds every distinct save of a slot.
+///
+/// Backups are grouped by content hash. Locked backups are always kept; a group with
+/// no locked backup keeps its newest one. Nothing is deleted here; the caller removes
+/// the suggested folders explicitly.
+pub fn suggest_retention(
+    save_dir: &Path,
+    game_number: u32,
+    settings: &BackupSettings,
+) -> Result<RetentionSuggestion, String> {
+    let backups = get_backups(save_dir, true, Some(game_number), settings)?;
+    let mut hashes = Vec::with_capacity(backups.len());
+    for backup in &backups {
+        let hash = if backup.hash.is_empty() {
+            find_stored_save_file(Path::new(&backup.path), &backup.original_filename)
+                .ok_or_else(|| format!("Backup {} has no main save file", backup.filename))?
+                .content_hash(&BackupSettings::default())?
+        } else {
+            backup.hash.clone()
+        };
+        hashes.push(hash);
+    }
+
+    // Locked backups already preserve their content, so they claim their hash first.
+    let mut covered: HashSet<&str> = backups
+        .iter()
+        .zip(&hashes)
+        .filter(|(backup, _)| backup.locked)
+        .map(|(_, hash)| hash.as_str())
+        .collect();
+
+    let mut suggestion = RetentionSuggestion {
+        game_number,
+        ..RetentionSuggestion::default()
+    };
+    // get_backups is sorted newest first, so the first unlocked backup seen per hash is kept.
+    for (backup, hash) in backups.iter().zip(&hashes) {
+        if backup.locked || covered.insert(hash.as_str()) {
+            suggestion.keep.push(backup.filename.clone());
+        } else {
+            suggestion.bytes_freed += folder_size(Path::new(&backup.path))?;
+            suggestion.delete.push(backup.filename.clone());
+        }
+    }
+    Ok(suggestion)
+}
@@ -0,0 +1,178 @@
+use super::compression::find_stored_save_file;
+use super::data::BackupInfo;
+use super::delta::stored_content;
+use crate::filename_utils;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io::Read;
+use std::path::Path;
+
+/// Field names (lowercase, letters and digits only) read as the in-game day.
+const DAY_KEYS: &[&str] = &["day", "days", "daycount", "currentday", "daynumber"];
+/// Field names read as the difficulty.
+const DIFFICULTY_KEYS: &[&str] = &["difficulty", "difficultylevel", "gamedifficulty"];
+/// Field names read as a survivor's name inside a survivor list.
+const NAME_KEYS: &[&str] = &["name", "displayname", "survivorname", "firstname"];
+/// Most survivor names reported for one save.
+const MAX_SURVIVORS: usize = 32;
+/// Largest decompressed size read from a gzip-compressed save.
+const MAX_DECOMPRESSED_BYTES: u64 = 256 * 1024 * 1024;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// How the save file's content was encoded.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveEncoding {
+    Json,
+    GzipJson,
+    /// Not a format the parser understands; only the size is known.
+    Unknown,
+}
+
+/// In-game details recognized in a save file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SaveMetadata {
+    /// Size of the save file in bytes, as stored by the game.
+    pub raw_size: u64,
+    pub encoding: SaveEncoding,
+    /// The in-game day the save was made on.
+    pub day: Option<u32>,
+    pub difficulty: Option<String>,
+    /// Survivor names, in the order the save lists them.
+    pub survivors: Vec<String>,
+}
+
+/// Reads the in-game details of a backup's main save.
+pub fn read_save_metadata(backup_folder: &Path) -> Result<SaveMetadata, String> {
+    let info = backup_folder
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(filename_utils::parse_backup_folder_name)
+        .ok_or_else(|| "Not a valid backup folder".to_string())?;
+    let main_filename = format!("gamesave_{}.sav", info.game_number);
+    let stored = find_stored_save_file(backup_folder, &main_filename)
+        .ok_or_else(|| format!("Backup has no {}", main_filename))?;
+    Ok(parse_save_metadata(&stored_content(&stored, 0)?))
+}
+
+/// Fills in `metadata` for each backup whose save could be read.
+pub fn attach_save_metadata(backups: &mut [BackupInfo]) {
+    for backup in backups {
+        match read_save_metadata(Path::new(&backup.path)) {
+            Ok(metadata) => backup.metadata = Some(metadata),
+            Err(e) => log::warn!("Could not read save metadata of {}: {}", backup.filename, e),
+        }
+    }
+}
+
+/// Extracts whatever known fields can be recognized in raw save bytes.
+///
+/// The save format is not documented, so this is a best-effort heuristic. A save that
+/// is JSON (optionally gzip-compressed) is searched for fields whose names look like a
+/// day counter, a difficulty, or a survivor list; any other save only reports its size.
+/// A missing field means it was not recognized, not that the save lacks it.
+pub(crate) fn parse_save_metadata(bytes: &[u8]) -> SaveMetadata {
+    let mut metadata = SaveMetadata {
+        raw_size: bytes.len() as u64,
+        encoding: SaveEncoding::Unknown,
+        day: None,
+        difficulty: None,
+        survivors: Vec::new(),
+    };
+
+    let (encoding, json) = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        let read = GzDecoder::new(bytes)
+            .take(MAX_DECOMPRESSED_BYTES)
+            .read_to_end(&mut decompressed);
+        if read.is_err() {
+            return metadata;
+        }
+        (SaveEncoding::GzipJson, decompressed)
+    } else {
+        (SaveEncoding::Json, bytes.to_vec())
+    };
+    let json = json.strip_prefix(UTF8_BOM).unwrap_or(&json);
+    let Ok(root) = serde_json::from_slice::<Value>(json) else {
+        return metadata;
+    };
+
+    metadata.encoding = encoding;
+    // Breadth first, so top-level fields win over same-named fields nested deeper.
+    let mut queue = VecDeque::from([&root]);
+    while let Some(value) = queue.pop_front() {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    let key = normalize_key(key);
+                    if metadata.day.is_none() && DAY_KEYS.contains(&key.as_str()) {
+                        metadata.day = as_day(field);
+                    }
+                    if metadata.difficulty.is_none() && DIFFICULTY_KEYS.contains(&key.as_str()) {
+                        metadata.difficulty = as_label(field);
+                    }
+                    if metadata.survivors.is_empty() && key.contains("survivor") {
+                        metadata.survivors = survivor_names(field);
+                    }
+                    queue.push_back(field);
+                }
+            }
+            Value::Array(items) => queue.extend(items),
+            _ => {}
+        }
+    }
+    metadata
+}
+
+/// Lowercases a field name and drops separators, so `current_day` matches `CurrentDay`.
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Reads a day counter stored as a number or a numeric string.
+fn as_day(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Reads a label stored as a non-empty string or a number.
+fn as_label(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Reads names from a survivor list of strings or of objects with a name field.
+fn survivor_names(value: &Value) -> Vec<String> {
+    let Value::Array(items) = value else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = Vec::new();
+    for item in items {
+        let name = match item {
+            Value::Object(fields) => fields
+                .iter()
+                .find(|(key, _)| NAME_KEYS.contains(&normalize_key(key).as_str()))
+                .and_then(|(_, name)| as_label(name)),
+            other => as_label(other),
+        };
+        if let Some(name) = name.filter(|name| !names.contains(name)) {
+            names.push(name);
+        }
+        if names.len() == MAX_SURVIVORS {
+            break;
+        }
+    }
+    names
+}
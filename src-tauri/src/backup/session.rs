@@ -0,0 +1,141 @@
+use super::create::{perform_backup_for_game_internal, BackupOptions};
+use super::data::discover_save_slots;
+use super::index::BackupStore;
+use super::listing::get_backups;
+use super::restore::{restore_backup, PendingRestores, RestoreConfirmation, RestoreRequest};
+use super::settings::BackupSettings;
+use std::path::Path;
+
+/// Validates and normalizes a session label.
+fn normalize_label(label: &str) -> Result<String, String> {
+    let trimmed = label.trim();
+    if trimmed.is_empty() {
+        return Err("Session label cannot be empty".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Snapshots every slot in the save directory under a single session label.
+///
+/// Each slot is backed up with deduplication bypassed so the session always captures
+/// the current state. Reusing an existing label replaces its folder list.
+/// Returns the folder names recorded for the session.
+pub fn create_session_snapshot(
+    save_dir: &Path,
+    label: &str,
+    limit: usize,
+    settings: &BackupSettings,
+) -> Result<Vec<String>, String> {
+    let label = normalize_label(label)?;
+    if !save_dir.exists() {
+        return Err(format!("Save directory does not exist: {:?}", save_dir));
+    }
+
+    let slots = discover_save_slots(save_dir)?;
+    if slots.is_empty() {
+        return Err("No save files found to snapshot".to_string());
+    }
+
+    let mut store = BackupStore::new(save_dir, settings)?;
+    let mut folders = Vec::new();
+
+    for game_number in slots {
+        let backups = get_backups(save_dir, true, Some(game_number), settings).unwrap_or_default();
+        let created = perform_backup_for_game_internal(
+            save_dir,
+            &store.root,
+            game_number,
+            &mut store.index,
+            &backups,
+            settings,
+            BackupOptions {
+                limit,
+                force: true,
+                ..BackupOptions::default()
+            },
+        )?
+        .created();
+
+        if let Some(folder_name) = created
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|n| n.to_string_lossy().into_owned())
+        {
+            folders.push(folder_name);
+        }
+    }
+
+    store.index.sessions.insert(label.clone(), folders.clone());
+    store.save()?;
+
+    log::info!(
+        "Created session snapshot '{}' with {} backup(s)",
+        label,
+        folders.len()
+    );
+    Ok(folders)
+}
+
+/// Builds the request a session restore token is bound to, normalizing the label.
+pub fn session_restore_request(save_dir: &Path, label: &str) -> Result<RestoreRequest, String> {
+    Ok(RestoreRequest::Session {
+        save_dir: save_dir.to_path_buf(),
+        label: normalize_label(label)?,
+    })
+}
+
+/// Issues the token that confirms restoring a session, once the session is known to exist.
+pub fn prepare_session_restore(
+    pending: &PendingRestores,
+    save_dir: &Path,
+    label: &str,
+    settings: &BackupSettings,
+) -> Result<RestoreConfirmation, String> {
+    let label = normalize_label(label)?;
+    let store = BackupStore::load_if_exists(save_dir, settings)?
+        .ok_or_else(|| "Backup directory not found".to_string())?;
+    if !store.index.sessions.contains_key(&label) {
+        return Err(format!("Session '{}' not found", label));
+    }
+    pending.confirmation(RestoreRequest::Session {
+        save_dir: save_dir.to_path_buf(),
+        label,
+    })
+}
+
+/// Restores every backup recorded under a session label into the save directory.
+///
+/// Fails without restoring anything if any folder in the session no longer exists.
+pub fn restore_session(
+    save_dir: &Path,
+    label: &str,
+    settings: &BackupSettings,
+) -> Result<(), String> {
+    let label = normalize_label(label)?;
+    let store = BackupStore::load_if_exists(save_dir, settings)?
+        .ok_or_else(|| "Backup directory not found".to_string())?;
+    let folders = store
+        .index
+        .sessions
+        .get(&label)
+        .cloned()
+        .ok_or_else(|| format!("Session '{}' not found", label))?;
+
+    let missing: Vec<&String> = folders
+        .iter()
+        .filter(|folder| !store.root.join(folder).is_dir())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Session '{}' references missing backups: {:?}",
+            label, missing
+        ));
+    }
+
+    for folder in &folders {
+        restore_backup(&store.root.join(folder), save_dir, settings)?;
+    }
+
+    log::info!("Restored session '{}' ({} backup(s))", label, folders.len());
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use super::common::SETTINGS_FILE_NAME;
+use super::create::{NamingScheme, DEFAULT_HASH_RECHECK_INTERVAL};
+use super::fileutil::{DEFAULT_COPY_BUFFER_KB, MAX_COPY_BUFFER_KB};
+use super::hashing::HashAlgorithm;
+use super::trash::DEFAULT_TRASH_RETENTION_DAYS;
+use crate::filename_utils::BackupNameFormat;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// App-wide settings that shape how backups are written, pruned, and restored.
+///
+/// Built from the app config and passed into every backup entry point, so each
+/// operation works from one consistent set of values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupSettings {
+    /// Buffer size in KiB for copying and hashing save files; 0 uses the default.
+    pub copy_buffer_kb: usize,
+    /// Whether deletion paths must keep the newest backup of each slot.
+    pub never_delete_last_backup: bool,
+    /// Age in days after which unlocked backups are deleted; `None` keeps them.
+    pub max_backup_age_days: Option<u32>,
+    /// Cap in bytes on the whole backup root, trash included; `None` leaves it unlimited.
+    pub max_total_backup_bytes: Option<u64>,
+    /// How new backup folders are named.
+    pub naming_scheme: NamingScheme,
+    /// Timestamp format used in new timestamp-named backup folders.
+    pub backup_name_format: BackupNameFormat,
+    /// Algorithm new saves are hashed with.
+    pub hash_algorithm: HashAlgorithm,
+    /// Backup attempts per slot after which a metadata match is rehashed; 0 never rehashes.
+    pub hash_recheck_interval: u32,
+    /// Days deleted backups stay in the trash; 0 keeps them until emptied manually.
+    pub trash_retention_days: u32,
+    /// Whether new backups store their save files gzip-compressed.
+    pub compress_backups: bool,
+    /// Whether `.bak` files are backed up and restored alongside the main save.
+    pub include_bak_files: bool,
+    /// Whether new backups hardlink to identical backups from any slot.
+    pub cross_slot_dedup: bool,
+    /// Whether new backups of a changed save are stored as a delta against the
+    /// slot's previous backup, with a full copy again after `DELTA_CHAIN_LIMIT` deltas.
+    pub delta_backups: bool,
+    /// Secondary directory new backups are copied into; `None` disables mirroring.
+    pub mirror_directory: Option<PathBuf>,
+    /// Directory holding each save directory's backups instead of its `.backups`
+    /// folder; `None` keeps backups inside the save directory.
+    pub backup_root_override: Option<PathBuf>,
+    /// Until when every backup is taken even if identical to an existing one.
+    pub dedup_disabled_until: Option<Instant>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            copy_buffer_kb: DEFAULT_COPY_BUFFER_KB,
+            never_delete_last_backup: true,
+            max_backup_age_days: None,
+            max_total_backup_bytes: None,
+            naming_scheme: NamingScheme::default(),
+            backup_name_format: BackupNameFormat::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            hash_recheck_interval: DEFAULT_HASH_RECHECK_INTERVAL,
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            compress_backups: false,
+            include_bak_files: true,
+            cross_slot_dedup: false,
+            delta_backups: false,
+            mirror_directory: None,
+            backup_root_override: None,
+            dedup_disabled_until: None,
+        }
+    }
+}
+
+impl BackupSettings {
+    /// Returns the copy buffer size in bytes.
+    ///
+    /// Zero falls back to the default; values above 16 MiB are clamped.
+    pub(crate) fn copy_buffer_size(&self) -> usize {
+        let kb = match self.copy_buffer_kb {
+            0 => DEFAULT_COPY_BUFFER_KB,
+            kb => kb.min(MAX_COPY_BUFFER_KB),
+        };
+        kb * 1024
+    }
+
+    /// Skips duplicate detection for `duration`; a zero duration ends the window.
+    pub fn disable_dedup_for(&mut self, duration: Duration) {
+        self.dedup_disabled_until = (!duration.is_zero()).then(|| Instant::now() + duration);
+    }
+
+    /// Returns true while a dedup override window is open.
+    pub(crate) fn dedup_disabled(&self) -> bool {
+        self.dedup_disabled_until
+            .is_some_and(|deadline| Instant::now() < deadline)
+    }
+}
+
+/// Per-save-directory overrides read from `.backups/settings.json`.
+///
+/// Each field left out of the file falls back to the global app setting, so a
+/// profile can tighten its limit without repeating everything else.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DirectorySettings {
+    /// Maximum backups kept per slot; 0 means unlimited.
+    #[serde(default)]
+    pub(crate) limit: Option<usize>,
+    #[serde(default)]
+    pub(crate) compress_backups: Option<bool>,
+    #[serde(default)]
+    pub(crate) naming_scheme: Option<NamingScheme>,
+}
+
+/// Loads the directory overrides from a backup root.
+///
+/// A missing file means no overrides; an unreadable or invalid one is logged and ignored.
+pub(crate) fn load_directory_settings(backup_root: &Path) -> DirectorySettings {
+    let path = backup_root.join(SETTINGS_FILE_NAME);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return DirectorySettings::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("Ignoring invalid directory settings {:?}: {}", path, e);
+        DirectorySettings::default()
+    })
+}
@@ -0,0 +1,206 @@
+use super::compression::{find_stored_save_file, StoredSaveFile};
+use super::data::{build_save_paths, BackupInfo};
+use super::fileutil::DEFAULT_COPY_BUFFER_SIZE;
+use super::hashing::{calculate_hash, hashes_match};
+use super::index::backup_root_for;
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use serde::Serialize;
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// A backup with its similarity to the live save.
+#[derive(Debug, Serialize, Clone)]
+pub struct RankedBackup {
+    /// The backup being ranked.
+    pub backup: BackupInfo,
+    /// Whether the backup's content hash matches the live save exactly.
+    pub exact_match: bool,
+    /// Absolute difference in bytes between the backup and the live save.
+    pub size_delta: u64,
+}
+
+/// Ranks a slot's backups by how closely they resemble the current live save.
+///
+/// Exact hash matches come first, then the smallest size difference. Ties keep the
+/// listing order, so newer backups win.
+pub fn rank_backups_by_similarity(
+    save_dir: &Path,
+    game_number: u32,
+    settings: &BackupSettings,
+) -> Result<Vec<RankedBackup>, String> {
+    let paths = build_save_paths(save_dir, game_number);
+    if !paths.main_path.exists() {
+        return Err(format!(
+            "Main save file not found for game {}",
+            game_number + 1
+        ));
+    }
+    let live_size = fs::metadata(&paths.main_path)
+        .map_err(|e| e.to_string())?
+        .len();
+    let live_hash = calculate_hash(&paths.main_path, settings)?;
+
+    let mut ranked: Vec<RankedBackup> = get_backups(save_dir, true, Some(game_number), settings)?
+        .into_iter()
+        .map(|backup| RankedBackup {
+            exact_match: hashes_match(&backup.hash, &live_hash),
+            size_delta: backup.size.abs_diff(live_size),
+            backup,
+        })
+        .collect();
+    ranked.sort_by_key(|r| (!r.exact_match, r.size_delta));
+    Ok(ranked)
+}
+
+/// Byte-level comparison of a backup's main save with the live save.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ByteDiff {
+    /// Size of the live save in bytes.
+    pub live_size: u64,
+    /// Uncompressed size of the backed-up save in bytes.
+    pub backup_size: u64,
+    /// Byte positions that differ, counting every byte past the end of the shorter save.
+    pub differing_bytes: u64,
+    /// Offset of the first differing byte, or the shorter save's length when it is a
+    /// prefix of the longer one; `None` when the files match.
+    pub first_difference_offset: Option<u64>,
+}
+
+/// Reads until `buf` is full or the reader is exhausted, returning the bytes read.
+fn read_chunk(reader: &mut dyn Read, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]).map_err(|e| e.to_string())? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Streams the live save and a stored save side by side, comparing them byte by byte.
+///
+/// Both files are read in buffered chunks, so large saves are never held in memory.
+/// Saves of different sizes are compared over their common length, and every byte
+/// past the end of the shorter one counts as differing.
+fn compare_with_live(live_path: &Path, stored: &StoredSaveFile) -> Result<ByteDiff, String> {
+    let mut live = BufReader::new(fs::File::open(live_path).map_err(|e| e.to_string())?);
+    let mut backup = stored.open_content()?;
+    let chunk_size = DEFAULT_COPY_BUFFER_SIZE;
+    let mut live_buf = vec![0_u8; chunk_size];
+    let mut backup_buf = vec![0_u8; chunk_size];
+    let mut live_size = 0_u64;
+    let mut backup_size = 0_u64;
+    let mut differing_bytes = 0_u64;
+    let mut first_difference_offset = None;
+    loop {
+        let live_read = read_chunk(&mut live, &mut live_buf)?;
+        let backup_read = read_chunk(backup.as_mut(), &mut backup_buf)?;
+        if live_read == 0 && backup_read == 0 {
+            break;
+        }
+        // Chunks only come up short at the end of a file, so offsets stay aligned.
+        let offset = live_size.max(backup_size);
+        let common = live_read.min(backup_read);
+        for (i, (a, b)) in live_buf[..common]
+            .iter()
+            .zip(&backup_buf[..common])
+            .enumerate()
+        {
+            if a != b {
+                differing_bytes += 1;
+                first_difference_offset.get_or_insert(offset + i as u64);
+            }
+        }
+        let excess = live_read.max(backup_read) - common;
+        if excess > 0 {
+            differing_bytes += excess as u64;
+            first_difference_offset.get_or_insert(offset + common as u64);
+        }
+        live_size += live_read as u64;
+        backup_size += backup_read as u64;
+    }
+
+    Ok(ByteDiff {
+        live_size,
+        backup_size,
+        differing_bytes,
+        first_difference_offset,
+    })
+}
+
+/// Counts the byte positions that differ between a backup's main save and the live save.
+pub fn byte_diff_count(
+    save_dir: &Path,
+    backup_folder_name: &str,
+    game_number: u32,
+    settings: &BackupSettings,
+) -> Result<ByteDiff, String> {
+    if Path::new(backup_folder_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        != Some(backup_folder_name)
+    {
+        return Err("Invalid backup folder name".to_string());
+    }
+    let paths = build_save_paths(save_dir, game_number);
+    if !paths.main_path.exists() {
+        return Err(format!(
+            "Main save file not found for game {}",
+            game_number + 1
+        ));
+    }
+    let backup_folder = backup_root_for(save_dir, settings).join(backup_folder_name);
+    let stored = find_stored_save_file(&backup_folder, &format!("gamesave_{}.sav", game_number))
+        .ok_or_else(|| format!("Backup {} has no save for this slot", backup_folder_name))?;
+    compare_with_live(&paths.main_path, &stored)
+}
+
+/// How far the live save has diverged from a backup's main save.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CurrentSaveDiff {
+    /// Whether the two saves have the same size and content.
+    pub identical: bool,
+    /// Offset of the first differing byte, or the shorter save's length when it is a
+    /// prefix of the longer one.
+    pub first_diff_offset: Option<u64>,
+    /// Byte positions that differ, counting every byte past the end of the shorter save.
+    pub byte_diff_count: u64,
+    /// Live save size minus backup size, in bytes.
+    pub size_delta: i64,
+}
+
+/// Compares a backup's main save with the live save of the same slot, byte by byte.
+///
+/// Uses the same comparison as `byte_diff_count`, with the backup's own slot.
+pub fn diff_backup_against_current(
+    save_dir: &Path,
+    backup_folder: &Path,
+) -> Result<CurrentSaveDiff, String> {
+    let folder_name = backup_folder
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?;
+    let info = filename_utils::parse_backup_folder_name(folder_name)
+        .ok_or_else(|| "Backup folder name did not match expected format".to_string())?;
+    let paths = build_save_paths(save_dir, info.game_number);
+    if !paths.main_path.exists() {
+        return Err(format!(
+            "Main save file not found for game {}",
+            info.game_number + 1
+        ));
+    }
+    let stored = find_stored_save_file(backup_folder, &paths.main_filename)
+        .ok_or_else(|| format!("Backup {} has no save for this slot", folder_name))?;
+
+    let diff = compare_with_live(&paths.main_path, &stored)?;
+    Ok(CurrentSaveDiff {
+        identical: diff.differing_bytes == 0,
+        first_diff_offset: diff.first_difference_offset,
+        byte_diff_count: diff.differing_bytes,
+        size_delta: diff.live_size as i64 - diff.backup_size as i64,
+    })
+}
@@ -0,0 +1,64 @@
+use super::common::LOCKED_FILE_NAME;
+use super::fileutil::folder_size;
+use super::index::BackupStore;
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Disk space used by the backup folders of a save directory.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct BackupStorageStats {
+    /// Bytes used by all backup folders.
+    pub total_bytes: u64,
+    /// Number of backup folders counted.
+    pub backup_count: usize,
+    /// Bytes used per game number (0-based).
+    pub per_game: HashMap<u32, u64>,
+    /// Bytes held by locked backups, which cleanup never reclaims.
+    pub locked_bytes: u64,
+    /// Bytes held by unlocked backups.
+    pub unlocked_bytes: u64,
+}
+
+/// Sums the on-disk size of every backup folder under `.backups`.
+///
+/// Unlike `BackupInfo::size`, which is only the main save, this counts every file in
+/// each folder: `.bak` files, compressed files, and metadata. Folders that do not follow
+/// the backup naming contract, and files like `index.json`, are not counted.
+pub fn get_backup_storage_stats(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<BackupStorageStats, String> {
+    let mut stats = BackupStorageStats::default();
+    let Some(store) = BackupStore::load_if_exists(save_dir, settings)? else {
+        return Ok(stats);
+    };
+
+    for entry in fs::read_dir(&store.root).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(info) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(filename_utils::parse_backup_folder_name)
+        else {
+            continue;
+        };
+
+        let size = folder_size(&path)?;
+        stats.total_bytes += size;
+        stats.backup_count += 1;
+        *stats.per_game.entry(info.game_number).or_default() += size;
+        if path.join(LOCKED_FILE_NAME).exists() {
+            stats.locked_bytes += size;
+        } else {
+            stats.unlocked_bytes += size;
+        }
+    }
+    Ok(stats)
+}
@@ -4,15 +4,89 @@
     reason = "file is the tests module declared in mod.rs; the inner mod groups the suite under the same name"
 )]
 mod tests {
-    use crate::backup::cleanup::delete_backups_batch;
-    use crate::backup::common::{BACKUP_DIR_NAME, INDEX_FILE_NAME};
-    use crate::backup::create::perform_backup_for_game;
-    use crate::backup::data::{build_save_paths, BackupInfo};
-    use crate::backup::hashing::calculate_hash;
-    use crate::backup::index::BackupStore;
-    use crate::backup::listing::{backup_info_from_folder, get_backups};
-    use crate::backup::notes::{set_backup_lock, set_backup_note};
-    use crate::backup::restore::restore_backup;
+    use crate::backup::activity::{
+        backup_activity_histogram, get_backup_time_bounds, DayCount, TimeBounds,
+    };
+    use crate::backup::catalog::{export_backup_catalog, CatalogFormat};
+    use crate::backup::cleanup::{
+        compact_index, delete_backup_folder, delete_backups_batch, enforce_backup_limit,
+        find_dangling_locks, find_incomplete_backups, preview_delete_backups_batch,
+        prune_by_total_size, prune_old_backups, remove_dangling_locks, remove_incomplete_backups,
+    };
+    use crate::backup::clock::detect_clock_skew;
+    use crate::backup::common::{
+        BACKUP_DIR_NAME, DELTA_BASE_FILE_NAME, HASH_FILE_NAME, INDEX_FILE_NAME, LOCKED_FILE_NAME,
+        MANIFEST_FILE_NAME, OWNER_FILE_NAME, QUARANTINE_DIR_NAME, QUARANTINE_FILE_NAME,
+        SAVE_DIR_FILE_NAME, SAVE_PATH_NOT_A_DIRECTORY, TRASH_DIR_NAME, TRASH_FILE_NAME,
+    };
+    use crate::backup::compression::{compress_backup, decompress_backup};
+    use crate::backup::create::{
+        backup_all_now, copy_save_files, next_folder_name_with, perform_backup_for_game,
+        perform_backup_for_game_internal, perform_backup_for_game_with,
+        perform_backup_with_progress, prime_index, rebuild_index, trigger_manual_backup,
+        write_hash_file, BackupOptions, BackupOutcome, BackupPhase, BackupProgress, NamingScheme,
+        DEFAULT_HASH_RECHECK_INTERVAL,
+    };
+    use crate::backup::data::{build_save_paths, read_settled_source_metadata, BackupInfo};
+    use crate::backup::delta::{apply_delta, encode_delta, DELTA_BLOCK_SIZE};
+    use crate::backup::diff::{compare_backups, diff_backup_sets, BackupComparison};
+    use crate::backup::fileutil::{
+        copy_file, folder_size, set_file_modified, DEFAULT_COPY_BUFFER_KB, DEFAULT_COPY_BUFFER_SIZE,
+    };
+    use crate::backup::hashing::{
+        aggregate_backups_hash, calculate_hash, hash_reader_with, hashes_match, tagged_hash,
+        HashAlgorithm,
+    };
+    use crate::backup::import::import_legacy_saves;
+    use crate::backup::index::{
+        ensure_backup_root, ensure_save_dir_is_directory, load_index, resolve_backup_root,
+        BackupIndex, BackupStore,
+    };
+    use crate::backup::listing::{
+        backup_info_from_folder, get_backup_details, get_backups, get_backups_page,
+        get_unannotated_backups, scan_backups_streaming,
+    };
+    use crate::backup::manifest::{read_manifest, write_manifest, BackupManifest};
+    use crate::backup::mirror::{
+        mirror_backup_to, remap_central_key, remap_central_key_in, save_key,
+    };
+    use crate::backup::notes::{
+        add_tag_batch, remove_tag_batch, set_backup_archived, set_backup_lock, set_backup_note,
+        set_backup_tags, toggle_backup_favorite,
+    };
+    use crate::backup::owner::{claim_backup_dir, classify_owner, release_backup_dir, OwnerStatus};
+    use crate::backup::quarantine::{list_quarantined, quarantine_backup, release_from_quarantine};
+    use crate::backup::rename::rename_backup;
+    use crate::backup::restore::{
+        plan_restore_full, prepare_restore, prepare_restore_to_slot, restore_backup,
+        restore_backup_cancellable, restore_backup_to_slot, restore_backup_with, PendingRestores,
+        RestoreCancelToken, RestoreFileAction, RestoreRequest, PRE_RESTORE_NOTE,
+    };
+    use crate::backup::retention::suggest_retention;
+    use crate::backup::save_format::{
+        attach_save_metadata, parse_save_metadata, read_save_metadata, SaveEncoding,
+    };
+    use crate::backup::session::{
+        create_session_snapshot, prepare_session_restore, restore_session, session_restore_request,
+    };
+    use crate::backup::settings::{load_directory_settings, BackupSettings, DirectorySettings};
+    use crate::backup::similarity::{
+        byte_diff_count, diff_backup_against_current, rank_backups_by_similarity, ByteDiff,
+        CurrentSaveDiff,
+    };
+    use crate::backup::storage::get_backup_storage_stats;
+    use crate::backup::trash::{
+        empty_trash, list_trash, purge_trash_older_than, restore_from_trash,
+    };
+    use crate::backup::verify::{
+        audit_timestamp_consistency, verify_all_backups, verify_backup, VerifyStatus,
+    };
+    use crate::filename_utils::{
+        format_backup_folder_name, parse_backup_folder_name, BackupNameFormat,
+    };
+    use chrono::{Duration, Local, TimeZone};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
     use std::fs::{self, File};
     use std::io::Write;
     use std::path::PathBuf;
@@ -92,7 +166,7 @@ mod tests {
         assert_ne!(result_new.unwrap(), backup_folder); // Different timestamp folder
 
         // 4. List backups
-        let backups = get_backups(save_dir, true, None).unwrap();
+        let backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups.len(), 2);
         assert_eq!(backups[0].game_number, 1);
         // Verify folder name logic in listing
@@ -142,7 +216,7 @@ mod tests {
         }
 
         // Restore
-        restore_backup(&backup_folder, save_dir).unwrap();
+        restore_backup(&backup_folder, save_dir, &BackupSettings::default()).unwrap();
 
         let content = fs::read_to_string(&main_sav).unwrap();
         assert_eq!(content.trim(), "original");
@@ -163,7 +237,7 @@ mod tests {
         }
 
         // Usefulness: verifies exact lowercase SHA-256 encoding, which the .hash smoke test does not cover.
-        let hash = calculate_hash(&save_file).unwrap();
+        let hash = calculate_hash(&save_file, &BackupSettings::default()).unwrap();
 
         assert_eq!(
             hash,
@@ -252,7 +326,7 @@ mod tests {
             perform_backup_for_game(save_dir, game_number, limit).unwrap();
         }
 
-        let backups = get_backups(save_dir, true, None).unwrap();
+        let backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups.len(), 2);
 
         // Helper to check content of a backup
@@ -311,7 +385,7 @@ mod tests {
             .unwrap();
 
         // Check backups
-        let backups = get_backups(save_dir, true, None).unwrap();
+        let backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups.len(), 3);
 
         // Verify content
@@ -335,7 +409,7 @@ mod tests {
         }
         perform_backup_for_game(save_dir, game_number, limit).unwrap();
 
-        let backups_final = get_backups(save_dir, true, None).unwrap();
+        let backups_final = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups_final.len(), 3);
 
         check_content(&backups_final[0], "data 4");
@@ -372,7 +446,7 @@ mod tests {
             .unwrap();
 
         // 3. Restore first backup
-        restore_backup(&backup1_path, save_dir).unwrap();
+        restore_backup(&backup1_path, save_dir, &BackupSettings::default()).unwrap();
 
         // 4. Try to backup again - it should be skipped because it matches backup 1
         let result = perform_backup_for_game(save_dir, game_number, 100).unwrap();
@@ -382,7 +456,7 @@ mod tests {
         );
 
         // 5. Verify index was updated to point to backup 1
-        let store = BackupStore::new(save_dir).unwrap();
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
         let entry = store.index.games.get(&game_number).unwrap();
         assert_eq!(
             entry.last_backup_path,
@@ -413,27 +487,37 @@ mod tests {
             .to_string();
 
         // 2. Set a note
-        set_backup_note(save_dir, &folder_name, Some("My Note".to_string())).unwrap();
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("My Note".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
 
-        // 3. Verify in index
-        let store = BackupStore::new(save_dir).unwrap();
-        assert_eq!(store.index.notes.get(&folder_name).unwrap(), "My Note");
+        // 3. Verify in index (keyed by the backup's stable ID)
+        let id = read_manifest(&backup_path).unwrap().id;
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store.index.notes.get(&id).unwrap(), "My Note");
 
         // 4. Update note
-        set_backup_note(save_dir, &folder_name, Some("Updated Note".to_string())).unwrap();
-        let store2 = BackupStore::new(save_dir).unwrap();
-        assert_eq!(
-            store2.index.notes.get(&folder_name).unwrap(),
-            "Updated Note"
-        );
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("Updated Note".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let store2 = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store2.index.notes.get(&id).unwrap(), "Updated Note");
 
         // 5. Verify get_backups retrieves it
-        let backups = get_backups(save_dir, true, None).unwrap();
+        let backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups[0].note.as_deref(), Some("Updated Note"));
 
         // 6. Remove note
-        set_backup_note(save_dir, &folder_name, None).unwrap();
-        let store3 = BackupStore::new(save_dir).unwrap();
+        set_backup_note(save_dir, &folder_name, None, &BackupSettings::default()).unwrap();
+        let store3 = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
         assert!(!store3.index.notes.contains_key(&folder_name));
     }
 
@@ -454,6 +538,7 @@ mod tests {
                 last_source_size: 100,
                 last_source_modified: 1000,
                 last_backup_path: folder.to_string(),
+                last_verified: 0,
             },
         );
         index
@@ -466,10 +551,11 @@ mod tests {
                 last_source_size: 200,
                 last_source_modified: 2000,
                 last_backup_path: other_folder.to_string(),
+                last_verified: 0,
             },
         );
 
-        index.prune_deleted(folder);
+        index.prune_deleted(folder, None);
 
         assert!(
             !index.notes.contains_key(folder),
@@ -512,19 +598,46 @@ mod tests {
         let folder_v1 = create_backup("v1"); // oldest — will be deleted
         let folder_v2 = create_backup("v2"); // newest — will be kept
 
-        set_backup_note(save_dir, &folder_v1, Some("note v1".to_string())).unwrap();
-        set_backup_note(save_dir, &folder_v2, Some("note v2".to_string())).unwrap();
+        set_backup_note(
+            save_dir,
+            &folder_v1,
+            Some("note v1".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        set_backup_note(
+            save_dir,
+            &folder_v2,
+            Some("note v2".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        let root = BackupStore::new(save_dir, &BackupSettings::default())
+            .unwrap()
+            .root;
+        let id_v1 = read_manifest(&root.join(&folder_v1)).unwrap().id;
+        let id_v2 = read_manifest(&root.join(&folder_v2)).unwrap().id;
 
         // Delete all but the latest (removes v1)
-        delete_backups_batch(save_dir, &[game_number], true, false).unwrap();
+        delete_backups_batch(
+            save_dir,
+            &[game_number],
+            true,
+            false,
+            false,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
 
-        let store = BackupStore::new(save_dir).unwrap();
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
         assert!(
-            !store.index.notes.contains_key(&folder_v1),
+            !store.index.notes.contains_key(&id_v1),
             "note for deleted backup should be pruned from index"
         );
         assert!(
-            store.index.notes.contains_key(&folder_v2),
+            store.index.notes.contains_key(&id_v2),
             "note for retained backup should remain in index"
         );
     }
@@ -547,7 +660,13 @@ mod tests {
             .unwrap()
             .unwrap();
         let folder1 = backup1.file_name().unwrap().to_string_lossy().to_string();
-        set_backup_note(save_dir, &folder1, Some("old note".to_string())).unwrap();
+        set_backup_note(
+            save_dir,
+            &folder1,
+            Some("old note".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
 
         // Create second backup (within limit, no eviction)
         std::thread::sleep(std::time::Duration::from_secs(2));
@@ -567,7 +686,7 @@ mod tests {
 
         assert!(!backup1.exists(), "evicted backup folder should be deleted");
 
-        let store = BackupStore::new(save_dir).unwrap();
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
         assert!(
             !store.index.notes.contains_key(&folder1),
             "note for limit-evicted backup should be pruned from index"
@@ -612,21 +731,39 @@ mod tests {
         create_backup("v3", false);
         create_backup("v4", false); // Newest
 
-        let backups = get_backups(save_dir, true, None).unwrap();
+        let backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups.len(), 4);
 
         // Scenario 1: Delete all but latest, EXCLUDE locked.
-        let deleted = delete_backups_batch(save_dir, &[game_number], true, false).unwrap();
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[game_number],
+            true,
+            false,
+            false,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(deleted, 2, "Should delete v1 and v3");
 
-        let remaining = get_backups(save_dir, true, None).unwrap();
+        let remaining = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(remaining.len(), 2);
 
-        // Scenario 2: Delete ALL, INCLUDE locked.
-        let deleted_2 = delete_backups_batch(save_dir, &[game_number], false, true).unwrap();
+        // Scenario 2: Delete ALL, INCLUDE locked, forcing past the keep-one safety.
+        let deleted_2 = delete_backups_batch(
+            save_dir,
+            &[game_number],
+            false,
+            true,
+            true,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(deleted_2, 2);
 
-        let final_backups = get_backups(save_dir, true, None).unwrap();
+        let final_backups = get_backups(save_dir, true, None, &BackupSettings::default()).unwrap();
         assert!(final_backups.is_empty());
     }
 
@@ -712,7 +849,15 @@ mod tests {
         perms.set_readonly(true);
         fs::set_permissions(&index_path, perms.clone()).unwrap();
 
-        let result = delete_backups_batch(save_dir, &[game_number], false, true);
+        let result = delete_backups_batch(
+            save_dir,
+            &[game_number],
+            false,
+            true,
+            true,
+            &BackupSettings::default(),
+            None,
+        );
 
         perms.set_readonly(false);
         fs::set_permissions(&index_path, perms).unwrap();
@@ -762,7 +907,7 @@ mod tests {
         perms.set_readonly(true);
         fs::set_permissions(&index_path, perms.clone()).unwrap();
 
-        let result = restore_backup(&backup_folder, save_dir);
+        let result = restore_backup(&backup_folder, save_dir, &BackupSettings::default());
 
         perms.set_readonly(false);
         fs::set_permissions(&index_path, perms).unwrap();
@@ -772,4 +917,3753 @@ mod tests {
             "restore should return Err when index cannot be written"
         );
     }
+
+    /// Tests that a session snapshot forces backups of every slot and restores them together.
+    #[test]
+    fn test_session_snapshot_and_restore() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let slot0 = save_dir.join("gamesave_0.sav");
+        let slot1 = save_dir.join("gamesave_1.sav");
+        fs::write(&slot0, "slot0 day 10").unwrap();
+        fs::write(&slot1, "slot1 day 10").unwrap();
+
+        // Regular backups exist, so a non-forced backup would be deduplicated.
+        let regular0 = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+
+        let folders =
+            create_session_snapshot(save_dir, "  Day 10 ", 100, &BackupSettings::default())
+                .unwrap();
+        assert_eq!(folders.len(), 2, "one forced backup per slot");
+        assert!(!folders.contains(&regular0.file_name().unwrap().to_string_lossy().to_string()));
+        assert_eq!(
+            get_backups(save_dir, true, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            4
+        );
+
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store.index.sessions.get("Day 10"), Some(&folders));
+
+        fs::write(&slot0, "slot0 day 12").unwrap();
+        fs::write(&slot1, "slot1 day 12").unwrap();
+
+        restore_session(save_dir, "Day 10", &BackupSettings::default()).unwrap();
+
+        assert_eq!(fs::read_to_string(&slot0).unwrap(), "slot0 day 10");
+        assert_eq!(fs::read_to_string(&slot1).unwrap(), "slot1 day 10");
+        assert!(restore_session(save_dir, "Day 99", &BackupSettings::default()).is_err());
+    }
+
+    /// Tests that copies made with a custom buffer size match those made with the default.
+    #[test]
+    fn test_copy_with_custom_buffer_matches_default() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("gamesave_0.sav");
+        let content: Vec<u8> = (0..300_000_u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&source, &content).unwrap();
+
+        let default_copy = dir.path().join("default.sav");
+        let small_copy = dir.path().join("small.sav");
+        let large_copy = dir.path().join("large.sav");
+
+        let copied = copy_file(&source, &default_copy, DEFAULT_COPY_BUFFER_KB * 1024).unwrap();
+        copy_file(&source, &small_copy, 1000).unwrap();
+        copy_file(&source, &large_copy, 1024 * 1024).unwrap();
+
+        assert_eq!(copied, content.len() as u64);
+        assert_eq!(fs::read(&default_copy).unwrap(), content);
+        assert_eq!(fs::read(&small_copy).unwrap(), content);
+        assert_eq!(fs::read(&large_copy).unwrap(), content);
+    }
+
+    /// Tests clock skew detection against fabricated timestamps.
+    #[test]
+    fn test_detect_clock_skew() {
+        use chrono::{Local, TimeZone};
+
+        let newest = Local.with_ymd_and_hms(2024, 6, 14, 12, 0, 0).unwrap();
+        let threshold = 300;
+
+        let behind = newest - chrono::Duration::hours(2);
+        assert_eq!(detect_clock_skew(newest, behind, threshold), Some(7200));
+
+        let slightly_behind = newest - chrono::Duration::seconds(60);
+        assert_eq!(detect_clock_skew(newest, slightly_behind, threshold), None);
+
+        let ahead = newest + chrono::Duration::days(1);
+        assert_eq!(detect_clock_skew(newest, ahead, threshold), None);
+    }
+
+    /// Tests that loose legacy saves are imported as listable backups, skipping duplicates.
+    #[test]
+    fn test_import_legacy_saves() {
+        let dir = tempdir().unwrap();
+        let source_dir = dir.path().join("old_saves");
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::create_dir_all(&save_dir).unwrap();
+
+        let write_legacy = |name: &str, content: &str, hours_ago: u64| {
+            let path = source_dir.join(name);
+            fs::write(&path, content).unwrap();
+            let mtime =
+                std::time::SystemTime::now() - std::time::Duration::from_secs(hours_ago * 3600);
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        };
+
+        write_legacy("gamesave_0 (1).sav", "slot0 early", 3);
+        write_legacy("gamesave_0 (2).sav", "slot0 late", 2);
+        write_legacy("gamesave_0 (3).sav", "slot0 early", 1); // duplicate content
+        write_legacy("gamesave_1 - Copy.sav", "slot1", 1);
+        write_legacy("readme.txt", "not a save", 1);
+
+        let imported =
+            import_legacy_saves(&source_dir, &save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(imported, 3);
+
+        let backups = get_backups(&save_dir, true, None, &BackupSettings::default()).unwrap();
+        assert_eq!(backups.len(), 3);
+        assert_eq!(backups.iter().filter(|b| b.game_number == 0).count(), 2);
+        assert!(backups.iter().all(|b| !b.hash.is_empty()));
+
+        // Re-importing the same folder creates nothing new.
+        assert_eq!(
+            import_legacy_saves(&source_dir, &save_dir, &BackupSettings::default()).unwrap(),
+            0
+        );
+    }
+
+    /// Tests that the full restore plan reports an overwrite and a pending safety backup.
+    #[test]
+    fn test_plan_restore_full_reports_overwrite_and_safety_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "backed up").unwrap();
+
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        // Unbacked-up progress made after the backup was taken.
+        fs::write(&main_sav, "newer progress").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&main_sav)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        let plan =
+            plan_restore_full(&backup_folder, save_dir, true, &BackupSettings::default()).unwrap();
+        assert_eq!(plan.game_number, 0);
+        assert_eq!(plan.files.len(), 1);
+        assert_eq!(plan.files[0].file_name, "gamesave_0.sav");
+        assert_eq!(plan.files[0].action, RestoreFileAction::Overwrite);
+        assert!(plan.safety_backup_pending);
+        assert!(plan.live_save_newer);
+
+        // Restoring makes the live save match, leaving nothing to protect.
+        restore_backup(&backup_folder, save_dir, &BackupSettings::default()).unwrap();
+        let plan_after =
+            plan_restore_full(&backup_folder, save_dir, true, &BackupSettings::default()).unwrap();
+        assert_eq!(plan_after.files[0].action, RestoreFileAction::Unchanged);
+        assert!(!plan_after.safety_backup_pending);
+    }
+
+    /// Tests that no safety backup is reported pending when snapshots before restore are off.
+    #[test]
+    fn test_plan_restore_full_without_snapshot_has_no_pending_safety_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "backed up").unwrap();
+
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(&main_sav, "newer progress").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&main_sav)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        let plan =
+            plan_restore_full(&backup_folder, save_dir, false, &BackupSettings::default()).unwrap();
+        assert_eq!(plan.files[0].action, RestoreFileAction::Overwrite);
+        assert!(!plan.safety_backup_pending);
+        assert!(plan.live_save_newer);
+    }
+
+    /// Tests round-tripping a backup through compression and back without losing content.
+    #[test]
+    fn test_compress_and_decompress_backup_round_trip() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_3.sav");
+        let bak_sav = save_dir.join("gamesave_3.sav.bak");
+        let content = "compressible ".repeat(500);
+        fs::write(&main_sav, &content).unwrap();
+        fs::write(&bak_sav, "bak content").unwrap();
+
+        let backup_folder = perform_backup_for_game(save_dir, 3, 100).unwrap().unwrap();
+        set_backup_lock(&backup_folder, true).unwrap();
+        let before = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+
+        compress_backup(&backup_folder, &BackupSettings::default()).unwrap();
+        assert!(!backup_folder.join("gamesave_3.sav").exists());
+        assert!(backup_folder.join("gamesave_3.sav.gz").exists());
+        assert!(backup_folder.join("gamesave_3.sav.bak.gz").exists());
+        assert!(backup_folder.join(".hash").exists());
+
+        let compressed = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        assert_eq!(compressed.size, content.len() as u64);
+        assert_eq!(compressed.hash, before.hash);
+        assert!(compressed.locked, "lock marker should survive compression");
+
+        fs::write(&main_sav, "changed").unwrap();
+        restore_backup(&backup_folder, save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), content);
+        assert_eq!(fs::read_to_string(&bak_sav).unwrap(), "bak content");
+
+        decompress_backup(&backup_folder, &BackupSettings::default()).unwrap();
+        assert!(!backup_folder.join("gamesave_3.sav.gz").exists());
+        assert_eq!(
+            fs::read_to_string(backup_folder.join("gamesave_3.sav")).unwrap(),
+            content
+        );
+        let after = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        assert_eq!(after.hash, before.hash);
+        assert_eq!(after.size, before.size);
+    }
+
+    /// Tests that settings passed to one backup do not leak into the next.
+    #[test]
+    fn test_backup_settings_apply_per_call() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "slot zero").unwrap();
+        fs::write(save_dir.join("gamesave_1.sav"), "slot one").unwrap();
+
+        let custom = BackupSettings {
+            naming_scheme: NamingScheme::Counter,
+            hash_algorithm: HashAlgorithm::Blake3,
+            compress_backups: true,
+            ..BackupSettings::default()
+        };
+        let custom_folder = perform_backup_for_game_with(save_dir, 0, 100, &custom)
+            .unwrap()
+            .unwrap();
+        let default_folder = perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+
+        assert_eq!(custom_folder.file_name().unwrap(), "Game 1 - #0001");
+        assert!(custom_folder.join("gamesave_0.sav.gz").exists());
+        let custom_hash = fs::read_to_string(custom_folder.join(HASH_FILE_NAME)).unwrap();
+        assert!(custom_hash.starts_with("blake3:"), "{}", custom_hash);
+
+        assert!(
+            parse_backup_folder_name(&default_folder.file_name().unwrap().to_string_lossy())
+                .is_some()
+        );
+        assert!(default_folder.join("gamesave_1.sav").exists());
+        let default_hash = fs::read_to_string(default_folder.join(HASH_FILE_NAME)).unwrap();
+        assert!(!default_hash.contains(':'), "{}", default_hash);
+    }
+
+    /// Tests that a note keyed by the stable backup ID survives a folder rename.
+    #[test]
+    fn test_note_survives_folder_rename_via_stable_id() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "data").unwrap();
+
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let folder_name = backup_folder
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let original = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        let id = original.id.clone().expect("new backups carry a stable ID");
+
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("Before the boss".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store.index.notes.get(&id).unwrap(), "Before the boss");
+        assert!(!store.index.notes.contains_key(&folder_name));
+
+        let renamed = backup_folder.with_file_name("Game 1 - 01-Jan-2024 10-00-00 AM");
+        fs::rename(&backup_folder, &renamed).unwrap();
+
+        let listed = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        assert_eq!(listed.filename, "Game 1 - 01-Jan-2024 10-00-00 AM");
+        assert_eq!(listed.id.as_deref(), Some(id.as_str()));
+        assert_eq!(listed.note.as_deref(), Some("Before the boss"));
+    }
+
+    /// Tests that legacy notes keyed by folder name still resolve and migrate to the ID.
+    #[test]
+    fn test_legacy_folder_name_note_fallback() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "data").unwrap();
+
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let folder_name = backup_folder
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        fs::remove_file(backup_folder.join(MANIFEST_FILE_NAME)).unwrap();
+
+        let mut store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        store
+            .index
+            .notes
+            .insert(folder_name.clone(), "legacy".to_string());
+        store.save().unwrap();
+
+        let listed = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        assert!(listed.id.is_none());
+        assert_eq!(listed.note.as_deref(), Some("legacy"));
+
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("migrated".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let listed = get_backups(save_dir, true, None, &BackupSettings::default())
+            .unwrap()
+            .remove(0);
+        let id = listed.id.expect("editing a legacy note assigns an ID");
+        let store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store.index.notes.get(&id).unwrap(), "migrated");
+        assert!(!store.index.notes.contains_key(&folder_name));
+    }
+
+    /// Tests that a backup page reports the total count and the summed size of its folders.
+    #[test]
+    fn test_get_backups_page_sums_folder_sizes() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+
+        for game in 0..3 {
+            fs::write(
+                save_dir.join(format!("gamesave_{}.sav", game)),
+                "x".repeat(100 * (game as usize + 1)),
+            )
+            .unwrap();
+            perform_backup_for_game(save_dir, game, 100)
+                .unwrap()
+                .unwrap();
+        }
+
+        let page = get_backups_page(save_dir, 1, 2, None, &BackupSettings::default()).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.backups.len(), 2);
+
+        let expected: u64 = page
+            .backups
+            .iter()
+            .flat_map(|b| fs::read_dir(&b.path).unwrap())
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum();
+        assert_eq!(page.page_size_bytes, expected);
+
+        let empty = get_backups_page(save_dir, 10, 5, None, &BackupSettings::default()).unwrap();
+        assert_eq!(empty.total, 3);
+        assert!(empty.backups.is_empty());
+        assert_eq!(empty.page_size_bytes, 0);
+    }
+
+    /// Tests that cancelling a restore mid-way leaves the target's files unchanged.
+    #[test]
+    fn test_cancelled_restore_leaves_original_intact() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        let secondary_sav = save_dir.join("gamesave_0.sav.bak");
+        fs::write(&main_sav, "backup main").unwrap();
+        fs::write(&secondary_sav, "backup secondary").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        fs::write(&main_sav, "live main").unwrap();
+        fs::write(&secondary_sav, "live secondary").unwrap();
+
+        // Cancel after the first file has been staged.
+        let checks = std::cell::Cell::new(0);
+        let result =
+            restore_backup_with(&backup_folder, save_dir, &BackupSettings::default(), || {
+                checks.set(checks.get() + 1);
+                checks.get() > 1
+            });
+
+        assert_eq!(result.unwrap_err(), "Restore cancelled");
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "live main");
+        assert_eq!(
+            fs::read_to_string(&secondary_sav).unwrap(),
+            "live secondary"
+        );
+        let leftovers: Vec<_> = fs::read_dir(save_dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".restore-tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "staged temps should be cleaned up");
+
+        // An uncancelled restore swaps every file in.
+        restore_backup_with(&backup_folder, save_dir, &BackupSettings::default(), || {
+            false
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "backup main");
+        assert_eq!(
+            fs::read_to_string(&secondary_sav).unwrap(),
+            "backup secondary"
+        );
+    }
+
+    /// Tests that a limit of one never evicts the newest existing backup of a slot.
+    #[test]
+    fn test_limit_enforcement_keeps_newest_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+
+        fs::write(&main_sav, "v1").unwrap();
+        let first = perform_backup_for_game(save_dir, 0, 1).unwrap().unwrap();
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        fs::write(&main_sav, "v2").unwrap();
+        let second = perform_backup_for_game(save_dir, 0, 1).unwrap().unwrap();
+
+        assert!(
+            first.exists(),
+            "newest existing backup should survive eviction"
+        );
+        assert!(second.exists());
+    }
+
+    /// Tests that batch "delete all" keeps the newest backup unless forced.
+    #[test]
+    fn test_batch_delete_all_keeps_last_backup_unless_forced() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+
+        fs::write(&main_sav, "v1").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        fs::write(&main_sav, "v2").unwrap();
+        let newest = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[0],
+            false,
+            true,
+            false,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(deleted, 1);
+        let remaining = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(PathBuf::from(&remaining[0].path), newest);
+
+        assert!(delete_backup_folder(&newest, false, &BackupSettings::default()).is_err());
+        assert!(newest.exists());
+
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[0],
+            false,
+            true,
+            true,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Tests that single-backup progress reports totals matching the save file size.
+    #[test]
+    fn test_backup_progress_totals_match_file_size() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, vec![7_u8; 100_000]).unwrap();
+        let file_size = fs::metadata(&main_sav).unwrap().len();
+
+        let mut events: Vec<BackupProgress> = Vec::new();
+        let created =
+            perform_backup_with_progress(save_dir, 0, 100, &BackupSettings::default(), |p| {
+                events.push(p)
+            })
+            .unwrap()
+            .expect("backup should be created");
+        assert!(created.join("gamesave_0.sav").exists());
+
+        for phase in [BackupPhase::Hashing, BackupPhase::Copying] {
+            let last = events
+                .iter()
+                .rfind(|p| p.phase == phase)
+                .expect("each phase should report progress");
+            assert_eq!(last.total_bytes, file_size);
+            assert_eq!(last.bytes_processed, file_size);
+        }
+    }
+
+    /// Tests that the aggregate hash is stable and changes when backups are added or removed.
+    #[test]
+    fn test_aggregate_backups_hash_tracks_backup_set() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+
+        fs::write(&main_sav, "v1").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        fs::write(&main_sav, "v2").unwrap();
+        let second = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        let two = aggregate_backups_hash(save_dir, 0, &BackupSettings::default()).unwrap();
+        assert_eq!(
+            two,
+            aggregate_backups_hash(save_dir, 0, &BackupSettings::default()).unwrap()
+        );
+
+        // Other slots do not affect the aggregate.
+        fs::write(save_dir.join("gamesave_1.sav"), "other").unwrap();
+        perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+        assert_eq!(
+            two,
+            aggregate_backups_hash(save_dir, 0, &BackupSettings::default()).unwrap()
+        );
+
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        fs::write(&main_sav, "v3").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let three = aggregate_backups_hash(save_dir, 0, &BackupSettings::default()).unwrap();
+        assert_ne!(two, three);
+
+        fs::remove_dir_all(&second).unwrap();
+        let after_removal =
+            aggregate_backups_hash(save_dir, 0, &BackupSettings::default()).unwrap();
+        assert_ne!(three, after_removal);
+        assert_ne!(two, after_removal);
+    }
+
+    /// Tests that new backups are mirrored and that a mirror failure leaves the primary intact.
+    #[test]
+    fn test_backup_mirrored_to_secondary_directory() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(&save_dir).unwrap();
+        let mirror_root = dir.path().join("mirror");
+        fs::create_dir_all(&mirror_root).unwrap();
+        let main_sav = save_dir.join("gamesave_0.sav");
+
+        fs::write(&main_sav, "v1").unwrap();
+        let mirrored_settings = BackupSettings {
+            mirror_directory: Some(mirror_root.clone()),
+            ..BackupSettings::default()
+        };
+        let first = perform_backup_for_game_with(&save_dir, 0, 100, &mirrored_settings);
+
+        // A mirror root that is a file cannot hold folders, so mirroring fails.
+        let blocked_root = dir.path().join("blocked");
+        fs::write(&blocked_root, "not a directory").unwrap();
+        let blocked_settings = BackupSettings {
+            mirror_directory: Some(blocked_root),
+            ..BackupSettings::default()
+        };
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        fs::write(&main_sav, "v2").unwrap();
+        let second = perform_backup_for_game_with(&save_dir, 0, 100, &blocked_settings);
+
+        let first = first.unwrap().unwrap();
+        let mirrored = mirror_root
+            .join(save_key(&save_dir))
+            .join(first.file_name().unwrap());
+        assert_eq!(
+            fs::read_to_string(mirrored.join("gamesave_0.sav")).unwrap(),
+            "v1"
+        );
+        assert!(mirrored.join(HASH_FILE_NAME).exists());
+
+        let second = second.expect("mirror failure must not fail the primary backup");
+        assert!(second.unwrap().join("gamesave_0.sav").exists());
+    }
+
+    /// Tests that lock markers outside valid backups are detected and cleaned up.
+    #[test]
+    fn test_find_and_remove_dangling_locks() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "data").unwrap();
+        let valid = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        set_backup_lock(&valid, true).unwrap();
+
+        // Backup whose main save was deleted.
+        fs::write(save_dir.join("gamesave_1.sav"), "other").unwrap();
+        let broken = perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+        set_backup_lock(&broken, true).unwrap();
+        fs::remove_file(broken.join("gamesave_1.sav")).unwrap();
+
+        // Folder that is not a backup at all.
+        let stray = save_dir.join(BACKUP_DIR_NAME).join("stray");
+        fs::create_dir_all(&stray).unwrap();
+        fs::write(stray.join(".locked"), "").unwrap();
+
+        let dangling = find_dangling_locks(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(dangling.len(), 2);
+        assert!(dangling
+            .iter()
+            .all(|p| !p.starts_with(&*valid.to_string_lossy())));
+
+        assert_eq!(
+            remove_dangling_locks(save_dir, &BackupSettings::default()).unwrap(),
+            2
+        );
+        assert!(find_dangling_locks(save_dir, &BackupSettings::default())
+            .unwrap()
+            .is_empty());
+        assert!(valid.join(".locked").exists());
+        assert!(!stray.join(".locked").exists());
+    }
+
+    /// Tests that a limit of 0 (unlimited) never evicts backups by count.
+    #[test]
+    fn test_unlimited_limit_disables_count_enforcement() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+
+        for i in 0..4 {
+            fs::write(&main_sav, format!("v{}", i)).unwrap();
+            perform_backup_for_game(save_dir, 0, 0).unwrap().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(1100));
+        }
+
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    /// Tests that a primed index skips a duplicate of the newest backup without a content scan.
+    #[test]
+    fn test_prime_index_enables_fast_path_dedup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "v1").unwrap();
+        let newest = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        // Simulate backups imported from elsewhere: folders exist but the index is empty.
+        fs::remove_file(save_dir.join(BACKUP_DIR_NAME).join(INDEX_FILE_NAME)).unwrap();
+        assert!(BackupStore::new(save_dir, &BackupSettings::default())
+            .unwrap()
+            .index
+            .games
+            .is_empty());
+
+        assert_eq!(
+            prime_index(save_dir, &BackupSettings::default()).unwrap(),
+            1
+        );
+        let mut store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        let entry = store.index.games.get(&0).expect("slot should be primed");
+        assert_eq!(
+            entry.last_backup_path,
+            newest.file_name().unwrap().to_string_lossy()
+        );
+
+        // An empty backup list rules out the content scan; only the index can dedup.
+        let root = store.root.clone();
+        let result = perform_backup_for_game_internal(
+            save_dir,
+            &root,
+            0,
+            &mut store.index,
+            &[],
+            &BackupSettings::default(),
+            BackupOptions {
+                limit: 100,
+                ..BackupOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            BackupOutcome::SkippedDuplicate,
+            "duplicate should be skipped via the index"
+        );
+
+        // Already-primed slots are left alone.
+        assert_eq!(
+            prime_index(save_dir, &BackupSettings::default()).unwrap(),
+            0
+        );
+    }
+
+    /// Tests owner marker classification for missing, own, stale, and conflicting owners.
+    #[test]
+    fn test_classify_owner_detects_stale_and_conflict() {
+        let alive = |pid: u32| pid == 200;
+        assert_eq!(classify_owner(None, 100, alive), OwnerStatus::Unowned);
+        assert_eq!(
+            classify_owner(Some(100), 100, alive),
+            OwnerStatus::OwnedBySelf
+        );
+        assert_eq!(
+            classify_owner(Some(200), 100, alive),
+            OwnerStatus::Conflict(200)
+        );
+        assert_eq!(
+            classify_owner(Some(300), 100, alive),
+            OwnerStatus::Stale(300)
+        );
+    }
+
+    /// Tests that claiming replaces a stale marker and release removes only our own.
+    #[test]
+    fn test_claim_backup_dir_replaces_stale_owner() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let owner_path = save_dir.join(BACKUP_DIR_NAME).join(OWNER_FILE_NAME);
+        fs::create_dir_all(owner_path.parent().unwrap()).unwrap();
+        // PIDs are bounded well below u32::MAX on supported platforms, so this is stale.
+        fs::write(&owner_path, u32::MAX.to_string()).unwrap();
+
+        assert_eq!(
+            claim_backup_dir(save_dir, &BackupSettings::default()).unwrap(),
+            None
+        );
+        assert_eq!(
+            fs::read_to_string(&owner_path).unwrap(),
+            std::process::id().to_string()
+        );
+
+        release_backup_dir(save_dir, &BackupSettings::default());
+        assert!(!owner_path.exists());
+    }
+
+    /// Tests that counter names advance per slot, skip deleted counters, and reseed from disk.
+    #[test]
+    fn test_counter_naming_never_reuses_counters() {
+        let dir = tempdir().unwrap();
+        let backup_root = dir.path();
+        let now = Local::now();
+        let mut index = BackupIndex::default();
+        let next = |index: &mut BackupIndex, game_number: u32| {
+            let name = next_folder_name_with(
+                NamingScheme::Counter,
+                BackupNameFormat::default(),
+                backup_root,
+                index,
+                game_number,
+                now,
+            );
+            fs::create_dir(backup_root.join(&name)).unwrap();
+            name
+        };
+
+        assert_eq!(next(&mut index, 0), "Game 1 - #0001");
+        assert_eq!(next(&mut index, 0), "Game 1 - #0002");
+        assert_eq!(next(&mut index, 1), "Game 2 - #0001");
+
+        fs::remove_dir(backup_root.join("Game 1 - #0002")).unwrap();
+        assert_eq!(next(&mut index, 0), "Game 1 - #0003");
+
+        // A fresh index continues after the highest counter already on disk
+        let mut rebuilt = BackupIndex::default();
+        assert_eq!(next(&mut rebuilt, 0), "Game 1 - #0004");
+    }
+
+    /// Tests that counter-named backups are listed by their manifest time, not their counter.
+    #[test]
+    fn test_counter_backups_sort_by_manifest_timestamp() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let now = Local::now();
+
+        for (folder_name, created_at) in [
+            ("Game 1 - #0001", now),
+            ("Game 1 - #0002", now - Duration::hours(2)),
+            ("Game 1 - #0003", now - Duration::hours(1)),
+        ] {
+            let folder = backup_root.join(folder_name);
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), folder_name).unwrap();
+            write_manifest(&folder, &BackupManifest::new(Some(created_at))).unwrap();
+        }
+
+        let names: Vec<String> = get_backups(save_dir, false, None, &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.filename)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Game 1 - #0001", "Game 1 - #0003", "Game 1 - #0002"]
+        );
+    }
+
+    /// Tests that backups are bucketed per local calendar day, oldest day first.
+    #[test]
+    fn test_backup_activity_histogram_buckets_by_day() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let day_one = Local.with_ymd_and_hms(2024, 3, 9, 10, 0, 0).unwrap();
+        let day_two = Local.with_ymd_and_hms(2024, 3, 10, 9, 0, 0).unwrap();
+
+        for (game_number, timestamp) in [
+            (0, day_one),
+            (0, day_one + Duration::hours(1)),
+            (1, day_one + Duration::hours(2)),
+            (0, day_two),
+        ] {
+            let folder = backup_root.join(format_backup_folder_name(game_number, timestamp));
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join(format!("gamesave_{}.sav", game_number)), "data").unwrap();
+        }
+
+        let day = |day: &str, count: usize| DayCount {
+            day: day.to_string(),
+            count,
+        };
+        assert_eq!(
+            backup_activity_histogram(save_dir, None, &BackupSettings::default()).unwrap(),
+            vec![day("2024-03-09", 3), day("2024-03-10", 1)]
+        );
+        assert_eq!(
+            backup_activity_histogram(save_dir, Some(0), &BackupSettings::default()).unwrap(),
+            vec![day("2024-03-09", 2), day("2024-03-10", 1)]
+        );
+        assert!(
+            backup_activity_histogram(save_dir, Some(2), &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Tests that a save path replaced by a file reports a specific error instead of a raw I/O one.
+    #[test]
+    fn test_save_path_file_reports_not_a_directory() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("saves");
+        fs::write(&save_dir, "not a directory").unwrap();
+
+        let err = BackupStore::new(&save_dir, &BackupSettings::default())
+            .err()
+            .unwrap();
+        assert!(err.starts_with(SAVE_PATH_NOT_A_DIRECTORY), "{}", err);
+
+        let err = ensure_backup_root(&save_dir, &BackupSettings::default()).unwrap_err();
+        assert!(err.starts_with(SAVE_PATH_NOT_A_DIRECTORY), "{}", err);
+        assert!(ensure_save_dir_is_directory(&save_dir).is_err());
+
+        // Missing and real directories are still accepted
+        assert!(ensure_save_dir_is_directory(&dir.path().join("missing")).is_ok());
+        assert!(ensure_backup_root(dir.path(), &BackupSettings::default()).is_ok());
+    }
+
+    /// Tests that locked, noted, and session backups are excluded from cleanup suggestions.
+    #[test]
+    fn test_get_unannotated_backups_excludes_annotated() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let base = Local.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+
+        let names: Vec<String> = (0..5)
+            .map(|i| {
+                let name = format_backup_folder_name(0, base + Duration::minutes(i));
+                let folder = backup_root.join(&name);
+                fs::create_dir_all(&folder).unwrap();
+                fs::write(folder.join("gamesave_0.sav"), format!("save {}", i)).unwrap();
+                name
+            })
+            .collect();
+
+        set_backup_lock(&backup_root.join(&names[0]), true).unwrap();
+        set_backup_note(
+            save_dir,
+            &names[1],
+            Some("boss fight".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let mut store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        store
+            .index
+            .sessions
+            .insert("Day 3".to_string(), vec![names[2].clone()]);
+        store.save().unwrap();
+
+        let bare: Vec<String> = get_unannotated_backups(save_dir, None, &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.filename)
+            .collect();
+        assert_eq!(bare, vec![names[4].clone(), names[3].clone()]);
+        assert!(
+            get_unannotated_backups(save_dir, Some(1), &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Tests that identical saves in different slots share one hardlinked file when enabled.
+    #[test]
+    fn test_cross_slot_dedup_hardlinks_identical_saves() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "identical content").unwrap();
+        fs::write(save_dir.join("gamesave_1.sav"), "identical content").unwrap();
+
+        let settings = BackupSettings {
+            cross_slot_dedup: true,
+            ..BackupSettings::default()
+        };
+        let first = perform_backup_for_game_with(save_dir, 0, 100, &settings);
+        let second = perform_backup_for_game_with(save_dir, 1, 100, &settings);
+
+        let first = first.unwrap().unwrap().join("gamesave_0.sav");
+        let second = second.unwrap().unwrap().join("gamesave_1.sav");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "identical content");
+
+        // Appending through one path is visible through the other only for a hardlink.
+        let mut file = fs::OpenOptions::new().append(true).open(&first).unwrap();
+        file.write_all(b" + shared").unwrap();
+        drop(file);
+        assert_eq!(
+            fs::read_to_string(&second).unwrap(),
+            "identical content + shared"
+        );
+    }
+
+    /// Tests that folders without their main save are reported and removed, sparing locked ones.
+    #[test]
+    fn test_find_and_remove_incomplete_backups() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "complete").unwrap();
+        let complete = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let hash_only = backup_root.join("Game 1 - #0042");
+        fs::create_dir_all(&hash_only).unwrap();
+        fs::write(hash_only.join(HASH_FILE_NAME), "abc").unwrap();
+        let locked = backup_root.join("Game 2 - #0001");
+        fs::create_dir_all(&locked).unwrap();
+        set_backup_lock(&locked, true).unwrap();
+        fs::create_dir_all(backup_root.join("not a backup")).unwrap();
+
+        assert_eq!(
+            find_incomplete_backups(save_dir, &BackupSettings::default()).unwrap(),
+            vec!["Game 1 - #0042", "Game 2 - #0001"]
+        );
+
+        assert_eq!(
+            remove_incomplete_backups(save_dir, &BackupSettings::default()).unwrap(),
+            1
+        );
+        assert!(!hash_only.exists());
+        assert!(locked.exists());
+        assert!(complete.exists());
+        assert_eq!(
+            find_incomplete_backups(save_dir, &BackupSettings::default()).unwrap(),
+            vec!["Game 2 - #0001"]
+        );
+    }
+
+    /// Tests that backup sets are split into only-in-A, only-in-B, and shared by slot and hash.
+    #[test]
+    fn test_diff_backup_sets_buckets() {
+        let dir_a = tempdir().unwrap();
+        let dir_b = tempdir().unwrap();
+        let backup = |save_dir: &std::path::Path, game_number: u32, content: &str| {
+            fs::write(
+                save_dir.join(format!("gamesave_{}.sav", game_number)),
+                content,
+            )
+            .unwrap();
+            let folder = perform_backup_for_game(save_dir, game_number, 100)
+                .unwrap()
+                .unwrap();
+            folder.file_name().unwrap().to_string_lossy().to_string()
+        };
+
+        let shared = backup(dir_a.path(), 0, "shared");
+        let only_a = backup(dir_a.path(), 0, "only in a");
+        backup(dir_b.path(), 0, "shared");
+        let only_b = backup(dir_b.path(), 0, "only in b");
+        // Same content in a different slot is not the same backup.
+        let other_slot = backup(dir_b.path(), 1, "only in a");
+
+        let diff =
+            diff_backup_sets(dir_a.path(), dir_b.path(), &BackupSettings::default()).unwrap();
+        let names = |entries: &[crate::backup::diff::BackupSetEntry]| {
+            entries
+                .iter()
+                .map(|e| e.filename.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&diff.in_both), vec![shared]);
+        assert_eq!(names(&diff.only_in_a), vec![only_a]);
+        let mut only_in_b = names(&diff.only_in_b);
+        only_in_b.sort();
+        let mut expected_b = vec![only_b, other_slot];
+        expected_b.sort();
+        assert_eq!(only_in_b, expected_b);
+    }
+
+    /// Tests that mirrored backups follow a save path change, merging into an existing key.
+    #[test]
+    fn test_remap_central_key_moves_mirrored_backups() {
+        let dir = tempdir().unwrap();
+        let mirror_root = dir.path().join("mirror");
+        let old_save = dir.path().join("OldSaves");
+        let new_save = dir.path().join("NewSaves");
+        let backup_folder = |name: &str| {
+            let folder = dir.path().join("src").join(name);
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), name).unwrap();
+            folder
+        };
+
+        mirror_backup_to(
+            &backup_folder("Game 1 - #0001"),
+            &old_save,
+            &mirror_root,
+            DEFAULT_COPY_BUFFER_SIZE,
+        )
+        .unwrap();
+        assert_eq!(
+            remap_central_key_in(&mirror_root, &old_save, &new_save).unwrap(),
+            1
+        );
+        assert!(!mirror_root.join(save_key(&old_save)).exists());
+        assert!(mirror_root
+            .join(save_key(&new_save))
+            .join("Game 1 - #0001")
+            .join("gamesave_0.sav")
+            .is_file());
+
+        // Remapping again merges into the existing key, leaving conflicting folders behind.
+        mirror_backup_to(
+            &backup_folder("Game 1 - #0002"),
+            &old_save,
+            &mirror_root,
+            DEFAULT_COPY_BUFFER_SIZE,
+        )
+        .unwrap();
+        mirror_backup_to(
+            &backup_folder("Game 1 - #0001"),
+            &old_save,
+            &mirror_root,
+            DEFAULT_COPY_BUFFER_SIZE,
+        )
+        .unwrap();
+        assert_eq!(
+            remap_central_key_in(&mirror_root, &old_save, &new_save).unwrap(),
+            1
+        );
+        assert!(mirror_root
+            .join(save_key(&new_save))
+            .join("Game 1 - #0002")
+            .is_dir());
+        assert!(mirror_root
+            .join(save_key(&old_save))
+            .join("Game 1 - #0001")
+            .is_dir());
+
+        assert!(
+            remap_central_key_in(&mirror_root, &dir.path().join("Missing"), &new_save).is_err()
+        );
+    }
+
+    /// Tests that a remap moves the override's key folder too and repoints its `.save_dir`.
+    #[test]
+    fn test_remap_central_key_moves_override_backups() {
+        let dir = tempdir().unwrap();
+        let old_save = dir.path().join("OldSaves");
+        let new_save = dir.path().join("NewSaves");
+        fs::create_dir_all(&old_save).unwrap();
+        fs::create_dir_all(&new_save).unwrap();
+        fs::write(old_save.join("gamesave_0.sav"), "moved").unwrap();
+        let settings = BackupSettings {
+            mirror_directory: Some(dir.path().join("mirror")),
+            backup_root_override: Some(dir.path().join("external")),
+            ..BackupSettings::default()
+        };
+        perform_backup_for_game_with(&old_save, 0, 10, &settings)
+            .unwrap()
+            .unwrap();
+
+        assert!(remap_central_key(&old_save, &new_save, &BackupSettings::default()).is_err());
+        assert_eq!(
+            remap_central_key(&old_save, &new_save, &settings).unwrap(),
+            2
+        );
+
+        let new_root = dir.path().join("external").join(save_key(&new_save));
+        assert!(!dir
+            .path()
+            .join("external")
+            .join(save_key(&old_save))
+            .exists());
+        assert_eq!(
+            fs::read_to_string(new_root.join(SAVE_DIR_FILE_NAME)).unwrap(),
+            new_save.to_string_lossy()
+        );
+        assert_eq!(
+            get_backups(&new_save, false, None, &settings)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(remap_central_key(&old_save, &new_save, &settings).is_err());
+    }
+
+    /// Tests that the hash algorithm is read from the `.hash` prefix, defaulting to SHA-256.
+    #[test]
+    fn backup_info_reports_hash_algorithm() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+
+        for (folder, hash) in [
+            ("Game 1 - 01-Jan-2024 10-00-00 AM", "abc123"),
+            ("Game 1 - 02-Jan-2024 10-00-00 AM", "blake3:def456"),
+        ] {
+            let folder = backup_root.join(folder);
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), "data").unwrap();
+            fs::write(folder.join(HASH_FILE_NAME), hash).unwrap();
+        }
+
+        for include_hash in [true, false] {
+            let backups =
+                get_backups(save_dir, include_hash, None, &BackupSettings::default()).unwrap();
+            let algorithms: Vec<&str> = backups.iter().map(|b| b.hash_algorithm.as_str()).collect();
+            assert_eq!(algorithms, vec!["blake3", "sha256"]);
+        }
+    }
+
+    /// Tests that identical saves are backed up while dedup is disabled and deduped afterward.
+    #[test]
+    fn test_dedup_override_window_forces_backups_until_expiry() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        fs::create_dir_all(&backup_root).unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), "same").unwrap();
+
+        let mut settings = BackupSettings::default();
+        let mut index = BackupIndex::default();
+        let backup = |index: &mut BackupIndex, settings: &BackupSettings| {
+            let backups = get_backups(save_dir, true, Some(0), &BackupSettings::default()).unwrap();
+            perform_backup_for_game_internal(
+                save_dir,
+                &backup_root,
+                0,
+                index,
+                &backups,
+                settings,
+                BackupOptions {
+                    limit: 0,
+                    ..BackupOptions::default()
+                },
+            )
+            .unwrap()
+            .created()
+        };
+
+        assert!(backup(&mut index, &settings).is_some());
+        assert!(backup(&mut index, &settings).is_none());
+
+        settings.disable_dedup_for(std::time::Duration::from_millis(300));
+        assert!(backup(&mut index, &settings).is_some());
+
+        std::thread::sleep(std::time::Duration::from_millis(350));
+        assert!(backup(&mut index, &settings).is_none());
+
+        settings.disable_dedup_for(std::time::Duration::from_secs(60));
+        settings.disable_dedup_for(std::time::Duration::ZERO);
+        assert!(backup(&mut index, &settings).is_none());
+    }
+
+    /// Tests that a backup matching the live save exactly ranks ahead of closer-sized ones.
+    #[test]
+    fn test_rank_backups_by_similarity_puts_exact_match_first() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let live = save_dir.join("gamesave_0.sav");
+
+        for (folder, content) in [
+            ("Game 1 - 01-Jan-2024 10-00-00 AM", "live state"),
+            (
+                "Game 1 - 02-Jan-2024 10-00-00 AM",
+                "far off state with much more data",
+            ),
+            ("Game 1 - 03-Jan-2024 10-00-00 AM", "live stat!"),
+        ] {
+            let folder = backup_root.join(folder);
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), content).unwrap();
+            fs::write(folder.join(HASH_FILE_NAME), "").unwrap();
+        }
+        fs::write(&live, "live state").unwrap();
+        let live_hash = calculate_hash(&live, &BackupSettings::default()).unwrap();
+        fs::write(
+            backup_root
+                .join("Game 1 - 01-Jan-2024 10-00-00 AM")
+                .join(HASH_FILE_NAME),
+            &live_hash,
+        )
+        .unwrap();
+
+        let ranked = rank_backups_by_similarity(save_dir, 0, &BackupSettings::default()).unwrap();
+        let order: Vec<(&str, bool, u64)> = ranked
+            .iter()
+            .map(|r| (r.backup.filename.as_str(), r.exact_match, r.size_delta))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("Game 1 - 01-Jan-2024 10-00-00 AM", true, 0),
+                ("Game 1 - 03-Jan-2024 10-00-00 AM", false, 0),
+                ("Game 1 - 02-Jan-2024 10-00-00 AM", false, 23),
+            ]
+        );
+
+        assert!(rank_backups_by_similarity(save_dir, 1, &BackupSettings::default()).is_err());
+    }
+
+    /// Tests tagging several backups at once with a normalized tag, then untagging them.
+    #[test]
+    fn test_add_tag_batch_normalizes_and_counts() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders: Vec<String> = (1..=3)
+            .map(|day| format!("Game 1 - 0{}-Jan-2024 10-00-00 AM", day))
+            .collect();
+        for folder in &folders {
+            let folder = backup_root.join(folder);
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), "data").unwrap();
+        }
+
+        assert_eq!(
+            add_tag_batch(
+                save_dir,
+                &folders,
+                "  Boss   Fight ".to_string(),
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            3
+        );
+        let backups = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        assert_eq!(backups.len(), 3);
+        assert!(backups.iter().all(|b| b.tags == vec!["boss fight"]));
+
+        // Re-adding the same tag in another spelling changes nothing.
+        assert_eq!(
+            add_tag_batch(
+                save_dir,
+                &folders,
+                "BOSS FIGHT".to_string(),
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            0
+        );
+        assert!(add_tag_batch(
+            save_dir,
+            &folders,
+            "   ".to_string(),
+            &BackupSettings::default()
+        )
+        .is_err());
+
+        assert_eq!(
+            remove_tag_batch(
+                save_dir,
+                &folders[..2],
+                "boss fight".to_string(),
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            2
+        );
+        let tagged: Vec<String> = get_backups(save_dir, false, None, &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .filter(|b| !b.tags.is_empty())
+            .map(|b| b.filename)
+            .collect();
+        assert_eq!(tagged, vec![folders[2].clone()]);
+    }
+
+    /// Tests that time bounds report each slot's oldest and newest backup and its count.
+    #[test]
+    fn test_get_backup_time_bounds_per_slot() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let start = Local.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap();
+
+        for (game_number, offset_days) in [(0, 3), (0, 0), (0, 10), (2, 5)] {
+            let timestamp = start + Duration::days(offset_days);
+            let folder = backup_root.join(format_backup_folder_name(game_number, timestamp));
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join(format!("gamesave_{}.sav", game_number)), "data").unwrap();
+        }
+
+        let at = |days: i64| (start + Duration::days(days)).to_rfc3339();
+        assert_eq!(
+            get_backup_time_bounds(save_dir, &BackupSettings::default()).unwrap(),
+            vec![
+                TimeBounds {
+                    game_number: 0,
+                    oldest: at(0),
+                    newest: at(10),
+                    count: 3,
+                },
+                TimeBounds {
+                    game_number: 2,
+                    oldest: at(5),
+                    newest: at(5),
+                    count: 1,
+                },
+            ]
+        );
+    }
+
+    /// Tests that rebuilding a deleted index restores the dedup fast path and sidecar notes.
+    #[test]
+    fn test_rebuild_index_restores_fast_path_and_notes() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let index_path = save_dir.join(BACKUP_DIR_NAME).join(INDEX_FILE_NAME);
+        fs::write(save_dir.join("gamesave_0.sav"), "v1").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let folder_name = backup.file_name().unwrap().to_string_lossy().to_string();
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("before the boss".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        fs::remove_file(&index_path).unwrap();
+        assert_eq!(
+            rebuild_index(save_dir, &BackupSettings::default()).unwrap(),
+            1
+        );
+        assert!(index_path.exists());
+
+        let mut store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(store.index.games[&0].last_backup_path, folder_name);
+        let root = store.root.clone();
+        let result = perform_backup_for_game_internal(
+            save_dir,
+            &root,
+            0,
+            &mut store.index,
+            &[],
+            &BackupSettings::default(),
+            BackupOptions {
+                limit: 100,
+                ..BackupOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            BackupOutcome::SkippedDuplicate,
+            "duplicate should be skipped via the index"
+        );
+
+        let backups = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        assert_eq!(backups[0].note.as_deref(), Some("before the boss"));
+
+        // Stale entries in an existing index are discarded rather than merged.
+        store.index.notes.insert("stale".into(), "gone".into());
+        store.save().unwrap();
+        rebuild_index(save_dir, &BackupSettings::default()).unwrap();
+        assert!(!BackupStore::new(save_dir, &BackupSettings::default())
+            .unwrap()
+            .index
+            .notes
+            .contains_key("stale"));
+    }
+
+    /// Tests that manual backups bypass dedup but still write hashes and honor the limit.
+    #[test]
+    fn test_trigger_manual_backup_forces_checkpoints() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "slot 0").unwrap();
+        fs::write(save_dir.join("gamesave_1.sav"), "slot 1").unwrap();
+        perform_backup_for_game(save_dir, 0, 2).unwrap().unwrap();
+        assert!(perform_backup_for_game(save_dir, 0, 2).unwrap().is_none());
+
+        let created =
+            trigger_manual_backup(save_dir, Some(0), 2, &BackupSettings::default()).unwrap();
+        assert_eq!(created.len(), 1);
+        let folder = save_dir.join(BACKUP_DIR_NAME).join(&created[0]);
+        assert!(folder.join(HASH_FILE_NAME).exists());
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Without a slot every save is forced; the limit of 2 still trims slot 0.
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert_eq!(
+            trigger_manual_backup(save_dir, None, 2, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            get_backups(save_dir, false, Some(1), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    /// Tests that backup details reflect the note, lock, and tags set through their APIs.
+    #[test]
+    fn test_get_backup_details_matches_annotations() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_2.sav"), "details").unwrap();
+        let folder = perform_backup_for_game(save_dir, 2, 100).unwrap().unwrap();
+        let folder_name = folder.file_name().unwrap().to_string_lossy().to_string();
+
+        let details = get_backup_details(save_dir, &folder, &BackupSettings::default()).unwrap();
+        assert!(details.note.is_none());
+        assert!(!details.locked);
+        assert!(!details.favorite);
+
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("checkpoint".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        set_backup_lock(&folder, true).unwrap();
+        add_tag_batch(
+            save_dir,
+            std::slice::from_ref(&folder_name),
+            "Boss".to_string(),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(
+            toggle_backup_favorite(save_dir, &folder_name, &BackupSettings::default()).unwrap()
+        );
+
+        let details = get_backup_details(save_dir, &folder, &BackupSettings::default()).unwrap();
+        let listed = &get_backups(save_dir, true, None, &BackupSettings::default()).unwrap()[0];
+        assert_eq!(details.filename, folder_name);
+        assert_eq!(details.game_number, 2);
+        assert_eq!(details.note.as_deref(), Some("checkpoint"));
+        assert!(details.locked);
+        assert_eq!(details.tags, vec!["boss"]);
+        assert!(details.favorite);
+        assert_eq!(details.size, 7);
+        assert_eq!(details.hash, listed.hash);
+        assert_eq!(details.hash_algorithm, "sha256");
+        assert_eq!(details.modified, listed.modified);
+
+        assert!(get_backup_details(
+            save_dir,
+            &save_dir.join(BACKUP_DIR_NAME).join("junk"),
+            &BackupSettings::default()
+        )
+        .is_err());
+    }
+
+    /// Tests that backups written compressed list, dedup, and restore like raw ones.
+    #[test]
+    fn test_compressed_copy_lists_dedups_and_restores() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_1.sav");
+        let bak_sav = save_dir.join("gamesave_1.sav.bak");
+        let content = "compressible ".repeat(500);
+        fs::write(&main_sav, &content).unwrap();
+        fs::write(&bak_sav, "bak content").unwrap();
+
+        let raw = perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+        let raw_hash = fs::read_to_string(raw.join(HASH_FILE_NAME)).unwrap();
+
+        let compressed = save_dir
+            .join(BACKUP_DIR_NAME)
+            .join("Game 2 - 01-Jan-2024 10-00-00 AM");
+        fs::create_dir_all(&compressed).unwrap();
+        copy_save_files(
+            &build_save_paths(save_dir, 1),
+            &compressed,
+            None,
+            true,
+            &BackupSettings::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+        write_hash_file(
+            &compressed,
+            &calculate_hash(&main_sav, &BackupSettings::default()).unwrap(),
+        )
+        .unwrap();
+        assert!(!compressed.join("gamesave_1.sav").exists());
+        assert!(compressed.join("gamesave_1.sav.gz").exists());
+        assert!(compressed.join("gamesave_1.sav.bak.gz").exists());
+
+        let listed = backup_info_from_folder(
+            &compressed,
+            "Game 2 - 01-Jan-2024 10-00-00 AM",
+            save_dir,
+            true,
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(listed.size, content.len() as u64);
+        assert_eq!(
+            listed.hash, raw_hash,
+            "hashes cover the uncompressed content"
+        );
+
+        fs::write(&main_sav, "changed").unwrap();
+        fs::write(&bak_sav, "changed").unwrap();
+        restore_backup(&compressed, save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), content);
+        assert_eq!(fs::read_to_string(&bak_sav).unwrap(), "bak content");
+    }
+
+    /// Tests that a restore first backs up unsaved live progress with a note.
+    #[test]
+    fn test_restore_snapshots_live_save_first() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "old progress").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(&main_sav, "current progress").unwrap();
+
+        restore_backup_cancellable(
+            &backup_folder,
+            save_dir,
+            &RestoreCancelToken::default(),
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "old progress");
+
+        let backups = get_backups(save_dir, true, Some(0), &BackupSettings::default()).unwrap();
+        assert_eq!(backups.len(), 2);
+        let safety = backups
+            .iter()
+            .find(|b| b.note.as_deref() == Some(PRE_RESTORE_NOTE))
+            .expect("safety backup should be noted");
+        let safety_main = std::path::Path::new(&safety.path).join("gamesave_0.sav");
+        assert_eq!(fs::read_to_string(safety_main).unwrap(), "current progress");
+
+        // The live save now matches a backup, so restoring again adds nothing.
+        restore_backup_cancellable(
+            &backup_folder,
+            save_dir,
+            &RestoreCancelToken::default(),
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    /// Tests that a failing safety backup aborts the restore without touching the save.
+    #[test]
+    fn test_restore_aborts_when_snapshot_fails() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("saves");
+        let elsewhere = dir.path().join("elsewhere");
+        fs::create_dir_all(&save_dir).unwrap();
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), "backed up").unwrap();
+        let backup_folder = perform_backup_for_game(&save_dir, 0, 100).unwrap().unwrap();
+
+        // The target's backup root is a file, so the safety backup cannot be written.
+        fs::write(elsewhere.join("gamesave_0.sav"), "precious").unwrap();
+        fs::write(elsewhere.join(BACKUP_DIR_NAME), "not a folder").unwrap();
+
+        let err = restore_backup_cancellable(
+            &backup_folder,
+            &elsewhere,
+            &RestoreCancelToken::default(),
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("safety backup failed"), "{}", err);
+        assert_eq!(
+            fs::read_to_string(elsewhere.join("gamesave_0.sav")).unwrap(),
+            "precious"
+        );
+    }
+
+    /// Tests that the safety backup does not age out the backup being restored.
+    #[test]
+    fn test_restore_keeps_over_age_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "ancient").unwrap();
+        let created = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let old = save_dir
+            .join(BACKUP_DIR_NAME)
+            .join("Game 1 - 01-Jan-2020 10-00-00 AM");
+        fs::rename(&created, &old).unwrap();
+        fs::write(&main_sav, "recent").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(&main_sav, "current").unwrap();
+
+        let settings = BackupSettings {
+            max_backup_age_days: Some(30),
+            ..BackupSettings::default()
+        };
+        restore_backup_cancellable(
+            &old,
+            save_dir,
+            &RestoreCancelToken::default(),
+            true,
+            &settings,
+        )
+        .unwrap();
+
+        assert!(old.exists(), "the restored backup should not be pruned");
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "ancient");
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    /// Tests that deleting a noted and tagged backup removes all of its index metadata.
+    #[test]
+    fn test_delete_backup_folder_prunes_index_metadata() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "v1").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), "v2").unwrap();
+        let doomed = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let folder_name = doomed.file_name().unwrap().to_string_lossy().to_string();
+        let id = read_manifest(&doomed).unwrap().id;
+
+        set_backup_note(
+            save_dir,
+            &folder_name,
+            Some("to be deleted".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        add_tag_batch(
+            save_dir,
+            std::slice::from_ref(&folder_name),
+            "boss".into(),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(
+            toggle_backup_favorite(save_dir, &folder_name, &BackupSettings::default()).unwrap()
+        );
+        let mut store = BackupStore::new(save_dir, &BackupSettings::default()).unwrap();
+        store
+            .index
+            .sessions
+            .insert("run".into(), vec![folder_name.clone()]);
+        store.save().unwrap();
+
+        delete_backup_folder(&doomed, false, &BackupSettings::default()).unwrap();
+
+        let index = BackupStore::new(save_dir, &BackupSettings::default())
+            .unwrap()
+            .index;
+        assert!(!index.notes.contains_key(&id));
+        assert!(!index.tags.contains_key(&id));
+        assert!(index.favorites.is_empty());
+        assert!(index.sessions.is_empty());
+        assert!(
+            !index.games.contains_key(&0),
+            "the dedup entry pointed at the deleted folder"
+        );
+    }
+
+    /// Tests that the retention suggestion keeps one backup per unique save and all locked ones.
+    #[test]
+    fn test_suggest_retention_keeps_unique_saves() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+
+        let folder = |day: u32| format!("Game 1 - 0{}-Jan-2024 10-00-00 AM", day);
+        for (day, content) in [(1, "a"), (2, "b"), (3, "a"), (4, "b"), (5, "a"), (6, "c")] {
+            let path = backup_root.join(folder(day));
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("gamesave_0.sav"), content).unwrap();
+        }
+        set_backup_lock(&backup_root.join(folder(1)), true).unwrap();
+
+        let suggestion = suggest_retention(save_dir, 0, &BackupSettings::default()).unwrap();
+        // Newest per hash: "c" on day 6, "a" on day 5, "b" on day 4; day 1 is locked.
+        assert_eq!(
+            suggestion.keep,
+            vec![folder(6), folder(4), folder(1)],
+            "locked day 1 already covers \"a\""
+        );
+        assert_eq!(suggestion.delete, vec![folder(5), folder(3), folder(2)]);
+        assert_eq!(suggestion.bytes_freed, 3);
+        assert!(backup_root.join(folder(2)).exists(), "nothing is deleted");
+    }
+
+    /// Tests restoring a backup into another slot renames its files and guards existing saves.
+    #[test]
+    fn test_restore_backup_to_slot() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "slot one").unwrap();
+        fs::write(save_dir.join("gamesave_0.sav.bak"), "slot one bak").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        restore_backup_to_slot(
+            &backup_folder,
+            save_dir,
+            3,
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(save_dir.join("gamesave_3.sav")).unwrap(),
+            "slot one"
+        );
+        assert_eq!(
+            fs::read_to_string(save_dir.join("gamesave_3.sav.bak")).unwrap(),
+            "slot one bak"
+        );
+        // Slot 0's folder must not stand in for slot 3, so slot 3 gets its own backup.
+        let index = BackupStore::new(save_dir, &BackupSettings::default())
+            .unwrap()
+            .index;
+        assert!(!index.games.contains_key(&3));
+        let slot_three = perform_backup_for_game(save_dir, 3, 100)
+            .unwrap()
+            .expect("the restored slot should be backed up into its own folder");
+        assert!(slot_three.join("gamesave_3.sav").exists());
+
+        fs::write(save_dir.join("gamesave_3.sav"), "newer").unwrap();
+        let err = restore_backup_to_slot(
+            &backup_folder,
+            save_dir,
+            3,
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("already has a save"), "{}", err);
+        assert_eq!(
+            fs::read_to_string(save_dir.join("gamesave_3.sav")).unwrap(),
+            "newer"
+        );
+
+        restore_backup_to_slot(
+            &backup_folder,
+            save_dir,
+            3,
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(save_dir.join("gamesave_3.sav")).unwrap(),
+            "slot one"
+        );
+        let safety = get_backups(save_dir, false, Some(3), &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .find(|b| b.note.as_deref() == Some(PRE_RESTORE_NOTE))
+            .expect("the overwritten save should get a safety backup");
+        assert_eq!(
+            fs::read_to_string(std::path::Path::new(&safety.path).join("gamesave_3.sav")).unwrap(),
+            "newer"
+        );
+        assert_eq!(
+            fs::read_to_string(save_dir.join("gamesave_0.sav")).unwrap(),
+            "slot one"
+        );
+    }
+
+    /// Tests that the streaming scan reports every backup once and returns the total.
+    #[test]
+    fn test_scan_backups_streaming_emits_each_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        for (game, minute) in [(0, 0), (0, 1), (1, 2), (2, 3)] {
+            let folder = backup_root.join(format!(
+                "Game {} - 01-Jan-2024 10-0{}-00 AM",
+                game + 1,
+                minute
+            ));
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join(format!("gamesave_{}.sav", game)), "data").unwrap();
+        }
+
+        let mut discovered = Vec::new();
+        let total =
+            scan_backups_streaming(save_dir, false, None, &BackupSettings::default(), |info| {
+                discovered.push(info.filename)
+            })
+            .unwrap();
+
+        assert_eq!(total, 4);
+        assert_eq!(discovered.len(), total);
+        discovered.sort();
+        discovered.dedup();
+        assert_eq!(discovered.len(), 4);
+        assert_eq!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            total
+        );
+    }
+
+    /// Tests that verification flags corrupted and hashless backups.
+    #[test]
+    fn test_verify_backup_detects_corruption() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "good data").unwrap();
+        let intact = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(save_dir.join("gamesave_1.sav"), "other data").unwrap();
+        let corrupted = perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+        fs::write(save_dir.join("gamesave_2.sav"), "third data").unwrap();
+        let hashless = perform_backup_for_game(save_dir, 2, 100).unwrap().unwrap();
+
+        fs::write(corrupted.join("gamesave_1.sav"), "bad sector").unwrap();
+        fs::remove_file(hashless.join(HASH_FILE_NAME)).unwrap();
+
+        assert_eq!(verify_backup(&intact).unwrap(), VerifyStatus::Ok);
+        assert_eq!(
+            verify_backup(&corrupted).unwrap(),
+            VerifyStatus::HashMismatch
+        );
+        assert_eq!(
+            verify_backup(&hashless).unwrap(),
+            VerifyStatus::MissingHashFile
+        );
+
+        let mut failures: Vec<_> = verify_all_backups(save_dir, &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .map(|f| (PathBuf::from(f.path), f.status))
+            .collect();
+        failures.sort_by_key(|(_, status)| *status as u8);
+        assert_eq!(
+            failures,
+            vec![
+                (corrupted, VerifyStatus::HashMismatch),
+                (hashless, VerifyStatus::MissingHashFile),
+            ]
+        );
+    }
+
+    /// Tests that a directory's settings file overrides the global limit and naming scheme.
+    #[test]
+    fn test_directory_settings_override_global_limit() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        fs::write(
+            backup_root.join("settings.json"),
+            r#"{"limit": 2, "naming_scheme": "counter"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            load_directory_settings(&backup_root),
+            DirectorySettings {
+                limit: Some(2),
+                compress_backups: None,
+                naming_scheme: Some(NamingScheme::Counter),
+            }
+        );
+
+        let main_sav = save_dir.join("gamesave_0.sav");
+        for i in 0..4 {
+            fs::write(&main_sav, format!("data {}", i)).unwrap();
+            perform_backup_for_game(save_dir, 0, 10).unwrap();
+        }
+
+        let names: Vec<_> = get_backups(save_dir, false, None, &BackupSettings::default())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.filename)
+            .collect();
+        assert_eq!(names, vec!["Game 1 - #0004", "Game 1 - #0003"]);
+    }
+
+    /// Tests that the storage cap does not let the safety backup delete the restore source.
+    #[test]
+    fn test_pre_restore_snapshot_skips_size_cap() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "restore me").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 10).unwrap().unwrap();
+        fs::write(&main_sav, "current").unwrap();
+
+        let settings = BackupSettings {
+            max_total_backup_bytes: Some(1),
+            ..BackupSettings::default()
+        };
+        restore_backup_cancellable(
+            &backup_folder,
+            save_dir,
+            &RestoreCancelToken::default(),
+            true,
+            &settings,
+        )
+        .unwrap();
+        assert!(backup_folder.exists());
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "restore me");
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    /// Tests that a directory limit does not let the safety backup prune older backups.
+    #[test]
+    fn test_directory_limit_skips_pre_restore_snapshot() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        fs::write(backup_root.join("settings.json"), r#"{"limit": 1}"#).unwrap();
+
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "restore me").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 10).unwrap().unwrap();
+        fs::write(&main_sav, "current").unwrap();
+
+        restore_backup_cancellable(
+            &backup_folder,
+            save_dir,
+            &RestoreCancelToken::default(),
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(backup_folder.exists());
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "restore me");
+        assert_eq!(
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    /// Tests that the age limit deletes old unlocked backups before the count limit applies.
+    #[test]
+    fn test_prune_old_backups_composes_with_count_limit() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        let folder = |day: u32, month: u32| {
+            let dt = Local.with_ymd_and_hms(2024, month, day, 10, 0, 0).unwrap();
+            format_backup_folder_name(0, dt)
+        };
+        for (day, month) in [(1, 1), (5, 1), (15, 2), (20, 2), (25, 2)] {
+            let path = backup_root.join(folder(day, month));
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("gamesave_0.sav"), "data").unwrap();
+        }
+        File::create(backup_root.join(folder(5, 1)).join(LOCKED_FILE_NAME)).unwrap();
+
+        let now = Local.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+        let mut index = BackupIndex::default();
+        let backups = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        let remaining = prune_old_backups(0, 30, &backups, &mut index, now, true).unwrap();
+
+        let names =
+            |list: &[BackupInfo]| list.iter().map(|b| b.filename.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            names(&remaining),
+            vec![folder(25, 2), folder(20, 2), folder(15, 2), folder(5, 1)],
+            "only the old unlocked backup is aged out"
+        );
+        assert!(!backup_root.join(folder(1, 1)).exists());
+
+        // The count limit then only sees what the age limit kept.
+        enforce_backup_limit(0, 3, &remaining, &mut index, true).unwrap();
+        assert_eq!(
+            names(&get_backups(save_dir, false, None, &BackupSettings::default()).unwrap()),
+            vec![folder(25, 2), folder(20, 2), folder(5, 1)]
+        );
+    }
+
+    /// Tests that a backup whose main save mtime is far from its folder timestamp is flagged.
+    #[test]
+    fn test_audit_timestamp_consistency_flags_mismatch() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        let seed = |timestamp: chrono::DateTime<Local>, mtime: chrono::DateTime<Local>| {
+            let folder = backup_root.join(format_backup_folder_name(0, timestamp));
+            fs::create_dir_all(&folder).unwrap();
+            let file = File::create(folder.join("gamesave_0.sav")).unwrap();
+            file.set_modified(mtime.into()).unwrap();
+            folder
+        };
+        let consistent = Local.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        seed(consistent, consistent + Duration::seconds(3));
+        let tampered = Local.with_ymd_and_hms(2024, 1, 2, 10, 0, 0).unwrap();
+        let flagged = seed(tampered, tampered + Duration::days(3));
+
+        let audits = audit_timestamp_consistency(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(audits.len(), 1);
+        assert_eq!(PathBuf::from(&audits[0].path), flagged);
+        assert_eq!(audits[0].difference_seconds, 3 * 24 * 60 * 60);
+    }
+
+    /// Tests that batch delete reports progress after each candidate folder.
+    #[test]
+    fn test_delete_backups_batch_reports_progress() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        for minute in 0..4 {
+            let folder = backup_root.join(format!("Game 1 - 01-Jan-2024 10-0{}-00 AM", minute));
+            fs::create_dir_all(&folder).unwrap();
+            fs::write(folder.join("gamesave_0.sav"), "data").unwrap();
+        }
+
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = calls.clone();
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[0],
+            true,
+            false,
+            false,
+            &BackupSettings::default(),
+            Some(std::sync::Arc::new(move |deleted, total| {
+                recorder.lock().unwrap().push((deleted, total));
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(deleted, 3);
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    /// Tests that storage stats count every file per folder and split locked bytes.
+    #[test]
+    fn test_backup_storage_stats_sums_folders() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        assert_eq!(
+            get_backup_storage_stats(save_dir, &BackupSettings::default())
+                .unwrap()
+                .backup_count,
+            0
+        );
+
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        let seed = |name: &str, files: &[(&str, usize)]| {
+            let folder = backup_root.join(name);
+            fs::create_dir_all(&folder).unwrap();
+            for (file, len) in files {
+                fs::write(folder.join(file), vec![b'x'; *len]).unwrap();
+            }
+        };
+        seed(
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            &[("gamesave_0.sav", 100), ("gamesave_0.sav.bak", 50)],
+        );
+        seed(
+            "Game 1 - 01-Jan-2024 10-01-00 AM",
+            &[("gamesave_0.sav.gz", 30), (LOCKED_FILE_NAME, 0)],
+        );
+        seed(
+            "Game 3 - 01-Jan-2024 10-02-00 AM",
+            &[("gamesave_2.sav", 20)],
+        );
+        seed("not a backup", &[("gamesave_0.sav", 1000)]);
+        fs::write(backup_root.join(INDEX_FILE_NAME), vec![b'x'; 500]).unwrap();
+
+        let stats = get_backup_storage_stats(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(stats.backup_count, 3);
+        assert_eq!(stats.total_bytes, 200);
+        assert_eq!(
+            stats.per_game,
+            std::collections::HashMap::from([(0, 180), (2, 20)])
+        );
+        assert_eq!(stats.locked_bytes, 30);
+        assert_eq!(stats.unlocked_bytes, 170);
+    }
+
+    /// Tests quarantining a corrupt backup hides it from listings until it is released.
+    #[test]
+    fn test_quarantine_and_release_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "good").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(backup.join("gamesave_0.sav"), "bad sector").unwrap();
+        assert_eq!(verify_backup(&backup).unwrap(), VerifyStatus::HashMismatch);
+        let folder_name = backup.file_name().unwrap().to_string_lossy().to_string();
+
+        let quarantined = quarantine_backup(&backup, "hash mismatch").unwrap();
+        assert!(!backup.exists());
+        assert!(quarantined.join("gamesave_0.sav").exists());
+        assert!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+        let listed = list_quarantined(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].filename, folder_name);
+        assert_eq!(listed[0].reason, "hash mismatch");
+        assert!(listed[0].quarantined_at.is_some());
+
+        assert!(
+            release_from_quarantine(save_dir, "../escape", &BackupSettings::default()).is_err()
+        );
+        let released =
+            release_from_quarantine(save_dir, &folder_name, &BackupSettings::default()).unwrap();
+        assert_eq!(released, backup);
+        assert!(!released.join(QUARANTINE_FILE_NAME).exists());
+        assert_eq!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(list_quarantined(save_dir, &BackupSettings::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Verifies that the byte diff counts differing positions and finds the first one.
+    #[test]
+    fn test_byte_diff_count_reports_count_and_first_offset() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let folder = "Game 1 - 01-Jan-2024 10-00-00 AM";
+        let backup_dir = save_dir.join(BACKUP_DIR_NAME).join(folder);
+        fs::create_dir_all(&backup_dir).unwrap();
+
+        let original = vec![0_u8; 10_000];
+        let mut changed = original.clone();
+        changed[4_100] = 1;
+        changed[9_999] = 2;
+        fs::write(backup_dir.join("gamesave_0.sav"), &original).unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), &changed).unwrap();
+
+        let diff = byte_diff_count(save_dir, folder, 0, &BackupSettings::default()).unwrap();
+        assert_eq!(
+            diff,
+            ByteDiff {
+                live_size: 10_000,
+                backup_size: 10_000,
+                differing_bytes: 2,
+                first_difference_offset: Some(4_100),
+            }
+        );
+
+        compress_backup(&backup_dir, &BackupSettings::default()).unwrap();
+        let compressed = byte_diff_count(save_dir, folder, 0, &BackupSettings::default()).unwrap();
+        assert_eq!(compressed, diff, "compressed backups compare by content");
+
+        fs::write(save_dir.join("gamesave_0.sav"), &changed[..5_000]).unwrap();
+        let resized = byte_diff_count(save_dir, folder, 0, &BackupSettings::default()).unwrap();
+        assert_eq!(
+            resized,
+            ByteDiff {
+                live_size: 5_000,
+                backup_size: 10_000,
+                differing_bytes: 5_001,
+                first_difference_offset: Some(4_100),
+            },
+            "saves of different sizes are compared over their common length"
+        );
+
+        assert!(byte_diff_count(save_dir, "../escape", 0, &BackupSettings::default()).is_err());
+    }
+
+    /// Verifies that hashes are tagged by algorithm and never match across algorithms.
+    #[test]
+    fn test_hash_algorithms_are_tagged_and_kept_apart() {
+        let content: &[u8] = b"same save content";
+        let tagged = |algorithm| {
+            let (tag, digest) =
+                hash_reader_with(algorithm, DEFAULT_COPY_BUFFER_SIZE, content).unwrap();
+            tagged_hash(tag, &digest)
+        };
+        let sha256 = tagged(HashAlgorithm::Sha256);
+        let blake3 = tagged(HashAlgorithm::Blake3);
+        let xxhash = tagged(HashAlgorithm::XxHash);
+
+        assert!(!sha256.contains(':'), "SHA-256 hashes stay unprefixed");
+        assert!(blake3.starts_with("blake3:"));
+        assert!(xxhash.starts_with("xxh3:"));
+        assert!(hashes_match(&sha256, &format!("sha256:{}", sha256)));
+        assert!(hashes_match(&blake3, &tagged(HashAlgorithm::Blake3)));
+        assert!(!hashes_match(&sha256, &blake3));
+        assert!(!hashes_match(&blake3, &xxhash));
+        // A digest from another algorithm is not a match even if the hex were equal.
+        let digest = blake3.trim_start_matches("blake3:");
+        assert!(!hashes_match(&blake3, digest));
+    }
+
+    /// Verifies that backups hashed with another algorithm list and verify correctly.
+    #[test]
+    fn test_backup_with_blake3_hash_lists_and_verifies() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "blake data").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+
+        let (tag, digest) = hash_reader_with(
+            HashAlgorithm::Blake3,
+            DEFAULT_COPY_BUFFER_SIZE,
+            &b"blake data"[..],
+        )
+        .unwrap();
+        let blake3 = tagged_hash(tag, &digest);
+        fs::write(backup.join(HASH_FILE_NAME), &blake3).unwrap();
+
+        let listed = get_backups(save_dir, true, Some(0), &BackupSettings::default()).unwrap();
+        assert_eq!(listed[0].hash, blake3);
+        assert_eq!(listed[0].hash_algorithm, "blake3");
+        assert_eq!(verify_backup(&backup).unwrap(), VerifyStatus::Ok);
+
+        fs::write(backup.join("gamesave_0.sav"), "tampered!!").unwrap();
+        assert_eq!(verify_backup(&backup).unwrap(), VerifyStatus::HashMismatch);
+    }
+
+    /// Verifies that a batch delete preview lists exactly the folders the real run removes.
+    #[test]
+    fn test_preview_batch_delete_matches_real_run() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders = [
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            "Game 1 - 01-Jan-2024 11-00-00 AM",
+            "Game 1 - 01-Jan-2024 12-00-00 PM",
+            "Game 2 - 01-Jan-2024 10-00-00 AM",
+        ];
+        for folder in folders {
+            let path = backup_root.join(folder);
+            fs::create_dir_all(&path).unwrap();
+            let game_number = if folder.starts_with("Game 1") { 0 } else { 1 };
+            fs::write(path.join(format!("gamesave_{}.sav", game_number)), folder).unwrap();
+        }
+        fs::write(backup_root.join(folders[1]).join(LOCKED_FILE_NAME), "").unwrap();
+
+        let mut preview: Vec<String> = preview_delete_backups_batch(
+            save_dir,
+            &[0, 1],
+            true,
+            false,
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|b| b.filename)
+        .collect();
+        preview.sort();
+        assert_eq!(preview, vec![folders[0].to_string()]);
+        assert!(
+            backup_root.join(folders[0]).exists(),
+            "a preview deletes nothing"
+        );
+
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[0, 1],
+            true,
+            false,
+            false,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(deleted, preview.len());
+        let mut remaining: Vec<String> =
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .into_iter()
+                .map(|b| b.filename)
+                .collect();
+        remaining.sort();
+        assert_eq!(remaining, folders[1..].to_vec());
+    }
+
+    /// Verifies that deleted backups go to the trash, stay unlisted, and can be restored.
+    #[test]
+    fn test_deleted_backup_can_be_restored_from_trash() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders = [
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            "Game 1 - 01-Jan-2024 11-00-00 AM",
+        ];
+        for folder in folders {
+            fs::create_dir_all(backup_root.join(folder)).unwrap();
+            fs::write(backup_root.join(folder).join("gamesave_0.sav"), folder).unwrap();
+        }
+
+        delete_backup_folder(
+            &backup_root.join(folders[0]),
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(!backup_root.join(folders[0]).exists());
+        assert!(backup_root.join(TRASH_DIR_NAME).is_dir());
+        assert_eq!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let trashed = list_trash(save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].filename, folders[0]);
+        assert!(trashed[0].deleted_at.is_some());
+
+        assert!(restore_from_trash(save_dir, "../escape", &BackupSettings::default()).is_err());
+        let restored =
+            restore_from_trash(save_dir, &trashed[0].trash_name, &BackupSettings::default())
+                .unwrap();
+        assert_eq!(restored, backup_root.join(folders[0]));
+        assert_eq!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(list_trash(save_dir, &BackupSettings::default())
+            .unwrap()
+            .is_empty());
+
+        delete_backup_folder(
+            &backup_root.join(folders[0]),
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            empty_trash(save_dir, &BackupSettings::default()).unwrap(),
+            1
+        );
+        assert!(list_trash(save_dir, &BackupSettings::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Verifies that a restore from the trash brings back the backup's index metadata.
+    #[test]
+    fn test_restore_from_trash_keeps_notes_tags_favorites_and_sessions() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folder = "Game 1 - 01-Jan-2024 10-00-00 AM";
+        fs::create_dir_all(backup_root.join(folder)).unwrap();
+        fs::write(backup_root.join(folder).join("gamesave_0.sav"), "save").unwrap();
+
+        let settings = BackupSettings::default();
+        set_backup_note(save_dir, folder, Some("Boss fight".to_string()), &settings).unwrap();
+        set_backup_tags(save_dir, folder, vec!["boss".to_string()], &settings).unwrap();
+        assert!(toggle_backup_favorite(save_dir, folder, &settings).unwrap());
+        let mut store = BackupStore::new(save_dir, &settings).unwrap();
+        store
+            .index
+            .sessions
+            .insert("Run".to_string(), vec![folder.to_string()]);
+        store.save().unwrap();
+
+        delete_backup_folder(&backup_root.join(folder), true, &settings).unwrap();
+        let index = load_index(&backup_root);
+        assert!(index.notes.is_empty());
+        assert!(index.tags.is_empty());
+        assert!(index.favorites.is_empty());
+        assert!(index.sessions.get("Run").is_none_or(|f| f.is_empty()));
+
+        let trashed = list_trash(save_dir, &settings).unwrap();
+        restore_from_trash(save_dir, &trashed[0].trash_name, &settings).unwrap();
+        assert!(!backup_root.join(folder).join(TRASH_FILE_NAME).exists());
+
+        let id = read_manifest(&backup_root.join(folder)).map(|m| m.id);
+        let index = load_index(&backup_root);
+        assert_eq!(
+            index.note_for(folder, id.as_deref()).map(String::as_str),
+            Some("Boss fight")
+        );
+        assert_eq!(
+            index.tags_for(folder, id.as_deref()),
+            Some(&vec!["boss".to_string()])
+        );
+        assert!(index.is_favorite(folder, id.as_deref()));
+        assert_eq!(index.sessions.get("Run"), Some(&vec![folder.to_string()]));
+
+        // Without a trash record, the note comes back from the note.txt sidecar.
+        delete_backup_folder(&backup_root.join(folder), true, &settings).unwrap();
+        let trashed = list_trash(save_dir, &settings).unwrap();
+        fs::remove_file(
+            backup_root
+                .join(TRASH_DIR_NAME)
+                .join(&trashed[0].trash_name)
+                .join(TRASH_FILE_NAME),
+        )
+        .unwrap();
+        restore_from_trash(save_dir, &trashed[0].trash_name, &settings).unwrap();
+        let index = load_index(&backup_root);
+        assert_eq!(
+            index.note_for(folder, id.as_deref()).map(String::as_str),
+            Some("Boss fight")
+        );
+        assert!(index.tags_for(folder, id.as_deref()).is_none());
+        assert!(!index.is_favorite(folder, id.as_deref()));
+    }
+
+    /// Verifies that only trash older than the retention window is purged.
+    #[test]
+    fn test_purge_trash_older_than_keeps_recent_deletes() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let trash = backup_root.join(TRASH_DIR_NAME);
+        let old = "Game 1 - 01-Jan-2024 10-00-00 AM~20240101-100000000";
+        let recent = "Game 1 - 01-Jan-2024 11-00-00 AM~20240120-100000000";
+        let unnamed = "manually copied";
+        for name in [old, recent, unnamed] {
+            fs::create_dir_all(trash.join(name)).unwrap();
+        }
+
+        let now = Local.with_ymd_and_hms(2024, 1, 22, 10, 0, 0).unwrap();
+        assert_eq!(purge_trash_older_than(&backup_root, 7, now).unwrap(), 1);
+        assert!(!trash.join(old).exists());
+        assert!(trash.join(recent).exists());
+        assert!(
+            trash.join(unnamed).exists(),
+            "unparseable names wait for a manual empty"
+        );
+    }
+
+    /// Verifies that backups are compared by content, falling back to hashing when needed.
+    #[test]
+    fn test_compare_backups_reports_identical_different_and_slots() {
+        let dir = tempdir().unwrap();
+        let backup_root = dir.path().join(BACKUP_DIR_NAME);
+        let make = |folder: &str, file: &str, content: &str| {
+            let path = backup_root.join(folder);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join(file), content).unwrap();
+            path
+        };
+        let a = make("Game 1 - 01-Jan-2024 10-00-00 AM", "gamesave_0.sav", "same");
+        let b = make("Game 1 - 01-Jan-2024 11-00-00 AM", "gamesave_0.sav", "same");
+        let c = make(
+            "Game 1 - 01-Jan-2024 12-00-00 PM",
+            "gamesave_0.sav",
+            "other",
+        );
+        let other_slot = make("Game 2 - 01-Jan-2024 10-00-00 AM", "gamesave_1.sav", "same");
+        write_hash_file(
+            &a,
+            &calculate_hash(&a.join("gamesave_0.sav"), &BackupSettings::default()).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            compare_backups(&a, &b).unwrap(),
+            BackupComparison::Identical
+        );
+        assert_eq!(
+            compare_backups(&a, &c).unwrap(),
+            BackupComparison::Different
+        );
+        assert_eq!(
+            compare_backups(&a, &other_slot).unwrap(),
+            BackupComparison::DifferentSlots
+        );
+
+        // With a stored hash on both sides the hashes are compared directly.
+        write_hash_file(
+            &b,
+            &calculate_hash(&b.join("gamesave_0.sav"), &BackupSettings::default()).unwrap(),
+        )
+        .unwrap();
+        write_hash_file(
+            &c,
+            &calculate_hash(&c.join("gamesave_0.sav"), &BackupSettings::default()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            compare_backups(&a, &b).unwrap(),
+            BackupComparison::Identical
+        );
+        assert_eq!(
+            compare_backups(&b, &c).unwrap(),
+            BackupComparison::Different
+        );
+    }
+
+    /// Verifies that an invalid index is set aside and rebuilt instead of reset.
+    #[test]
+    fn test_invalid_index_is_rebuilt_from_backups() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "content").unwrap();
+        perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        assert!(!backup_root.join("index.json.tmp").exists());
+
+        fs::write(backup_root.join(INDEX_FILE_NAME), "{\"games\": {\"0\": {").unwrap();
+        let index = load_index(&backup_root);
+
+        assert!(
+            index.games.contains_key(&0),
+            "the slot should be re-indexed"
+        );
+        assert!(backup_root.join("index.json.corrupt").exists());
+        assert!(
+            perform_backup_for_game(save_dir, 0, 100).unwrap().is_none(),
+            "the unchanged save should still be detected as a duplicate"
+        );
+    }
+
+    /// Verifies that restored files carry the backup's timestamp and the index records it.
+    #[test]
+    fn test_restore_sets_modified_time_to_backup_time() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        fs::write(&main_sav, "original").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(&main_sav, "changed").unwrap();
+
+        restore_backup(&backup_folder, save_dir, &BackupSettings::default()).unwrap();
+
+        let folder_name = backup_folder.file_name().unwrap().to_str().unwrap();
+        let info = crate::filename_utils::parse_backup_folder_name(folder_name).unwrap();
+        let expected = std::time::SystemTime::from(info.timestamp.unwrap());
+        let modified = fs::metadata(&main_sav).unwrap().modified().unwrap();
+        assert_eq!(modified, expected);
+
+        let index = load_index(&save_dir.join(BACKUP_DIR_NAME));
+        let entry = &index.games[&0];
+        assert_eq!(
+            entry.last_source_modified,
+            modified
+                .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        );
+    }
+
+    /// Tests replacing a backup's tags and pruning the entry once the list is empty.
+    #[test]
+    fn test_set_backup_tags_replaces_and_prunes() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let folder_name = "Game 1 - 01-Jan-2024 10-00-00 AM";
+        let folder = save_dir.join(BACKUP_DIR_NAME).join(folder_name);
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("gamesave_0.sav"), "data").unwrap();
+
+        set_backup_tags(
+            save_dir,
+            folder_name,
+            vec![
+                "Before Trade".to_string(),
+                " boss  fight".to_string(),
+                "BEFORE TRADE".to_string(),
+            ],
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let backups = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        assert_eq!(backups[0].tags, vec!["before trade", "boss fight"]);
+
+        assert!(set_backup_tags(
+            save_dir,
+            folder_name,
+            vec!["  ".to_string()],
+            &BackupSettings::default()
+        )
+        .is_err());
+
+        set_backup_tags(
+            save_dir,
+            folder_name,
+            Vec::new(),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(load_index(&save_dir.join(BACKUP_DIR_NAME)).tags.is_empty());
+        assert!(
+            get_backups(save_dir, false, None, &BackupSettings::default()).unwrap()[0]
+                .tags
+                .is_empty()
+        );
+    }
+
+    /// Tests exporting the backup catalog as JSON and as CSV with a quoted note.
+    #[test]
+    fn test_export_backup_catalog_formats() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "data").unwrap();
+        let backup_folder = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let folder_name = backup_folder.file_name().unwrap().to_str().unwrap();
+        set_backup_note(
+            save_dir,
+            folder_name,
+            Some("boss, \"final\"\nround".to_string()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        let json_path = save_dir.join("catalog.json");
+        assert_eq!(
+            export_backup_catalog(
+                save_dir,
+                CatalogFormat::Json,
+                &json_path,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            1
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json[0]["filename"], folder_name);
+        assert_eq!(json[0]["game_number"], 0);
+        assert_eq!(json[0]["note"], "boss, \"final\"\nround");
+
+        let csv_path = save_dir.join("catalog.csv");
+        export_backup_catalog(
+            save_dir,
+            CatalogFormat::Csv,
+            &csv_path,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let csv = fs::read_to_string(&csv_path).unwrap();
+        assert!(csv.starts_with("filename,game_number,size,modified,locked,note,hash\n"));
+        assert!(csv.contains(&format!("{},0,4,", folder_name)));
+        assert!(csv.contains(",false,\"boss, \"\"final\"\"\nround\","));
+    }
+
+    /// Tests that a delta round-trips edits, growth, and truncation of the base.
+    #[test]
+    fn test_delta_round_trips_changed_content() {
+        let base: Vec<u8> = (0..20_000_u32).map(|i| (i % 251) as u8).collect();
+
+        let mut edited = base.clone();
+        edited[5_000] ^= 0xff;
+        let mut grown = base.clone();
+        grown.extend_from_slice(b"new tail");
+        let truncated = base[..9_000].to_vec();
+
+        for target in [base.clone(), edited, grown, truncated, Vec::new()] {
+            let delta = encode_delta(&base, &target);
+            assert_eq!(apply_delta(&base, &delta).unwrap(), target);
+        }
+    }
+
+    /// Tests that a one-byte change only stores the block that changed.
+    #[test]
+    fn test_delta_of_small_edit_stays_small() {
+        let base = vec![7_u8; DELTA_BLOCK_SIZE * 16];
+        let mut target = base.clone();
+        target[DELTA_BLOCK_SIZE * 3 + 10] = 8;
+
+        let delta = encode_delta(&base, &target);
+
+        assert!(delta.len() < DELTA_BLOCK_SIZE + 64);
+    }
+
+    /// Tests that a damaged delta is rejected instead of producing wrong content.
+    #[test]
+    fn test_apply_delta_rejects_corrupt_input() {
+        let base = vec![1_u8; DELTA_BLOCK_SIZE * 4];
+        let mut target = base.clone();
+        target[50] = 2;
+        let delta = encode_delta(&base, &target);
+
+        assert!(apply_delta(&base, &delta[..delta.len() - 1]).is_err());
+        assert!(apply_delta(&base[..DELTA_BLOCK_SIZE], &delta).is_err());
+        assert!(apply_delta(&base, b"NOTADELTA").is_err());
+
+        // A header claiming a huge length must fail without trying to allocate it.
+        let mut oversized = delta.clone();
+        oversized[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(apply_delta(&base, &oversized).is_err());
+        oversized[8..16].copy_from_slice(&(usize::MAX as u64 / 2).to_le_bytes());
+        assert!(apply_delta(&base, &oversized).is_err());
+    }
+
+    /// Tests that delta backups list and restore in full and survive deleting their base.
+    #[test]
+    fn test_delta_backup_lists_restores_and_outlives_base() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_7.sav");
+        let original: Vec<u8> = (0..40_000_u32).map(|i| (i % 241) as u8).collect();
+        let mut changed = original.clone();
+        changed[12_345] ^= 0xff;
+
+        let settings = BackupSettings {
+            delta_backups: true,
+            ..BackupSettings::default()
+        };
+        fs::write(&main_sav, &original).unwrap();
+        let base = perform_backup_for_game_with(save_dir, 7, 100, &settings)
+            .unwrap()
+            .unwrap();
+        fs::write(&main_sav, &changed).unwrap();
+        let delta = perform_backup_for_game_with(save_dir, 7, 100, &settings)
+            .unwrap()
+            .unwrap();
+
+        assert!(base.join("gamesave_7.sav").exists());
+        assert!(!delta.join("gamesave_7.sav").exists());
+        assert!(delta.join("gamesave_7.sav.delta").exists());
+        assert_eq!(
+            fs::read_to_string(delta.join(DELTA_BASE_FILE_NAME)).unwrap(),
+            base.file_name().unwrap().to_string_lossy()
+        );
+
+        let listed = get_backups(save_dir, true, Some(7), &BackupSettings::default()).unwrap();
+        let listed_delta = listed
+            .iter()
+            .find(|b| b.path == delta.to_string_lossy())
+            .unwrap();
+        assert_eq!(listed_delta.size, changed.len() as u64);
+        assert_eq!(
+            listed_delta.hash,
+            fs::read_to_string(delta.join(HASH_FILE_NAME)).unwrap()
+        );
+
+        fs::write(&main_sav, b"newer progress").unwrap();
+        restore_backup(&delta, save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(fs::read(&main_sav).unwrap(), changed);
+
+        delete_backup_folder(&base, false, &BackupSettings::default()).unwrap();
+        assert!(delta.join("gamesave_7.sav").exists());
+        assert!(!delta.join("gamesave_7.sav.delta").exists());
+        assert!(!delta.join(DELTA_BASE_FILE_NAME).exists());
+        assert_eq!(fs::read(delta.join("gamesave_7.sav")).unwrap(), changed);
+    }
+
+    /// Tests that a save still being written is reported as unsettled.
+    #[test]
+    fn test_settled_metadata_detects_growing_save() {
+        let dir = tempdir().unwrap();
+        let main_sav = dir.path().join("gamesave_0.sav");
+        fs::write(&main_sav, "settled").unwrap();
+
+        let settled =
+            read_settled_source_metadata(&main_sav, std::time::Duration::from_millis(50)).unwrap();
+        assert_eq!(settled.unwrap().size, 7);
+
+        let writer_path = main_sav.clone();
+        let writer = std::thread::spawn(move || {
+            let mut file = fs::OpenOptions::new()
+                .append(true)
+                .open(writer_path)
+                .unwrap();
+            for _ in 0..100 {
+                file.write_all(b"chunk").unwrap();
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+        });
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let unsettled =
+            read_settled_source_metadata(&main_sav, std::time::Duration::from_millis(50)).unwrap();
+        writer.join().unwrap();
+        assert!(unsettled.is_none());
+    }
+
+    /// Tests that favorites are keyed by backup ID, listed, and not kept by cleanup.
+    #[test]
+    fn test_toggle_favorite_is_listed_and_does_not_protect_from_cleanup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_3.sav");
+        fs::write(&main_sav, "first").unwrap();
+        let first = perform_backup_for_game(save_dir, 3, 100).unwrap().unwrap();
+        let first_name = first.file_name().unwrap().to_string_lossy().to_string();
+
+        assert!(toggle_backup_favorite(save_dir, &first_name, &BackupSettings::default()).unwrap());
+        let listed = get_backups(save_dir, false, Some(3), &BackupSettings::default()).unwrap();
+        assert!(listed[0].favorite);
+        assert!(!listed[0].locked);
+        assert!(
+            get_backup_details(save_dir, &first, &BackupSettings::default())
+                .unwrap()
+                .favorite
+        );
+
+        assert!(
+            !toggle_backup_favorite(save_dir, &first_name, &BackupSettings::default()).unwrap()
+        );
+        assert!(
+            !get_backups(save_dir, false, Some(3), &BackupSettings::default()).unwrap()[0].favorite
+        );
+
+        // A tag named "favorite" is an ordinary tag, not a second favorite mark.
+        set_backup_tags(
+            save_dir,
+            &first_name,
+            vec!["favorite".into()],
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(
+            !get_backups(save_dir, false, Some(3), &BackupSettings::default()).unwrap()[0].favorite
+        );
+
+        assert!(toggle_backup_favorite(save_dir, &first_name, &BackupSettings::default()).unwrap());
+        let id = read_manifest(&first).unwrap().id;
+        assert_eq!(
+            load_index(&save_dir.join(BACKUP_DIR_NAME)).favorites,
+            std::collections::HashSet::from([id])
+        );
+        assert!(
+            toggle_backup_favorite(save_dir, "Game 4 - missing", &BackupSettings::default())
+                .is_err()
+        );
+        for (content, limit) in [("second", 100), ("third", 2)] {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            fs::write(&main_sav, content).unwrap();
+            perform_backup_for_game(save_dir, 3, limit)
+                .unwrap()
+                .unwrap();
+        }
+
+        assert!(
+            !first.exists(),
+            "favorites are not protected from the limit"
+        );
+        assert!(load_index(&save_dir.join(BACKUP_DIR_NAME))
+            .favorites
+            .is_empty());
+    }
+
+    /// Tests that the size cap deletes the oldest backups across games, sparing locked and favorite ones.
+    #[test]
+    fn test_prune_by_total_size_deletes_oldest_unprotected_first() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_at = |game_number: u32, content: &str, secs: u64| {
+            let save = save_dir.join(format!("gamesave_{}.sav", game_number));
+            fs::write(&save, content).unwrap();
+            File::options()
+                .write(true)
+                .open(&save)
+                .unwrap()
+                .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
+            perform_backup_for_game(save_dir, game_number, 100)
+                .unwrap()
+                .unwrap()
+        };
+        let locked = backup_at(0, "save a", 1_700_000_000);
+        let favorite = backup_at(1, "save b", 1_700_000_100);
+        let oldest_unprotected = backup_at(0, "save c", 1_700_000_200);
+        let kept = backup_at(1, "save d", 1_700_000_300);
+        let newest = backup_at(0, "save e", 1_700_000_400);
+        set_backup_lock(&locked, true).unwrap();
+        let favorite_name = favorite.file_name().unwrap().to_string_lossy().to_string();
+        toggle_backup_favorite(save_dir, &favorite_name, &BackupSettings::default()).unwrap();
+
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let total = folder_size(&backup_root).unwrap();
+        let cap = total - folder_size(&oldest_unprotected).unwrap();
+
+        let mut index = load_index(&backup_root);
+        assert_eq!(
+            prune_by_total_size(
+                save_dir,
+                total,
+                &mut index,
+                &newest,
+                true,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            0
+        );
+        assert_eq!(
+            prune_by_total_size(
+                save_dir,
+                cap,
+                &mut index,
+                &newest,
+                true,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            1
+        );
+
+        assert!(!oldest_unprotected.exists());
+        for folder in [&locked, &favorite, &kept, &newest] {
+            assert!(folder.exists(), "{:?} should be kept", folder);
+        }
+    }
+
+    /// Tests that archived backups count toward the size cap but are never deleted by it.
+    #[test]
+    fn test_prune_by_total_size_counts_archived_backups() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders = [
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            "Game 1 - 01-Jan-2024 11-00-00 AM",
+            "Game 1 - 01-Jan-2024 12-00-00 PM",
+        ];
+        for folder in folders {
+            let path = backup_root.join(folder);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("gamesave_0.sav"), vec![0_u8; 1000]).unwrap();
+        }
+        let archived = backup_root.join(folders[0]);
+        set_backup_archived(&archived, true).unwrap();
+        let total = folder_size(&backup_root).unwrap();
+
+        // Only the archived bytes push the total over the cap.
+        let cap = total - 1;
+        let newest = backup_root.join(folders[2]);
+        let mut index = load_index(&backup_root);
+        assert_eq!(
+            prune_by_total_size(
+                save_dir,
+                cap,
+                &mut index,
+                &newest,
+                true,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            1
+        );
+        assert!(archived.exists());
+        assert!(!backup_root.join(folders[1]).exists());
+        assert!(newest.exists());
+    }
+
+    /// Tests that the size cap counts the trash and quarantine and empties the trash first.
+    #[test]
+    fn test_prune_by_total_size_counts_whole_root_and_empties_trash_first() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders = [
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            "Game 1 - 01-Jan-2024 11-00-00 AM",
+            "Game 1 - 01-Jan-2024 12-00-00 PM",
+        ];
+        for folder in folders {
+            let path = backup_root.join(folder);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("gamesave_0.sav"), vec![0_u8; 1000]).unwrap();
+        }
+        delete_backup_folder(
+            &backup_root.join(folders[0]),
+            false,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let quarantined = backup_root.join(QUARANTINE_DIR_NAME).join("damaged");
+        fs::create_dir_all(&quarantined).unwrap();
+        fs::write(quarantined.join("gamesave_0.sav"), vec![0_u8; 1000]).unwrap();
+
+        // Emptying the trash alone brings the root down to the cap.
+        let cap = folder_size(&backup_root).unwrap()
+            - folder_size(&backup_root.join(TRASH_DIR_NAME)).unwrap();
+        let newest = backup_root.join(folders[2]);
+        let mut index = load_index(&backup_root);
+        assert_eq!(
+            prune_by_total_size(
+                save_dir,
+                cap,
+                &mut index,
+                &newest,
+                true,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            0
+        );
+        assert!(list_trash(save_dir, &BackupSettings::default())
+            .unwrap()
+            .is_empty());
+        assert!(backup_root.join(folders[1]).exists());
+
+        // The quarantined bytes keep the root over a slightly lower cap.
+        assert_eq!(
+            prune_by_total_size(
+                save_dir,
+                cap - 1,
+                &mut index,
+                &newest,
+                true,
+                &BackupSettings::default()
+            )
+            .unwrap(),
+            1
+        );
+        assert!(!backup_root.join(folders[1]).exists());
+        assert!(quarantined.exists());
+        assert!(newest.exists());
+    }
+
+    /// Tests that known fields are found in a JSON save, preferring top-level ones.
+    #[test]
+    fn test_parse_save_metadata_reads_json_fields() {
+        let fixture = br#"{
+            "Version": 3,
+            "Run": {
+                "CurrentDay": 14,
+                "Difficulty": "Hard",
+                "Survivors": [
+                    {"Id": 1, "DisplayName": "Mara"},
+                    {"Id": 2, "DisplayName": "Jonah"},
+                    {"Id": 3, "DisplayName": "Mara"}
+                ],
+                "Shelter": {"Day": 2}
+            }
+        }"#;
+
+        let metadata = parse_save_metadata(fixture);
+
+        assert_eq!(metadata.raw_size, fixture.len() as u64);
+        assert_eq!(metadata.encoding, SaveEncoding::Json);
+        assert_eq!(metadata.day, Some(14));
+        assert_eq!(metadata.difficulty.as_deref(), Some("Hard"));
+        assert_eq!(metadata.survivors, vec!["Mara", "Jonah"]);
+    }
+
+    /// Tests that gzip-compressed JSON is read and unknown bytes only report their size.
+    #[test]
+    fn test_parse_save_metadata_handles_gzip_and_unknown_bytes() {
+        let json =
+            b"\xef\xbb\xbf{\"day_count\": \"7\", \"survivor_names\": [\"Ada\"], \"difficulty\": 2}";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json).unwrap();
+        let fixture = encoder.finish().unwrap();
+
+        let metadata = parse_save_metadata(&fixture);
+        assert_eq!(metadata.encoding, SaveEncoding::GzipJson);
+        assert_eq!(metadata.raw_size, fixture.len() as u64);
+        assert_eq!(metadata.day, Some(7));
+        assert_eq!(metadata.difficulty.as_deref(), Some("2"));
+        assert_eq!(metadata.survivors, vec!["Ada"]);
+
+        let binary = [0x00, 0x13, 0x37, 0xff, b'D', b'a', b'y'];
+        let metadata = parse_save_metadata(&binary);
+        assert_eq!(metadata.encoding, SaveEncoding::Unknown);
+        assert_eq!(metadata.raw_size, 7);
+        assert_eq!(metadata.day, None);
+        assert!(metadata.survivors.is_empty());
+    }
+
+    /// Tests that metadata is read from a backup folder and attached to listings on request.
+    #[test]
+    fn test_read_save_metadata_from_backup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_4.sav"), r#"{"Day": 30}"#).unwrap();
+        let folder = perform_backup_for_game(save_dir, 4, 100).unwrap().unwrap();
+
+        assert_eq!(read_save_metadata(&folder).unwrap().day, Some(30));
+        assert!(read_save_metadata(save_dir).is_err());
+
+        let mut listed = get_backups(save_dir, false, None, &BackupSettings::default()).unwrap();
+        assert!(listed[0].metadata.is_none());
+        attach_save_metadata(&mut listed);
+        assert_eq!(listed[0].metadata.as_ref().unwrap().day, Some(30));
+    }
+
+    /// Tests that excluded `.bak` files are neither stored nor restored.
+    #[test]
+    fn test_excluded_bak_files_are_not_backed_up_or_restored() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_6.sav");
+        let bak_sav = save_dir.join("gamesave_6.sav.bak");
+        fs::write(&main_sav, "main").unwrap();
+        fs::write(&bak_sav, "bak").unwrap();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let without_bak = backup_root.join("Game 7 - 01-Jan-2024 10-00-00 AM");
+        let with_bak = backup_root.join("Game 7 - 02-Jan-2024 10-00-00 AM");
+        fs::create_dir_all(&without_bak).unwrap();
+        fs::create_dir_all(&with_bak).unwrap();
+
+        let excluded = BackupSettings {
+            include_bak_files: false,
+            ..BackupSettings::default()
+        };
+        copy_save_files(
+            &build_save_paths(save_dir, 6),
+            &without_bak,
+            None,
+            false,
+            &excluded,
+            &mut |_| {},
+        )
+        .unwrap();
+        copy_save_files(
+            &build_save_paths(save_dir, 6),
+            &with_bak,
+            None,
+            false,
+            &BackupSettings::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+        assert!(without_bak.join("gamesave_6.sav").exists());
+        assert!(!without_bak.join("gamesave_6.sav.bak").exists());
+        assert!(with_bak.join("gamesave_6.sav.bak").exists());
+
+        write_hash_file(
+            &without_bak,
+            &calculate_hash(&main_sav, &BackupSettings::default()).unwrap(),
+        )
+        .unwrap();
+        let listed = backup_info_from_folder(
+            &without_bak,
+            "Game 7 - 01-Jan-2024 10-00-00 AM",
+            save_dir,
+            false,
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(listed.size, 4);
+
+        fs::write(&main_sav, "live main").unwrap();
+        fs::write(&bak_sav, "live bak").unwrap();
+        restore_backup(&with_bak, save_dir, &excluded).unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "main");
+        assert_eq!(fs::read_to_string(&bak_sav).unwrap(), "live bak");
+    }
+
+    /// Tests that renaming a backup keeps its annotations, slot history, and delta dependents.
+    #[test]
+    fn test_rename_backup_moves_annotations_and_deltas() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_2.sav"), "first").unwrap();
+        let first = perform_backup_for_game(save_dir, 2, 100).unwrap().unwrap();
+        let first_name = first.file_name().unwrap().to_string_lossy().to_string();
+        set_backup_note(
+            save_dir,
+            &first_name,
+            Some("before the boss".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        set_backup_tags(
+            save_dir,
+            &first_name,
+            vec!["boss".into()],
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(toggle_backup_favorite(save_dir, &first_name, &BackupSettings::default()).unwrap());
+        let dependent = save_dir.join(BACKUP_DIR_NAME).join("Game 2 - dependent");
+        fs::create_dir_all(&dependent).unwrap();
+        fs::write(dependent.join(DELTA_BASE_FILE_NAME), &first_name).unwrap();
+
+        let labeled = rename_backup(
+            save_dir,
+            &first_name,
+            Some(" Final Boss ".into()),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(labeled, format!("{} [Final Boss]", first_name));
+        assert!(!first.exists());
+        assert_eq!(
+            parse_backup_folder_name(&labeled),
+            parse_backup_folder_name(&first_name)
+        );
+        assert_eq!(
+            fs::read_to_string(dependent.join(DELTA_BASE_FILE_NAME)).unwrap(),
+            labeled
+        );
+        let index = load_index(&save_dir.join(BACKUP_DIR_NAME));
+        assert_eq!(index.games[&2].last_backup_path, labeled);
+
+        let listed = get_backups(save_dir, false, Some(2), &BackupSettings::default()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].filename, labeled);
+        assert_eq!(listed[0].note.as_deref(), Some("before the boss"));
+        assert_eq!(listed[0].tags, vec!["boss"]);
+        assert!(listed[0].favorite);
+
+        assert!(rename_backup(
+            save_dir,
+            &labeled,
+            Some("a]b".into()),
+            &BackupSettings::default()
+        )
+        .is_err());
+        assert!(rename_backup(
+            save_dir,
+            &labeled,
+            Some("x".repeat(65)),
+            &BackupSettings::default()
+        )
+        .is_err());
+        assert!(rename_backup(save_dir, "../outside", None, &BackupSettings::default()).is_err());
+        assert!(rename_backup(
+            save_dir,
+            "Game 2 - missing",
+            None,
+            &BackupSettings::default()
+        )
+        .is_err());
+
+        let unlabeled =
+            rename_backup(save_dir, &labeled, None, &BackupSettings::default()).unwrap();
+        assert_eq!(unlabeled, first_name);
+        assert!(first.is_dir());
+        assert!(
+            get_backups(save_dir, false, Some(2), &BackupSettings::default()).unwrap()[0].favorite
+        );
+    }
+
+    /// Tests that a restore token confirms only its own restore, once, before it expires.
+    #[test]
+    fn test_prepare_restore_token_is_single_use_and_bound() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "backed up").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), "live").unwrap();
+
+        let pending = PendingRestores::default();
+        let prepared = prepare_restore(
+            &pending,
+            &backup,
+            save_dir,
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(prepared.expires_in_seconds, 60);
+        assert_eq!(prepared.plan.files[0].file_name, "gamesave_0.sav");
+        assert_eq!(prepared.plan.files[0].action, RestoreFileAction::Overwrite);
+
+        let request = |target_dir: &std::path::Path| RestoreRequest::Backup {
+            backup_path: backup.clone(),
+            target_dir: target_dir.to_path_buf(),
+        };
+        let other_dir = tempdir().unwrap();
+        assert!(pending
+            .redeem(&prepared.token, &request(other_dir.path()))
+            .is_err());
+        // A mismatched attempt spends the token.
+        assert!(pending.redeem(&prepared.token, &request(save_dir)).is_err());
+
+        let prepared = prepare_restore(
+            &pending,
+            &backup,
+            save_dir,
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        assert!(pending.redeem(&prepared.token, &request(save_dir)).is_ok());
+        assert!(pending.redeem(&prepared.token, &request(save_dir)).is_err());
+        assert!(pending.redeem("unknown", &request(save_dir)).is_err());
+
+        let expiring = PendingRestores::with_ttl(std::time::Duration::ZERO);
+        let prepared = prepare_restore(
+            &expiring,
+            &backup,
+            save_dir,
+            true,
+            &BackupSettings::default(),
+        )
+        .unwrap();
+        let err = expiring
+            .redeem(&prepared.token, &request(save_dir))
+            .unwrap_err();
+        assert!(err.contains("expired"));
+    }
+
+    /// Tests that slot and session restore tokens are bound to their slot and label.
+    #[test]
+    fn test_slot_and_session_restore_tokens_are_bound() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "backed up").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let pending = PendingRestores::default();
+
+        let to_slot = |game_number: u32| RestoreRequest::BackupToSlot {
+            backup_path: backup.clone(),
+            target_dir: save_dir.to_path_buf(),
+            game_number,
+        };
+        let confirmation = prepare_restore_to_slot(&pending, &backup, save_dir, 2).unwrap();
+        assert!(pending.redeem(&confirmation.token, &to_slot(3)).is_err());
+        let confirmation = prepare_restore_to_slot(&pending, &backup, save_dir, 2).unwrap();
+        assert!(pending.redeem(&confirmation.token, &to_slot(2)).is_ok());
+        // A slot token does not confirm a plain restore of the same backup.
+        let confirmation = prepare_restore_to_slot(&pending, &backup, save_dir, 0).unwrap();
+        let plain = RestoreRequest::Backup {
+            backup_path: backup.clone(),
+            target_dir: save_dir.to_path_buf(),
+        };
+        assert!(pending.redeem(&confirmation.token, &plain).is_err());
+
+        assert!(
+            prepare_session_restore(&pending, save_dir, "Day 1", &BackupSettings::default())
+                .is_err()
+        );
+        create_session_snapshot(save_dir, "Day 1", 100, &BackupSettings::default()).unwrap();
+        let confirmation =
+            prepare_session_restore(&pending, save_dir, " Day 1 ", &BackupSettings::default())
+                .unwrap();
+        let other = session_restore_request(save_dir, "Day 2").unwrap();
+        assert!(pending.redeem(&confirmation.token, &other).is_err());
+        let confirmation =
+            prepare_session_restore(&pending, save_dir, "Day 1", &BackupSettings::default())
+                .unwrap();
+        let request = session_restore_request(save_dir, "Day 1 ").unwrap();
+        assert!(pending.redeem(&confirmation.token, &request).is_ok());
+    }
+
+    /// Tests that archived backups count as locked and are skipped even by locked batch deletes.
+    #[test]
+    fn test_archived_backups_are_invisible_to_cleanup() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        let folders = [
+            "Game 1 - 01-Jan-2024 10-00-00 AM",
+            "Game 1 - 01-Jan-2024 11-00-00 AM",
+            "Game 1 - 01-Jan-2024 12-00-00 PM",
+        ];
+        for folder in folders {
+            let path = backup_root.join(folder);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("gamesave_0.sav"), folder).unwrap();
+        }
+        let archived = backup_root.join(folders[2]);
+        set_backup_archived(&archived, true).unwrap();
+
+        let listed = get_backups(save_dir, false, Some(0), &BackupSettings::default()).unwrap();
+        assert!(listed[0].archived && listed[0].locked);
+        assert!(!listed[1].archived && !listed[1].locked);
+
+        // The archived backup is not the slot's newest, so 11:00 is kept as the latest.
+        let deleted = delete_backups_batch(
+            save_dir,
+            &[0],
+            true,
+            true,
+            false,
+            &BackupSettings::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(deleted, 1);
+        let remaining: Vec<String> =
+            get_backups(save_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .into_iter()
+                .map(|b| b.filename)
+                .collect();
+        assert_eq!(remaining, vec![folders[2], folders[1]]);
+
+        delete_backup_folder(&archived, false, &BackupSettings::default()).unwrap();
+        assert!(!archived.exists());
+
+        assert!(set_backup_archived(&archived, true).is_err());
+        let kept = backup_root.join(folders[1]);
+        set_backup_archived(&kept, true).unwrap();
+        set_backup_archived(&kept, false).unwrap();
+        assert!(
+            !get_backups(save_dir, false, Some(0), &BackupSettings::default()).unwrap()[0].locked
+        );
+    }
+
+    /// Tests that backing up all slots reports created, skipped, and failed slots.
+    #[test]
+    fn test_backup_all_now_summarizes_each_slot() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "slot 0").unwrap();
+        fs::write(save_dir.join("gamesave_2.sav"), "slot 2").unwrap();
+        fs::write(save_dir.join("gamesave_5.sav.bak"), "bak only").unwrap();
+
+        let summary = backup_all_now(save_dir, 100, &BackupSettings::default()).unwrap();
+        assert_eq!(summary.created, vec![0, 2]);
+        assert!(summary.skipped.is_empty());
+        assert!(summary.errors.is_empty());
+        assert_eq!(
+            get_backups(save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        let index = load_index(&save_dir.join(BACKUP_DIR_NAME));
+        assert!(index.games.contains_key(&0) && index.games.contains_key(&2));
+
+        let summary = backup_all_now(save_dir, 100, &BackupSettings::default()).unwrap();
+        assert!(summary.created.is_empty());
+        assert_eq!(summary.skipped, vec![0, 2]);
+
+        assert!(
+            backup_all_now(&save_dir.join("missing"), 100, &BackupSettings::default()).is_err()
+        );
+    }
+
+    /// Tests that compaction drops entries for vanished folders and writes only when needed.
+    #[test]
+    fn test_compact_index_removes_entries_for_missing_folders() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_root = save_dir.join(BACKUP_DIR_NAME);
+        fs::write(save_dir.join("gamesave_0.sav"), "kept").unwrap();
+        let kept = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        let kept_name = kept.file_name().unwrap().to_string_lossy().to_string();
+        fs::write(save_dir.join("gamesave_1.sav"), "gone").unwrap();
+        let gone = perform_backup_for_game(save_dir, 1, 100).unwrap().unwrap();
+        let gone_name = gone.file_name().unwrap().to_string_lossy().to_string();
+        fs::write(save_dir.join("gamesave_2.sav"), "quarantined").unwrap();
+        let held = perform_backup_for_game(save_dir, 2, 100).unwrap().unwrap();
+        let held_name = held.file_name().unwrap().to_string_lossy().to_string();
+        for name in [&kept_name, &gone_name, &held_name] {
+            set_backup_note(
+                save_dir,
+                name,
+                Some("note".into()),
+                &BackupSettings::default(),
+            )
+            .unwrap();
+            assert!(toggle_backup_favorite(save_dir, name, &BackupSettings::default()).unwrap());
+        }
+        quarantine_backup(&held, "broken").unwrap();
+        fs::remove_dir_all(&gone).unwrap();
+
+        assert_eq!(
+            compact_index(save_dir, &BackupSettings::default()).unwrap(),
+            4
+        );
+        let index = load_index(&backup_root);
+        assert_eq!(index.notes.len(), 2);
+        assert!(index.favorites.contains(&read_manifest(&kept).unwrap().id));
+        assert_eq!(
+            index.favorites.len(),
+            2,
+            "the deleted backup's favorite goes"
+        );
+        assert!(index.games.contains_key(&0));
+        assert!(!index.games.contains_key(&1));
+        assert!(!index.games.contains_key(&2));
+
+        let written = fs::metadata(backup_root.join(INDEX_FILE_NAME))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(
+            compact_index(save_dir, &BackupSettings::default()).unwrap(),
+            0
+        );
+        assert_eq!(
+            fs::metadata(backup_root.join(INDEX_FILE_NAME))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            written
+        );
+    }
+
+    /// Tests that a rename failure midway through a restore rolls back the files already replaced.
+    #[test]
+    fn test_restore_rolls_back_when_a_rename_fails() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        let bak_sav = save_dir.join("gamesave_0.sav.bak");
+        fs::write(&main_sav, "old main").unwrap();
+        fs::write(&bak_sav, "old bak").unwrap();
+        let backup = perform_backup_for_game(save_dir, 0, 100).unwrap().unwrap();
+        fs::write(&main_sav, "live main").unwrap();
+        fs::write(&bak_sav, "live bak").unwrap();
+
+        // A non-empty directory where the live .bak would be set aside makes its rename fail
+        // after the main save, which sorts first, has already been replaced.
+        let blocker = save_dir.join(".gamesave_0.sav.bak.restore-orig");
+        fs::create_dir_all(&blocker).unwrap();
+        fs::write(blocker.join("keep"), "").unwrap();
+
+        let err = restore_backup(&backup, save_dir, &BackupSettings::default()).unwrap_err();
+        assert!(err.contains("gamesave_0.sav.bak"), "{}", err);
+        assert!(err.contains("left unchanged"), "{}", err);
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "live main");
+        assert_eq!(fs::read_to_string(&bak_sav).unwrap(), "live bak");
+        let leftovers: Vec<String> = fs::read_dir(save_dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.contains(".restore-"))
+            .collect();
+        assert_eq!(leftovers, vec![".gamesave_0.sav.bak.restore-orig"]);
+
+        fs::remove_dir_all(&blocker).unwrap();
+        restore_backup(&backup, save_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(fs::read_to_string(&main_sav).unwrap(), "old main");
+        assert_eq!(fs::read_to_string(&bak_sav).unwrap(), "old bak");
+    }
+
+    /// Verifies that diffing against the live save also compares saves of different sizes.
+    #[test]
+    fn test_diff_backup_against_current_counts_size_changes() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let backup_dir = save_dir
+            .join(BACKUP_DIR_NAME)
+            .join("Game 1 - 01-Jan-2024 10-00-00 AM");
+        fs::create_dir_all(&backup_dir).unwrap();
+        let original = vec![7_u8; 10_000];
+        fs::write(backup_dir.join("gamesave_0.sav"), &original).unwrap();
+        let live = save_dir.join("gamesave_0.sav");
+
+        fs::write(&live, &original).unwrap();
+        assert_eq!(
+            diff_backup_against_current(save_dir, &backup_dir).unwrap(),
+            CurrentSaveDiff {
+                identical: true,
+                first_diff_offset: None,
+                byte_diff_count: 0,
+                size_delta: 0,
+            }
+        );
+
+        let mut grown = original.clone();
+        grown[6_000] = 0;
+        grown.extend_from_slice(&[1, 2, 3]);
+        fs::write(&live, &grown).unwrap();
+        assert_eq!(
+            diff_backup_against_current(save_dir, &backup_dir).unwrap(),
+            CurrentSaveDiff {
+                identical: false,
+                first_diff_offset: Some(6_000),
+                byte_diff_count: 4,
+                size_delta: 3,
+            }
+        );
+
+        fs::write(&live, &original[..4_000]).unwrap();
+        compress_backup(&backup_dir, &BackupSettings::default()).unwrap();
+        assert_eq!(
+            diff_backup_against_current(save_dir, &backup_dir).unwrap(),
+            CurrentSaveDiff {
+                identical: false,
+                first_diff_offset: Some(4_000),
+                byte_diff_count: 6_000,
+                size_delta: -6_000,
+            }
+        );
+
+        fs::remove_file(&live).unwrap();
+        assert!(diff_backup_against_current(save_dir, &backup_dir).is_err());
+    }
+
+    /// Tests that an override gives each save directory its own folder outside the save dir.
+    #[test]
+    fn test_backup_root_override_is_per_save_directory() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("a").join("saves");
+        let other_save_dir = dir.path().join("b").join("saves");
+        let override_root = dir.path().join("external");
+
+        assert_eq!(
+            resolve_backup_root(&save_dir, None),
+            save_dir.join(BACKUP_DIR_NAME)
+        );
+
+        let root = resolve_backup_root(&save_dir, Some(&override_root));
+        assert_eq!(root, override_root.join(save_key(&save_dir)));
+        assert!(!root.starts_with(&save_dir));
+        assert_ne!(
+            root,
+            resolve_backup_root(&other_save_dir, Some(&override_root))
+        );
+    }
+
+    /// Tests that backups follow the override carried in the settings passed to each call.
+    #[test]
+    fn test_backup_root_override_comes_from_settings() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("saves");
+        fs::create_dir_all(&save_dir).unwrap();
+        fs::write(save_dir.join("gamesave_0.sav"), "external").unwrap();
+        let settings = BackupSettings {
+            backup_root_override: Some(dir.path().join("external")),
+            ..BackupSettings::default()
+        };
+
+        perform_backup_for_game_with(&save_dir, 0, 10, &settings)
+            .unwrap()
+            .unwrap();
+
+        let root = resolve_backup_root(&save_dir, settings.backup_root_override.as_deref());
+        assert_eq!(BackupStore::new(&save_dir, &settings).unwrap().root, root);
+        assert_eq!(
+            get_backups(&save_dir, false, None, &settings)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(!save_dir.join(BACKUP_DIR_NAME).exists());
+        assert!(
+            get_backups(&save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// Tests that a same-size save with an unchanged mtime is caught by the periodic rehash.
+    #[test]
+    fn test_periodic_rehash_catches_metadata_collision() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path();
+        let main_sav = save_dir.join("gamesave_0.sav");
+        let backup_root = ensure_backup_root(save_dir, &BackupSettings::default()).unwrap();
+        let mut index = BackupIndex::default();
+
+        fs::write(&main_sav, "aaaa").unwrap();
+        let modified = fs::metadata(&main_sav).unwrap().modified().unwrap();
+        let attempt = |index: &mut BackupIndex| {
+            perform_backup_for_game_internal(
+                save_dir,
+                &backup_root,
+                0,
+                index,
+                &[],
+                &BackupSettings::default(),
+                BackupOptions {
+                    limit: 100,
+                    ..BackupOptions::default()
+                },
+            )
+            .unwrap()
+        };
+        assert!(matches!(attempt(&mut index), BackupOutcome::Created(_)));
+
+        // Same size and mtime, as on a filesystem with coarse timestamps.
+        fs::write(&main_sav, "bbbb").unwrap();
+        set_file_modified(&main_sav, modified).unwrap();
+        assert_eq!(attempt(&mut index), BackupOutcome::SkippedDuplicate);
+        assert_eq!(index.games[&0].last_verified, 1);
+
+        index.games.get_mut(&0).unwrap().last_verified = DEFAULT_HASH_RECHECK_INTERVAL - 1;
+        assert!(matches!(attempt(&mut index), BackupOutcome::Created(_)));
+        assert_eq!(index.games[&0].last_verified, 0);
+        assert_eq!(
+            index.games[&0].last_hash,
+            calculate_hash(&main_sav, &BackupSettings::default()).unwrap()
+        );
+    }
 }
@@ -0,0 +1,295 @@
+use super::common::{TRASH_DIR_NAME, TRASH_FILE_NAME};
+use super::delta::release_delta_links;
+use super::index::{backup_root_for, BackupIndex, BackupStore};
+use super::manifest::read_manifest;
+use super::notes::read_note_sidecar;
+use super::settings::BackupSettings;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Days a deleted backup stays in the trash before the next cleanup pass empties it.
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 7;
+
+/// Separates the original folder name from the deletion time in a trashed folder's name.
+const TRASH_SUFFIX_MARKER: char = '~';
+const TRASH_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+
+/// Index metadata of a trashed backup, stored inside the trashed folder.
+///
+/// The index entries are pruned on delete; this record lets a restore bring them back.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+struct TrashRecord {
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    favorite: bool,
+    /// Labels of the sessions the backup belonged to.
+    #[serde(default)]
+    sessions: Vec<String>,
+}
+
+impl TrashRecord {
+    /// Collects the index metadata of a backup folder.
+    fn from_index(index: &BackupIndex, folder_name: &str, id: Option<&str>) -> Self {
+        let mut sessions: Vec<String> = index
+            .sessions
+            .iter()
+            .filter(|(_, folders)| folders.iter().any(|folder| folder == folder_name))
+            .map(|(label, _)| label.clone())
+            .collect();
+        sessions.sort();
+        Self {
+            note: index.note_for(folder_name, id).cloned(),
+            tags: index.tags_for(folder_name, id).cloned().unwrap_or_default(),
+            favorite: index.is_favorite(folder_name, id),
+            sessions,
+        }
+    }
+
+    /// Puts the metadata back into the index for a restored backup folder.
+    fn apply(self, index: &mut BackupIndex, folder_name: &str, id: Option<&str>) {
+        let key = BackupIndex::metadata_key(folder_name, id);
+        if let Some(note) = self.note {
+            index.notes.insert(key.clone(), note);
+        }
+        if !self.tags.is_empty() {
+            index.tags.insert(key.clone(), self.tags);
+        }
+        if self.favorite {
+            index.favorites.insert(key);
+        }
+        for label in self.sessions {
+            let folders = index.sessions.entry(label).or_default();
+            if !folders.iter().any(|folder| folder == folder_name) {
+                folders.push(folder_name.to_string());
+            }
+        }
+    }
+}
+
+/// A deleted backup folder waiting in the trash.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TrashedBackup {
+    /// The folder name the backup had before it was deleted.
+    pub filename: String,
+    /// The folder name inside the trash, used to restore it.
+    pub trash_name: String,
+    pub path: String,
+    /// When the backup was deleted (RFC 3339), if the name records it.
+    pub deleted_at: Option<String>,
+}
+
+/// Returns the trash directory inside a backup root.
+fn trash_root(backup_root: &Path) -> PathBuf {
+    backup_root.join(TRASH_DIR_NAME)
+}
+
+/// Splits a trashed folder name into the original folder name and deletion time.
+fn parse_trash_name(trash_name: &str) -> Option<(&str, DateTime<Local>)> {
+    let (original, stamp) = trash_name.rsplit_once(TRASH_SUFFIX_MARKER)?;
+    let naive = NaiveDateTime::parse_from_str(stamp, TRASH_TIMESTAMP_FORMAT).ok()?;
+    let deleted_at = Local.from_local_datetime(&naive).earliest()?;
+    Some((original, deleted_at))
+}
+
+/// Moves a backup folder into `.backups/.trash/`, suffixing its name with the deletion time.
+///
+/// The folder drops out of listings, cleanup, and dedup, and can be restored until the
+/// trash is emptied. Its note, tags, favorite flag, and session membership in `index`
+/// are recorded in `trash.json` inside the trashed folder, so callers can prune them
+/// from the index. Returns the trashed folder path.
+pub(crate) fn move_to_trash(
+    backup_folder_path: &Path,
+    index: &BackupIndex,
+) -> Result<PathBuf, String> {
+    let (Some(backup_root), Some(folder_name)) = (
+        backup_folder_path.parent(),
+        backup_folder_path.file_name().and_then(|n| n.to_str()),
+    ) else {
+        return Err("Backup folder path is invalid".to_string());
+    };
+
+    let trash_dir = trash_root(backup_root);
+    fs::create_dir_all(&trash_dir).map_err(|e| e.to_string())?;
+    let trash_name = format!(
+        "{}{}{}",
+        folder_name,
+        TRASH_SUFFIX_MARKER,
+        Local::now().format(TRASH_TIMESTAMP_FORMAT)
+    );
+    let target = trash_dir.join(&trash_name);
+    if target.exists() {
+        return Err(format!("{} is already in the trash", trash_name));
+    }
+
+    let id = read_manifest(backup_folder_path).map(|m| m.id);
+    let record = TrashRecord::from_index(index, folder_name, id.as_deref());
+
+    release_delta_links(backup_folder_path, true)?;
+    fs::rename(backup_folder_path, &target).map_err(|e| e.to_string())?;
+    if record != TrashRecord::default() {
+        let written = serde_json::to_string(&record)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                fs::write(target.join(TRASH_FILE_NAME), content).map_err(|e| e.to_string())
+            });
+        if let Err(e) = written {
+            log::warn!(
+                "Failed to record metadata of trashed backup {}: {}",
+                folder_name,
+                e
+            );
+        }
+    }
+    log::info!("Moved backup {} to the trash", folder_name);
+    Ok(target)
+}
+
+/// Lists trashed backups, most recently deleted first.
+pub fn list_trash(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<TrashedBackup>, String> {
+    let trash_dir = trash_root(&backup_root_for(save_dir, settings));
+    if !trash_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut trashed = Vec::new();
+    for entry in fs::read_dir(&trash_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let trash_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let parsed = parse_trash_name(&trash_name);
+        trashed.push(TrashedBackup {
+            filename: parsed
+                .map(|(original, _)| original.to_string())
+                .unwrap_or_else(|| trash_name.clone()),
+            deleted_at: parsed.map(|(_, deleted_at)| deleted_at.to_rfc3339()),
+            trash_name,
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    trashed.sort_by(|a, b| {
+        let parse = |at: &Option<String>| {
+            at.as_deref()
+                .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        };
+        parse(&b.deleted_at).cmp(&parse(&a.deleted_at))
+    });
+    Ok(trashed)
+}
+
+/// Moves a trashed backup back into `.backups` under its original name.
+///
+/// Fails if the name is not a plain folder name, is not in the trash, or a backup
+/// with the original name already exists. The note, tags, favorite flag, and session
+/// membership recorded on delete are put back into the index; without a record the
+/// note is re-read from `note.txt`, as when rebuilding the index. Returns the restored
+/// folder path.
+pub fn restore_from_trash(
+    save_dir: &Path,
+    trash_name: &str,
+    settings: &BackupSettings,
+) -> Result<PathBuf, String> {
+    if Path::new(trash_name).file_name().and_then(|n| n.to_str()) != Some(trash_name) {
+        return Err("Invalid trash folder name".to_string());
+    }
+    let backup_root = backup_root_for(save_dir, settings);
+    let source = trash_root(&backup_root).join(trash_name);
+    if !source.is_dir() {
+        return Err(format!("No trashed backup named {}", trash_name));
+    }
+    let (folder_name, _) = parse_trash_name(trash_name)
+        .ok_or_else(|| format!("Trashed folder {} has no deletion time", trash_name))?;
+    let target = backup_root.join(folder_name);
+    if target.exists() {
+        return Err(format!("A backup named {} already exists", folder_name));
+    }
+
+    let record_path = source.join(TRASH_FILE_NAME);
+    let mut record = fs::read_to_string(&record_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<TrashRecord>(&content).ok())
+        .unwrap_or_default();
+    if record_path.exists() {
+        fs::remove_file(&record_path).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&source, &target).map_err(|e| e.to_string())?;
+
+    if record.note.is_none() {
+        record.note = read_note_sidecar(&target);
+    }
+    let id = read_manifest(&target).map(|m| m.id);
+    let mut store = BackupStore::new(save_dir, settings)?;
+    record.apply(&mut store.index, folder_name, id.as_deref());
+    store.save()?;
+
+    log::info!("Restored backup {} from the trash", folder_name);
+    Ok(target)
+}
+
+/// Permanently deletes every trashed backup, returning how many were removed.
+pub fn empty_trash(save_dir: &Path, settings: &BackupSettings) -> Result<usize, String> {
+    purge_trash(&backup_root_for(save_dir, settings), |_| true)
+}
+
+/// Permanently deletes trashed backups older than `retention_days`.
+///
+/// Runs during the cleanup after each backup. Does nothing when retention is `0`.
+pub(crate) fn purge_expired_trash(
+    backup_root: &Path,
+    retention_days: u32,
+) -> Result<usize, String> {
+    match retention_days {
+        0 => Ok(0),
+        days => purge_trash_older_than(backup_root, days, Local::now()),
+    }
+}
+
+/// Permanently deletes trashed backups deleted more than `days` before `now`.
+///
+/// Folders without a readable deletion time are left for a manual empty.
+pub(crate) fn purge_trash_older_than(
+    backup_root: &Path,
+    days: u32,
+    now: DateTime<Local>,
+) -> Result<usize, String> {
+    let cutoff = now - Duration::days(i64::from(days));
+    purge_trash(backup_root, |trash_name| {
+        parse_trash_name(trash_name).is_some_and(|(_, deleted_at)| deleted_at < cutoff)
+    })
+}
+
+/// Removes the trashed folders whose name satisfies `should_remove`.
+fn purge_trash(backup_root: &Path, should_remove: impl Fn(&str) -> bool) -> Result<usize, String> {
+    let trash_dir = trash_root(backup_root);
+    if !trash_dir.is_dir() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for entry in fs::read_dir(&trash_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if !path.is_dir() || !should_remove(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+        removed += 1;
+    }
+    if removed > 0 {
+        log::info!("Permanently deleted {} trashed backup(s)", removed);
+    }
+    Ok(removed)
+}
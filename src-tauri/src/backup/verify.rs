@@ -0,0 +1,155 @@
+use super::common::HASH_FILE_NAME;
+use super::compression::find_stored_save_file;
+use super::fileutil::DEFAULT_COPY_BUFFER_SIZE;
+use super::hashing::{hash_algorithm_of, hash_digest_of, hash_reader_with, HashAlgorithm};
+use super::listing::get_backups;
+use super::settings::BackupSettings;
+use crate::filename_utils;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Largest gap in seconds between a backup's folder-name timestamp and its main save's
+/// mtime that is still considered consistent.
+///
+/// Watcher backups are copied within seconds of the save, so their stored file is only
+/// a little newer than the timestamp taken from the live save.
+pub(crate) const TIMESTAMP_TOLERANCE_SECONDS: i64 = 60;
+
+/// Result of checking a backup's main save against its stored hash.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    /// The main save still hashes to the stored value.
+    Ok,
+    /// The main save's content no longer matches the stored hash.
+    HashMismatch,
+    /// The backup has no `.hash` file to compare against.
+    MissingHashFile,
+    /// The main save is missing or could not be read.
+    Unreadable,
+}
+
+/// A backup that failed verification.
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupVerification {
+    pub filename: String,
+    pub path: String,
+    pub status: VerifyStatus,
+    /// Read error for `Unreadable` backups.
+    pub error: Option<String>,
+}
+
+/// Recomputes a backup's main save hash and compares it with the stored `.hash` file.
+///
+/// The save is rehashed with the algorithm the stored hash was made with. Errors when
+/// the main save cannot be found or read, or the stored hash uses an unknown algorithm.
+pub fn verify_backup(backup_folder: &Path) -> Result<VerifyStatus, String> {
+    let folder_name = backup_folder
+        .file_name()
+        .ok_or_else(|| "Backup folder name is invalid".to_string())?
+        .to_string_lossy();
+    let info = filename_utils::parse_backup_folder_name(&folder_name)
+        .ok_or_else(|| format!("Not a backup folder: {}", folder_name))?;
+    let main_file =
+        find_stored_save_file(backup_folder, &format!("gamesave_{}.sav", info.game_number))
+            .ok_or_else(|| format!("Backup {} has no main save file", folder_name))?;
+
+    let stored_hash = match fs::read_to_string(backup_folder.join(HASH_FILE_NAME)) {
+        Ok(hash) => hash.trim().to_string(),
+        Err(_) => return Ok(VerifyStatus::MissingHashFile),
+    };
+    if stored_hash.is_empty() {
+        return Ok(VerifyStatus::MissingHashFile);
+    }
+    let tag = hash_algorithm_of(&stored_hash);
+    let algorithm = HashAlgorithm::from_tag(tag)
+        .ok_or_else(|| format!("Unsupported hash algorithm: {}", tag))?;
+
+    let (_, digest) = hash_reader_with(
+        algorithm,
+        DEFAULT_COPY_BUFFER_SIZE,
+        main_file.open_content()?,
+    )?;
+    if digest == hash_digest_of(&stored_hash) {
+        Ok(VerifyStatus::Ok)
+    } else {
+        Ok(VerifyStatus::HashMismatch)
+    }
+}
+
+/// Verifies every backup in the save directory and returns the ones that failed.
+pub fn verify_all_backups(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<BackupVerification>, String> {
+    let mut failures = Vec::new();
+    for backup in get_backups(save_dir, false, None, settings)? {
+        let (status, error) = match verify_backup(Path::new(&backup.path)) {
+            Ok(VerifyStatus::Ok) => continue,
+            Ok(status) => (status, None),
+            Err(e) => (VerifyStatus::Unreadable, Some(e)),
+        };
+        failures.push(BackupVerification {
+            filename: backup.filename,
+            path: backup.path,
+            status,
+            error,
+        });
+    }
+    Ok(failures)
+}
+
+/// A backup whose folder-name timestamp disagrees with its main save's mtime.
+#[derive(Debug, Serialize, Clone)]
+pub struct TimestampAudit {
+    pub filename: String,
+    pub path: String,
+    /// Timestamp encoded in the folder name (RFC 3339).
+    pub folder_timestamp: String,
+    /// Modification time of the stored main save (RFC 3339).
+    pub file_modified: String,
+    /// How many seconds the file's mtime is after the folder timestamp (negative if before).
+    pub difference_seconds: i64,
+}
+
+/// Lists backups whose folder-name timestamp and main save mtime differ by more than
+/// `TIMESTAMP_TOLERANCE_SECONDS`.
+///
+/// A mismatch can point to tampering or an mtime reset. Counter-named folders have no
+/// timestamp to compare and are skipped.
+pub fn audit_timestamp_consistency(
+    save_dir: &Path,
+    settings: &BackupSettings,
+) -> Result<Vec<TimestampAudit>, String> {
+    let mut mismatches = Vec::new();
+    for backup in get_backups(save_dir, false, None, settings)? {
+        let Some(folder_timestamp) = filename_utils::parse_backup_folder_name(&backup.filename)
+            .and_then(|info| info.timestamp)
+        else {
+            continue;
+        };
+        let Some(main_file) =
+            find_stored_save_file(Path::new(&backup.path), &backup.original_filename)
+        else {
+            continue;
+        };
+        let file_modified: DateTime<Local> = fs::metadata(&main_file.path)
+            .and_then(|m| m.modified())
+            .map_err(|e| e.to_string())?
+            .into();
+
+        let difference_seconds = (file_modified - folder_timestamp).num_seconds();
+        if difference_seconds.abs() > TIMESTAMP_TOLERANCE_SECONDS {
+            mismatches.push(TimestampAudit {
+                filename: backup.filename,
+                path: backup.path,
+                folder_timestamp: folder_timestamp.to_rfc3339(),
+                file_modified: file_modified.to_rfc3339(),
+                difference_seconds,
+            });
+        }
+    }
+    Ok(mismatches)
+}
@@ -1,10 +1,21 @@
-use crate::backup::index::BackupStore;
-use crate::backup::{self, BackupInfo};
-use crate::config::ConfigState;
-use crate::watcher::FileWatcher;
+use crate::backup::manifest::ensure_manifest;
+use crate::backup::{
+    self, BackupAllSummary, BackupComparison, BackupDetails, BackupInfo, BackupPage, BackupSetDiff,
+    BackupSettings, BackupStorageStats, BackupVerification, ByteDiff, CatalogFormat,
+    ClockSkewWarning, CurrentSaveDiff, DayCount, FullRestorePlan, PendingRestores, PreparedRestore,
+    QuarantinedBackup, RankedBackup, RestoreCancelToken, RestoreConfirmation, RestoreRequest,
+    RetentionSuggestion, SaveMetadata, TimeBounds, TimestampAudit, TrashedBackup, VerifyStatus,
+};
+use crate::config::{AppConfig, ConfigState, SavePathRelocationState};
+use crate::diagnostics::{diagnose_auto_backup, AutoBackupDiagnosis, AutoBackupSignals};
+use crate::notifications::NotificationLevel;
+use crate::watcher::{FileWatcher, PathMissingCallback};
+use crate::wrapper_launch::{LaunchMode, LaunchModeState};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{async_runtime, Emitter, Manager, State};
+use tauri_plugin_opener::OpenerExt;
 
 /// Extracts the configured save path without holding the mutex across blocking work.
 fn extract_save_path(state: &State<'_, ConfigState>) -> Result<Option<PathBuf>, String> {
@@ -17,6 +28,15 @@ fn extract_save_path(state: &State<'_, ConfigState>) -> Result<Option<PathBuf>,
     Ok(save_path.map(PathBuf::from))
 }
 
+/// Extracts the backup settings so blocking work does not need the config lock.
+fn extract_backup_settings(state: &State<'_, ConfigState>) -> Result<BackupSettings, String> {
+    Ok(state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock config: {}", e))?
+        .backup_settings())
+}
+
 /// Runs blocking work on the blocking thread pool and surfaces join errors.
 async fn run_blocking<T, F>(task: F) -> Result<T, String>
 where
@@ -28,125 +48,1331 @@ where
         .map_err(|e| format!("Blocking task join error: {}", e))?
 }
 
-/// Verifies that a backup path is valid and within the allowed backup directory.
-fn verify_backup_path(save_path: &Path, backup_path: &Path) -> Result<PathBuf, String> {
-    let backup_root = save_path.join(".backups");
-    let canonical_target = backup_path
-        .canonicalize()
-        .map_err(|_| "Invalid backup path".to_string())?;
-    let canonical_root = backup_root
-        .canonicalize()
-        .map_err(|_| "Backup directory not found".to_string())?;
+/// Verifies that a backup path is valid and within the allowed backup directory.
+fn verify_backup_path(
+    save_path: &Path,
+    backup_path: &Path,
+    settings: &BackupSettings,
+) -> Result<PathBuf, String> {
+    verify_path_in_backup_root(&backup::backup_root_for(save_path, settings), backup_path)
+}
+
+/// Verifies that `backup_path` resolves to a location inside `backup_root`.
+fn verify_path_in_backup_root(backup_root: &Path, backup_path: &Path) -> Result<PathBuf, String> {
+    let canonical_target = backup_path
+        .canonicalize()
+        .map_err(|_| "Invalid backup path".to_string())?;
+    let canonical_root = backup_root
+        .canonicalize()
+        .map_err(|_| "Backup directory not found".to_string())?;
+
+    if !canonical_target.starts_with(&canonical_root) {
+        return Err("Security violation: Path is outside the backup directory".to_string());
+    }
+
+    Ok(canonical_target)
+}
+
+/// Verifies that a restore target is the configured save directory when strict mode is on.
+///
+/// With `restrict` off any target is accepted. Otherwise the target and the configured
+/// save path are both normalized to directories and must resolve to the same folder.
+fn verify_restore_target(
+    target_dir: &Path,
+    save_path: Option<&Path>,
+    restrict: bool,
+) -> Result<(), String> {
+    if !restrict {
+        return Ok(());
+    }
+    let save_path = save_path.ok_or_else(|| "No save path configured".to_string())?;
+    let save_dir = crate::filename_utils::normalize_to_directory(save_path)?;
+    let canonical_save = save_dir
+        .canonicalize()
+        .map_err(|_| "Configured save directory not found".to_string())?;
+    let canonical_target = target_dir
+        .canonicalize()
+        .map_err(|_| "Invalid target path".to_string())?;
+
+    if canonical_target != canonical_save {
+        return Err(
+            "Restore target must be the configured save directory while restore target restriction is enabled"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Checks a restore target against the configured save directory and restriction setting.
+fn check_restore_target(state: &State<'_, ConfigState>, target_dir: &Path) -> Result<(), String> {
+    let (save_path, restrict) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (
+            config.save_path.as_deref().map(PathBuf::from),
+            config.restrict_restore_target,
+        )
+    };
+    verify_restore_target(target_dir, save_path.as_deref(), restrict)
+}
+
+/// The request `restore_backup_command` redeems its confirmation token against.
+fn backup_restore_request(backup: &Path, target_dir: &Path) -> RestoreRequest {
+    RestoreRequest::Backup {
+        backup_path: backup.to_path_buf(),
+        target_dir: target_dir.to_path_buf(),
+    }
+}
+
+/// The request `restore_backup_to_slot_command` redeems its confirmation token against.
+fn slot_restore_request(backup: &Path, target_dir: &Path, game_number: u32) -> RestoreRequest {
+    RestoreRequest::BackupToSlot {
+        backup_path: backup.to_path_buf(),
+        target_dir: target_dir.to_path_buf(),
+        game_number,
+    }
+}
+
+/// Tauri command to list available backups for the configured save path.
+///
+/// # Arguments
+///
+/// * `game_number` - When set, only backups of this game slot are read and returned.
+/// * `include_metadata` - When `true`, each backup's save is parsed for in-game details.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backups_command(
+    state: State<'_, ConfigState>,
+    game_number: Option<u32>,
+    include_metadata: Option<bool>,
+) -> Result<Vec<BackupInfo>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || {
+            let mut backups = backup::get_backups(&path, false, game_number, &settings)?;
+            if include_metadata.unwrap_or(false) {
+                backup::attach_save_metadata(&mut backups);
+            }
+            Ok(backups)
+        })
+        .await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to list backups progressively for large save directories.
+///
+/// Emits a `backup-discovered` event per backup as it is parsed, then `scan-complete`
+/// with the total, which is also returned.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn scan_backups_streaming_command(
+    app: tauri::AppHandle,
+    state: State<'_, ConfigState>,
+) -> Result<usize, String> {
+    let Some(path) = extract_save_path(&state)? else {
+        emit_scan_complete(&app, 0);
+        return Ok(0);
+    };
+    let settings = extract_backup_settings(&state)?;
+    let total = run_blocking({
+        let app = app.clone();
+        move || {
+            backup::scan_backups_streaming(&path, false, None, &settings, |info| {
+                if let Err(e) = app.emit("backup-discovered", info) {
+                    log::error!("Failed to emit backup-discovered event: {}", e);
+                }
+            })
+        }
+    })
+    .await?;
+    emit_scan_complete(&app, total);
+    Ok(total)
+}
+
+/// Emits the `scan-complete` event that closes a streaming backup scan.
+fn emit_scan_complete(app: &tauri::AppHandle, total: usize) {
+    if let Err(e) = app.emit("scan-complete", total) {
+        log::error!("Failed to emit scan-complete event: {}", e);
+    }
+}
+
+/// Tauri command to get when the watcher last created a backup, as RFC 3339.
+///
+/// Returns `None` until the watcher has backed something up since the app started.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_last_backup_time_command(
+    watcher: State<'_, FileWatcher>,
+) -> Result<Option<String>, String> {
+    Ok(watcher
+        .last_backup_time()
+        .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339()))
+}
+
+/// Tauri command to pause auto-backups while keeping the watch alive.
+///
+/// Save changes made while paused are queued and backed up after resuming.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn pause_watcher_command(
+    app: tauri::AppHandle,
+    watcher: State<'_, FileWatcher>,
+) -> Result<(), String> {
+    watcher.set_paused(true);
+    crate::tray::set_watcher_status(&app, true);
+    Ok(())
+}
+
+/// Tauri command to resume auto-backups after a pause.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resume_watcher_command(
+    app: tauri::AppHandle,
+    watcher: State<'_, FileWatcher>,
+) -> Result<(), String> {
+    watcher.set_paused(false);
+    crate::tray::set_watcher_status(&app, false);
+    Ok(())
+}
+
+/// Tauri command to list one page of backups with the total count and page size.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backups_page_with_size_command(
+    state: State<'_, ConfigState>,
+    offset: usize,
+    limit: usize,
+    game_number: Option<u32>,
+) -> Result<BackupPage, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::get_backups_page(&path, offset, limit, game_number, &settings))
+            .await
+    } else {
+        Ok(BackupPage {
+            backups: Vec::new(),
+            total: 0,
+            page_size_bytes: 0,
+        })
+    }
+}
+
+/// Tauri command to preview a restore and get the token that confirms it.
+///
+/// The token must be passed to `restore_backup_command` within a minute.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prepare_restore_command(
+    state: State<'_, ConfigState>,
+    pending: State<'_, PendingRestores>,
+    backup_path: String,
+    target_path: String,
+) -> Result<PreparedRestore, String> {
+    let backup = PathBuf::from(backup_path);
+    let target = PathBuf::from(target_path);
+
+    let target_dir = crate::filename_utils::normalize_to_directory(&target)
+        .map_err(|_| "Invalid target path".to_string())?;
+    check_restore_target(&state, &target_dir)?;
+
+    let (snapshot_before, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (config.snapshot_before_restore, config.backup_settings())
+    };
+    let pending = pending.inner().clone();
+    run_blocking(move || {
+        backup::prepare_restore(&pending, &backup, &target_dir, snapshot_before, &settings)
+    })
+    .await
+}
+
+/// Tauri command to restore a specific backup to a target location.
+///
+/// Requires the token from `prepare_restore_command` for the same backup and target.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn restore_backup_command(
+    state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    cancel_token: State<'_, RestoreCancelToken>,
+    pending: State<'_, PendingRestores>,
+    backup_path: String,
+    target_path: String,
+    confirm_token: String,
+) -> Result<(), String> {
+    let backup = PathBuf::from(backup_path);
+    let target = PathBuf::from(target_path);
+
+    let target_dir = crate::filename_utils::normalize_to_directory(&target)
+        .map_err(|_| "Invalid target path".to_string())?;
+    check_restore_target(&state, &target_dir)?;
+    pending.redeem(
+        &confirm_token,
+        &backup_restore_request(&backup, &target_dir),
+    )?;
+    let (snapshot_before, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (config.snapshot_before_restore, config.backup_settings())
+    };
+
+    let token = cancel_token.inner().clone();
+    token.reset();
+
+    // Keep the watcher from backing up the files the restore is about to write.
+    watcher.begin_restore();
+    let result = run_blocking(move || {
+        backup::restore_backup_cancellable(&backup, &target_dir, &token, snapshot_before, &settings)
+    })
+    .await;
+    watcher.end_restore();
+    result
+}
+
+/// Tauri command to get the token that confirms restoring a backup into another slot.
+///
+/// The token must be passed to `restore_backup_to_slot_command` within a minute.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prepare_restore_to_slot_command(
+    state: State<'_, ConfigState>,
+    pending: State<'_, PendingRestores>,
+    backup_path: String,
+    target_path: String,
+    target_game_number: u32,
+) -> Result<RestoreConfirmation, String> {
+    let backup = PathBuf::from(backup_path);
+    let target_dir = crate::filename_utils::normalize_to_directory(Path::new(&target_path))
+        .map_err(|_| "Invalid target path".to_string())?;
+    check_restore_target(&state, &target_dir)?;
+    let pending = pending.inner().clone();
+
+    run_blocking(move || {
+        backup::prepare_restore_to_slot(&pending, &backup, &target_dir, target_game_number)
+    })
+    .await
+}
+
+/// Tauri command to restore a backup into a different game slot.
+///
+/// Requires the token from `prepare_restore_to_slot_command` for the same backup,
+/// target and slot.
+///
+/// # Arguments
+///
+/// * `target_game_number` - Zero-based slot that receives the restored files.
+/// * `overwrite` - Replace an existing save in the target slot instead of failing.
+#[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
+pub async fn restore_backup_to_slot_command(
+    state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    pending: State<'_, PendingRestores>,
+    backup_path: String,
+    target_path: String,
+    target_game_number: u32,
+    overwrite: bool,
+    confirm_token: String,
+) -> Result<(), String> {
+    let backup = PathBuf::from(backup_path);
+    let target_dir = crate::filename_utils::normalize_to_directory(Path::new(&target_path))
+        .map_err(|_| "Invalid target path".to_string())?;
+    check_restore_target(&state, &target_dir)?;
+    pending.redeem(
+        &confirm_token,
+        &slot_restore_request(&backup, &target_dir, target_game_number),
+    )?;
+    let settings = extract_backup_settings(&state)?;
+
+    watcher.begin_restore();
+    let result = run_blocking(move || {
+        backup::restore_backup_to_slot(
+            &backup,
+            &target_dir,
+            target_game_number,
+            overwrite,
+            &settings,
+        )
+    })
+    .await;
+    watcher.end_restore();
+    result
+}
+
+/// Tauri command to check whether a restore target would be accepted.
+///
+/// Returns an error describing the rejection when restore targets are restricted
+/// to the configured save directory and the target is elsewhere.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn validate_restore_target_command(
+    state: State<'_, ConfigState>,
+    target_path: String,
+) -> Result<(), String> {
+    let target_dir = crate::filename_utils::normalize_to_directory(Path::new(&target_path))
+        .map_err(|_| "Invalid target path".to_string())?;
+    check_restore_target(&state, &target_dir)
+}
+
+/// Tauri command to cancel the in-progress restore before its files are swapped in.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_restore_command(
+    cancel_token: State<'_, RestoreCancelToken>,
+) -> Result<(), String> {
+    cancel_token.cancel();
+    Ok(())
+}
+
+/// Tauri command to preview the full impact of restoring a backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn plan_restore_full_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+    target_path: String,
+) -> Result<FullRestorePlan, String> {
+    let backup = PathBuf::from(backup_path);
+    let target = PathBuf::from(target_path);
+
+    let target_dir = crate::filename_utils::normalize_to_directory(&target)
+        .map_err(|_| "Invalid target path".to_string())?;
+    let (snapshot_before, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (config.snapshot_before_restore, config.backup_settings())
+    };
+
+    run_blocking(move || {
+        backup::plan_restore_full(&backup, &target_dir, snapshot_before, &settings)
+    })
+    .await
+}
+
+/// Tauri command to toggle the lock status of a backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn toggle_backup_lock_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+    locked: bool,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let path = PathBuf::from(&backup_path);
+
+    let verified_path = verify_backup_path(&save_path, &path, &settings)?;
+
+    run_blocking(move || backup::set_backup_lock(&verified_path, locked)).await
+}
+
+/// Tauri command to archive a backup or clear its archived mark.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_backup_archived_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+    archived: bool,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let path = PathBuf::from(&backup_path);
+
+    let verified_path = verify_backup_path(&save_path, &path, &settings)?;
+
+    run_blocking(move || backup::set_backup_archived(&verified_path, archived)).await
+}
+
+/// Tauri command to set or update a note for a backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_backup_note_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+    note: Option<String>,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::set_backup_note(&save_path, &backup_filename, note, &settings))
+        .await
+}
+
+/// Tauri command to replace the tags of a backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_backup_tags_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+    tags: Vec<String>,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::set_backup_tags(&save_path, &backup_filename, tags, &settings))
+        .await
+}
+
+/// Tauri command to mark or unmark a backup as a favorite, returning the new state.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn toggle_backup_favorite_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+) -> Result<bool, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::toggle_backup_favorite(&save_path, &backup_filename, &settings))
+        .await
+}
+
+/// Tauri command to add a tag to several backups at once, returning how many were updated.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_tag_batch_command(
+    state: State<'_, ConfigState>,
+    backup_filenames: Vec<String>,
+    tag: String,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::add_tag_batch(&save_path, &backup_filenames, tag, &settings)).await
+}
+
+/// Tauri command to remove a tag from several backups at once, returning how many were updated.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_tag_batch_command(
+    state: State<'_, ConfigState>,
+    backup_filenames: Vec<String>,
+    tag: String,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::remove_tag_batch(&save_path, &backup_filenames, tag, &settings))
+        .await
+}
+
+/// Tauri command to convert a backup to compressed storage in place.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn compress_backup_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::compress_backup(&verified_path, &settings)).await
+}
+
+/// Tauri command to convert a compressed backup back to raw storage in place.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn decompress_backup_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::decompress_backup(&verified_path, &settings)).await
+}
+
+/// Tauri command to show a backup folder in the system file manager.
+///
+/// On Windows the folder is selected in Explorer; on other platforms it is opened.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn reveal_backup_in_explorer_command(
+    app: tauri::AppHandle,
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let path = PathBuf::from(&backup_path);
+    if !path.is_dir() {
+        return Err("Backup folder no longer exists".to_string());
+    }
+    verify_backup_path(&save_path, &path, &settings)?;
+
+    // The verified path is canonical (`\\?\` prefixed on Windows), which Explorer
+    // does not accept, so the checked original path is opened instead.
+    let opener = app.opener();
+    let opened = if cfg!(target_os = "windows") {
+        opener.reveal_item_in_dir(&path)
+    } else {
+        opener.open_path(path.to_string_lossy(), None::<&str>)
+    };
+    opened.map_err(|e| format!("Failed to open backup folder: {}", e))
+}
+
+/// Tauri command to get a backup's stable unique ID, assigning one to legacy backups.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backup_id_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<String, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || ensure_manifest(&verified_path).map(|m| m.id)).await
+}
+
+/// Tauri command to read the note, lock state, tags, and file details of one backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backup_details_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<BackupDetails, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::get_backup_details(&save_path, &verified_path, &settings)).await
+}
+
+/// Tauri command to read in-game details from a backup's main save, best effort.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn read_save_metadata_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<SaveMetadata, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::read_save_metadata(&verified_path)).await
+}
+
+/// Tauri command to check a backup's main save against its stored hash.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn verify_backup_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<VerifyStatus, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::verify_backup(&verified_path)).await
+}
+
+/// Tauri command to check whether two backups of the same slot hold the same save.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn compare_backups_command(
+    state: State<'_, ConfigState>,
+    path_a: String,
+    path_b: String,
+) -> Result<BackupComparison, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_a = verify_backup_path(&save_path, Path::new(&path_a), &settings)?;
+    let verified_b = verify_backup_path(&save_path, Path::new(&path_b), &settings)?;
+
+    run_blocking(move || backup::compare_backups(&verified_a, &verified_b)).await
+}
+
+/// Tauri command to verify every backup and list the ones that failed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn verify_all_backups_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<BackupVerification>, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::verify_all_backups(&save_path, &settings)).await
+}
+
+/// Tauri command to list backups whose folder timestamp disagrees with their file's mtime.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn audit_timestamp_consistency_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<TimestampAudit>, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::audit_timestamp_consistency(&save_path, &settings)).await
+}
+
+/// Tauri command to report how much disk space the backups use.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backup_storage_stats_command(
+    state: State<'_, ConfigState>,
+) -> Result<BackupStorageStats, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::get_backup_storage_stats(&path, &settings)).await
+    } else {
+        Ok(BackupStorageStats::default())
+    }
+}
+
+/// Tauri command to list deleted backups still held in the trash.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_trash_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<TrashedBackup>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::list_trash(&path, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to undo a delete by moving a trashed backup back into the backup list.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn restore_from_trash_command(
+    state: State<'_, ConfigState>,
+    trash_name: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::restore_from_trash(&save_path, &trash_name, &settings).map(|_| ()))
+        .await
+}
+
+/// Tauri command to label a backup folder, or remove its label when `label` is empty.
+///
+/// Returns the new folder name.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rename_backup_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+    label: Option<String>,
+) -> Result<String, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::rename_backup(&save_path, &backup_filename, label, &settings))
+        .await
+}
+
+/// Tauri command to permanently delete everything in the trash.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn empty_trash_command(state: State<'_, ConfigState>) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::empty_trash(&save_path, &settings)).await
+}
+
+/// Tauri command to move a suspect backup into quarantine instead of deleting it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn quarantine_backup_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+    reason: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, Path::new(&backup_path), &settings)?;
+
+    run_blocking(move || backup::quarantine_backup(&verified_path, &reason).map(|_| ())).await
+}
+
+/// Tauri command to list quarantined backups.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_quarantined_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<QuarantinedBackup>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::list_quarantined(&path, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to move a quarantined backup back into the backup list.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn release_from_quarantine_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || {
+        backup::release_from_quarantine(&save_path, &backup_filename, &settings).map(|_| ())
+    })
+    .await
+}
+
+/// Tauri command to compute a single digest covering every backup of a game slot.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn aggregate_backups_hash_command(
+    state: State<'_, ConfigState>,
+    game_number: u32,
+) -> Result<String, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::aggregate_backups_hash(&save_path, game_number, &settings)).await
+}
+
+/// Tauri command to delete a specific backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_backup_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let path = PathBuf::from(&backup_path);
+    let force = force.unwrap_or(false);
+
+    let verified_path = verify_backup_path(&save_path, &path, &settings)?;
+
+    run_blocking(move || backup::delete_backup_folder(&verified_path, force, &settings)).await
+}
+
+/// Tauri command to list the backups a batch delete with the same arguments would remove.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_batch_delete_command(
+    state: State<'_, ConfigState>,
+    game_numbers: Vec<u32>,
+    keep_latest: bool,
+    delete_locked: bool,
+    force: Option<bool>,
+) -> Result<Vec<BackupInfo>, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let force = force.unwrap_or(false);
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || {
+        backup::preview_delete_backups_batch(
+            &save_path,
+            &game_numbers,
+            keep_latest,
+            delete_locked,
+            force,
+            &settings,
+        )
+    })
+    .await
+}
+
+/// Tauri command to batch delete backups.
+///
+/// Emits `batch-delete-progress` with `(deleted_so_far, total_candidates)` after each folder.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn batch_delete_backups_command(
+    app: tauri::AppHandle,
+    state: State<'_, ConfigState>,
+    game_numbers: Vec<u32>,
+    keep_latest: bool,
+    delete_locked: bool,
+    force: Option<bool>,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let force = force.unwrap_or(false);
+    let settings = extract_backup_settings(&state)?;
+
+    let on_progress: backup::BatchDeleteProgress = Arc::new(move |deleted, total| {
+        if let Err(e) = app.emit("batch-delete-progress", (deleted, total)) {
+            log::error!("Failed to emit batch-delete-progress event: {}", e);
+        }
+    });
+
+    run_blocking(move || {
+        backup::delete_backups_batch(
+            &save_path,
+            &game_numbers,
+            keep_latest,
+            delete_locked,
+            force,
+            &settings,
+            Some(on_progress),
+        )
+    })
+    .await
+}
+
+/// Tauri command to list `.locked` markers left in folders that are not valid backups.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_dangling_locks_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<String>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::find_dangling_locks(&path, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to delete dangling `.locked` markers.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_dangling_locks_command(state: State<'_, ConfigState>) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
 
-    if !canonical_target.starts_with(&canonical_root) {
-        return Err("Security violation: Path is outside the backup directory".to_string());
-    }
+    run_blocking(move || backup::remove_dangling_locks(&save_path, &settings)).await
+}
 
-    Ok(canonical_target)
+/// Tauri command to drop index entries for backup folders that no longer exist.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn compact_index_command(state: State<'_, ConfigState>) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::compact_index(&save_path, &settings)).await
 }
 
-/// Tauri command to list available backups for the configured save path.
+/// Tauri command to list backup folders that are missing their main save file.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_backups_command(state: State<'_, ConfigState>) -> Result<Vec<BackupInfo>, String> {
+pub async fn find_incomplete_backups_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<String>, String> {
+    let settings = extract_backup_settings(&state)?;
     if let Some(path) = extract_save_path(&state)? {
-        run_blocking(move || backup::get_backups(&path, false, None)).await
+        run_blocking(move || backup::find_incomplete_backups(&path, &settings)).await
     } else {
         Ok(Vec::new())
     }
 }
 
-/// Tauri command to restore a specific backup to a target location.
+/// Tauri command to delete backup folders that are missing their main save file.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn restore_backup_command(
-    backup_path: String,
-    target_path: String,
-) -> Result<(), String> {
-    let backup = PathBuf::from(backup_path);
-    let target = PathBuf::from(target_path);
+pub async fn remove_incomplete_backups_command(
+    state: State<'_, ConfigState>,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
 
-    let target_dir = crate::filename_utils::normalize_to_directory(&target)
-        .map_err(|_| "Invalid target path".to_string())?;
+    run_blocking(move || backup::remove_incomplete_backups(&save_path, &settings)).await
+}
 
-    run_blocking(move || backup::restore_backup(&backup, &target_dir)).await
+/// Tauri command to back up every slot under a single labeled session.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_session_snapshot_command(
+    state: State<'_, ConfigState>,
+    label: String,
+) -> Result<Vec<String>, String> {
+    let (save_path, limit, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (
+            config.save_path.clone().map(PathBuf::from),
+            config.effective_backup_limit(),
+            config.backup_settings(),
+        )
+    };
+    let save_path = save_path.ok_or_else(|| "Save path not configured".to_string())?;
+
+    run_blocking(move || backup::create_session_snapshot(&save_path, &label, limit, &settings))
+        .await
 }
 
-/// Tauri command to toggle the lock status of a backup.
+/// Tauri command to back up one slot while emitting `single-backup-progress` events.
+///
+/// Events are emitted at most once per whole percent per phase to avoid flooding the UI.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn toggle_backup_lock_command(
+pub async fn perform_backup_with_progress_command(
+    app: tauri::AppHandle,
     state: State<'_, ConfigState>,
-    backup_path: String,
-    locked: bool,
-) -> Result<(), String> {
+    game_number: u32,
+) -> Result<Option<String>, String> {
+    let (save_path, limit, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (
+            config.save_path.clone().map(PathBuf::from),
+            config.effective_backup_limit(),
+            config.backup_settings(),
+        )
+    };
+    let save_path = save_path.ok_or_else(|| "Save path not configured".to_string())?;
+
+    run_blocking(move || {
+        let mut last_emitted = None;
+        backup::perform_backup_with_progress(
+            &save_path,
+            game_number,
+            limit,
+            &settings,
+            |progress| {
+                let percent = (progress.bytes_processed * 100)
+                    .checked_div(progress.total_bytes)
+                    .unwrap_or(100);
+                if last_emitted == Some((progress.phase, percent)) {
+                    return;
+                }
+                last_emitted = Some((progress.phase, percent));
+                if let Err(e) = app.emit("single-backup-progress", progress) {
+                    log::error!("Failed to emit single-backup-progress event: {}", e);
+                }
+            },
+        )
+        .map(|created| created.map(|path| path.to_string_lossy().to_string()))
+    })
+    .await
+}
+
+/// Tauri command to get the token that confirms restoring a session.
+///
+/// The token must be passed to `restore_session_command` within a minute.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prepare_session_restore_command(
+    state: State<'_, ConfigState>,
+    pending: State<'_, PendingRestores>,
+    label: String,
+) -> Result<RestoreConfirmation, String> {
     let save_path =
         extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
-    let path = PathBuf::from(&backup_path);
-
-    let verified_path = verify_backup_path(&save_path, &path)?;
+    let settings = extract_backup_settings(&state)?;
+    let pending = pending.inner().clone();
 
-    run_blocking(move || backup::set_backup_lock(&verified_path, locked)).await
+    run_blocking(move || backup::prepare_session_restore(&pending, &save_path, &label, &settings))
+        .await
 }
 
-/// Tauri command to set or update a note for a backup.
+/// Tauri command to restore every backup recorded under a session label.
+///
+/// Requires the token from `prepare_session_restore_command` for the same label.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn set_backup_note_command(
+pub async fn restore_session_command(
     state: State<'_, ConfigState>,
-    backup_filename: String,
-    note: Option<String>,
+    watcher: State<'_, FileWatcher>,
+    pending: State<'_, PendingRestores>,
+    label: String,
+    confirm_token: String,
 ) -> Result<(), String> {
     let save_path =
         extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    pending.redeem(
+        &confirm_token,
+        &backup::session_restore_request(&save_path, &label)?,
+    )?;
+    let settings = extract_backup_settings(&state)?;
 
-    run_blocking(move || backup::set_backup_note(&save_path, &backup_filename, note)).await
+    // Keep the watcher from backing up the files the restore is about to write.
+    watcher.begin_restore();
+    let result = run_blocking(move || backup::restore_session(&save_path, &label, &settings)).await;
+    watcher.end_restore();
+    result
 }
 
-/// Tauri command to delete a specific backup.
+/// Tauri command to check for a backward system clock jump relative to the newest backup.
+///
+/// Emits a `clock-skew-warning` event carrying the warning when skew is detected.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn delete_backup_command(
+pub async fn check_clock_skew_command(
+    app: tauri::AppHandle,
     state: State<'_, ConfigState>,
-    backup_path: String,
-) -> Result<(), String> {
+) -> Result<Option<ClockSkewWarning>, String> {
+    let Some(save_path) = extract_save_path(&state)? else {
+        return Ok(None);
+    };
+    let settings = extract_backup_settings(&state)?;
+
+    let warning = run_blocking(move || backup::check_clock_skew(&save_path, &settings)).await?;
+    if let Some(w) = &warning {
+        if let Err(e) = app.emit("clock-skew-warning", w.clone()) {
+            log::error!("Failed to emit clock-skew-warning event: {}", e);
+        }
+    }
+    Ok(warning)
+}
+
+/// Tauri command to rebuild the dedup index fast path from existing backups.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prime_index_command(state: State<'_, ConfigState>) -> Result<usize, String> {
     let save_path =
         extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
-    let path = PathBuf::from(&backup_path);
+    let settings = extract_backup_settings(&state)?;
 
-    let verified_path = verify_backup_path(&save_path, &path)?;
+    run_blocking(move || backup::prime_index(&save_path, &settings)).await
+}
 
-    run_blocking(move || {
-        let folder_name = verified_path
-            .file_name()
-            .map(|n| n.to_string_lossy().into_owned())
-            .unwrap_or_default();
+/// Tauri command to take a checkpoint backup even when the save is unchanged.
+///
+/// Backs up `game_number`, or every slot with a save when it is omitted, and
+/// returns the folder names created.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn trigger_manual_backup(
+    app: tauri::AppHandle,
+    state: State<'_, ConfigState>,
+    game_number: Option<u32>,
+) -> Result<Vec<String>, String> {
+    let (save_path, limit, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (
+            config.save_path.clone().map(PathBuf::from),
+            config.effective_backup_limit(),
+            config.backup_settings(),
+        )
+    };
+    let save_path = save_path.ok_or_else(|| "Save path not configured".to_string())?;
+
+    let created = run_blocking(move || {
+        backup::trigger_manual_backup(&save_path, game_number, limit, &settings)
+    })
+    .await?;
+    if !created.is_empty() {
+        if let Err(e) = app.emit("backups-updated", ()) {
+            log::error!("Failed to emit backups-updated event: {}", e);
+        }
+    }
+    Ok(created)
+}
 
-        backup::delete_backup_folder(&verified_path)?;
+/// Tauri command to back up every slot with a save right now.
+///
+/// Works whether or not the watcher is running, and reports which slots were backed
+/// up, skipped, or failed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn backup_all_now_command(
+    app: tauri::AppHandle,
+    state: State<'_, ConfigState>,
+) -> Result<BackupAllSummary, String> {
+    let (save_path, limit, settings) = {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        (
+            config.save_path.clone().map(PathBuf::from),
+            config.effective_backup_limit(),
+            config.backup_settings(),
+        )
+    };
+    let save_path = save_path.ok_or_else(|| "Save path not configured".to_string())?;
 
-        if let Some(mut store) = BackupStore::load_if_exists(&save_path)? {
-            store.index.prune_deleted(&folder_name);
-            store.save()?;
+    let summary =
+        run_blocking(move || backup::backup_all_now(&save_path, limit, &settings)).await?;
+    if !summary.created.is_empty() {
+        if let Err(e) = app.emit("backups-updated", ()) {
+            log::error!("Failed to emit backups-updated event: {}", e);
         }
+    }
+    Ok(summary)
+}
 
-        Ok(())
-    })
-    .await
+/// Tauri command to discard the index and rebuild it from the backup folders on disk.
+///
+/// Returns the number of slots whose dedup entry was restored.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rebuild_index_command(state: State<'_, ConfigState>) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::rebuild_index(&save_path, &settings)).await
 }
 
-/// Tauri command to batch delete backups.
+/// Tauri command to force new backups of identical saves for the next `duration_seconds`.
+///
+/// Applies to both watcher and manual backups. Passing 0 re-enables dedup immediately.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn batch_delete_backups_command(
+pub fn set_dedup_disabled_command(
     state: State<'_, ConfigState>,
-    game_numbers: Vec<u32>,
-    keep_latest: bool,
-    delete_locked: bool,
+    watcher: State<'_, FileWatcher>,
+    duration_seconds: u64,
+) -> Result<(), String> {
+    let settings = {
+        let mut config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        let mut settings = config.backup_settings();
+        settings.disable_dedup_for(Duration::from_secs(duration_seconds));
+        config.dedup_disabled_until = settings.dedup_disabled_until;
+        settings
+    };
+    watcher.set_backup_settings(settings);
+    log::info!("Dedup disabled for {} second(s)", duration_seconds);
+    Ok(())
+}
+
+/// Tauri command to list backups without a lock, note, or session, as cleanup suggestions.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_unannotated_backups_command(
+    state: State<'_, ConfigState>,
+    game_number: Option<u32>,
+) -> Result<Vec<BackupInfo>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::get_unannotated_backups(&path, game_number, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to move mirrored and overridden backups from an old save path's key folder to a new one.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remap_central_key_command(
+    state: State<'_, ConfigState>,
+    old_path: String,
+    new_path: String,
 ) -> Result<usize, String> {
+    let old_dir = crate::filename_utils::normalize_to_directory(Path::new(&old_path))?;
+    let new_dir = crate::filename_utils::normalize_to_directory(Path::new(&new_path))?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::remap_central_key(&old_dir, &new_dir, &settings)).await
+}
+
+/// Tauri command to compare the backups of two save directories by slot and content.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_backup_sets_command(
+    state: State<'_, ConfigState>,
+    dir_a: String,
+    dir_b: String,
+) -> Result<BackupSetDiff, String> {
+    let settings = extract_backup_settings(&state)?;
+    let dir_a = crate::filename_utils::normalize_to_directory(Path::new(&dir_a))?;
+    let dir_b = crate::filename_utils::normalize_to_directory(Path::new(&dir_b))?;
+
+    run_blocking(move || backup::diff_backup_sets(&dir_a, &dir_b, &settings)).await
+}
+
+/// Tauri command to count backups per local calendar day, optionally for one slot.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn backup_activity_histogram_command(
+    state: State<'_, ConfigState>,
+    game_number: Option<u32>,
+) -> Result<Vec<DayCount>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::backup_activity_histogram(&path, game_number, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to get the oldest and newest backup timestamps of each slot.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_backup_time_bounds_command(
+    state: State<'_, ConfigState>,
+) -> Result<Vec<TimeBounds>, String> {
+    let settings = extract_backup_settings(&state)?;
+    if let Some(path) = extract_save_path(&state)? {
+        run_blocking(move || backup::get_backup_time_bounds(&path, &settings)).await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Tauri command to suggest which duplicate backups of a slot can be deleted.
+///
+/// Only reports the suggestion; deleting the folders is a separate step.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_retention_command(
+    state: State<'_, ConfigState>,
+    game_number: u32,
+) -> Result<RetentionSuggestion, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::suggest_retention(&save_path, game_number, &settings)).await
+}
+
+/// Tauri command to rank a slot's backups by similarity to its live save.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rank_backups_by_similarity_command(
+    state: State<'_, ConfigState>,
+    game_number: u32,
+) -> Result<Vec<RankedBackup>, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::rank_backups_by_similarity(&save_path, game_number, &settings))
+        .await
+}
+
+/// Tauri command to count the bytes that differ between a backup and the live save.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn byte_diff_count_command(
+    state: State<'_, ConfigState>,
+    backup_filename: String,
+    game_number: u32,
+) -> Result<ByteDiff, String> {
     let save_path =
         extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
 
     run_blocking(move || {
-        backup::delete_backups_batch(&save_path, &game_numbers, keep_latest, delete_locked)
+        backup::byte_diff_count(&save_path, &backup_filename, game_number, &settings)
     })
     .await
 }
 
+/// Tauri command to measure how far the live save has diverged from a backup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_backup_against_current_command(
+    state: State<'_, ConfigState>,
+    backup_path: String,
+) -> Result<CurrentSaveDiff, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    let verified_path = verify_backup_path(&save_path, &PathBuf::from(&backup_path), &settings)?;
+
+    run_blocking(move || backup::diff_backup_against_current(&save_path, &verified_path)).await
+}
+
+/// Tauri command to import loose legacy save files from a folder as backups.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_legacy_saves_command(
+    state: State<'_, ConfigState>,
+    source_dir: String,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let source = PathBuf::from(source_dir);
+    let settings = extract_backup_settings(&state)?;
+
+    run_blocking(move || backup::import_legacy_saves(&source, &save_path, &settings)).await
+}
+
+/// How often a save directory that disappeared is checked for again.
+const SAVE_PATH_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Command to initialize the watcher from the frontend.
+///
+/// If the configured save path has been replaced by a file, auto-backup stays off,
+/// a `save-path-not-a-directory` event is emitted, and a `SavePathNotADirectory`
+/// error is returned. A save path relocated at startup is reported once through a
+/// `save-path-relocated` event.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn init_watcher(
     app: tauri::AppHandle,
@@ -158,12 +1384,46 @@ pub async fn init_watcher(
         }
     }
 
-    let config = state
+    {
+        let config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        start_configured_watcher(&app, &config)?;
+    }
+
+    let relocation = app
+        .state::<SavePathRelocationState>()
         .0
         .lock()
-        .map_err(|e| format!("Failed to lock config: {}", e))?;
+        .ok()
+        .and_then(|mut pending| pending.take());
+    if let Some(relocation) = relocation {
+        if let Err(e) = app.emit("save-path-relocated", relocation) {
+            log::error!("Failed to emit save-path-relocated event: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Starts the watcher on the configured save path with the app's event callbacks.
+///
+/// If the save directory later disappears, a `save-path-missing` event is emitted and
+/// the watcher is restarted once the directory is back.
+fn start_configured_watcher(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), String> {
     if let Some(path_str) = &config.save_path {
         let path = PathBuf::from(path_str);
+        if let Err(e) = backup::ensure_save_dir_is_directory(&path) {
+            log::error!("{}", e);
+            app.state::<FileWatcher>().stop();
+            if let Err(emit_err) = app.emit("save-path-not-a-directory", path_str.clone()) {
+                log::error!(
+                    "Failed to emit save-path-not-a-directory event: {}",
+                    emit_err
+                );
+            }
+            return Err(e);
+        }
         if path.exists() {
             let watcher = app.state::<FileWatcher>();
             let app_handle = app.clone();
@@ -171,13 +1431,207 @@ pub async fn init_watcher(
                 if let Err(e) = app_handle.emit("backups-updated", ()) {
                     log::error!("Failed to emit backups-updated event: {}", e);
                 }
+                if let Some(time) = app_handle.state::<FileWatcher>().last_backup_time() {
+                    crate::tray::set_last_backup_time(&app_handle, time);
+                }
+                crate::notifications::notify(
+                    &app_handle,
+                    NotificationLevel::Verbose,
+                    "Backup created",
+                    "Your save was backed up.",
+                );
+            });
+            let activity_handle = app.clone();
+            watcher.set_on_activity(Some(Arc::new(move |activity| {
+                if let Err(e) = activity_handle.emit("watcher-activity", activity) {
+                    log::error!("Failed to emit watcher-activity event: {}", e);
+                }
+            })));
+            let missing_handle = app.clone();
+            let configured_path = path_str.clone();
+            let on_path_missing: PathMissingCallback = Arc::new(move |_| {
+                if let Err(e) = missing_handle.emit("save-path-missing", configured_path.clone()) {
+                    log::error!("Failed to emit save-path-missing event: {}", e);
+                }
+                let handle = missing_handle.clone();
+                let configured_path = configured_path.clone();
+                std::thread::spawn(move || restart_watcher_when_present(handle, configured_path));
             });
-            watcher.start(path, config.max_backups_per_game, Some(on_backup))?;
+            watcher.start(
+                path,
+                config.effective_backup_limit(),
+                Some(on_backup),
+                Some(on_path_missing),
+            )?;
+            if let Some(pid) = watcher.owner_conflict() {
+                if let Err(e) = app.emit("backup-dir-conflict", pid) {
+                    log::error!("Failed to emit backup-dir-conflict event: {}", e);
+                }
+            }
         }
     }
     Ok(())
 }
 
+/// Waits for a save directory that disappeared to come back, then restarts the watcher.
+///
+/// Gives up once a different save path is configured, since changing the path starts
+/// its own watcher. Emits `save-path-restored` when the watcher is running again.
+fn restart_watcher_when_present(app: tauri::AppHandle, path_str: String) {
+    loop {
+        std::thread::sleep(SAVE_PATH_RETRY_INTERVAL);
+        let config_state = app.state::<ConfigState>();
+        let Ok(config) = config_state.0.lock() else {
+            return;
+        };
+        if config.save_path.as_deref() != Some(path_str.as_str()) {
+            return;
+        }
+        if !Path::new(&path_str).exists() {
+            continue;
+        }
+
+        log::info!(
+            "Save directory {} is back; restarting the watcher",
+            path_str
+        );
+        match start_configured_watcher(&app, &config) {
+            Ok(()) => {
+                if let Err(e) = app.emit("save-path-restored", path_str.clone()) {
+                    log::error!("Failed to emit save-path-restored event: {}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to restart the watcher: {}", e),
+        }
+        return;
+    }
+}
+
+/// Tauri command to write metadata of all backups to a JSON or CSV file.
+///
+/// Returns the number of backups written.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_backup_catalog_command(
+    state: State<'_, ConfigState>,
+    format: CatalogFormat,
+    output_path: String,
+) -> Result<usize, String> {
+    let save_path =
+        extract_save_path(&state)?.ok_or_else(|| "Save path not configured".to_string())?;
+    let settings = extract_backup_settings(&state)?;
+    if !crate::config::is_valid_path(&output_path) {
+        return Err("The output path must be a file in an existing directory.".to_string());
+    }
+    let output = PathBuf::from(output_path);
+
+    run_blocking(move || backup::export_backup_catalog(&save_path, format, &output, &settings))
+        .await
+}
+
+/// Tauri command to bundle the configuration and all backups into one archive.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_app_state_command(
+    state: State<'_, ConfigState>,
+    dest_path: String,
+) -> Result<usize, String> {
+    let config = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock config: {}", e))?
+        .clone();
+    let dest = PathBuf::from(dest_path);
+
+    run_blocking(move || crate::app_state::export_app_state(&config, &dest)).await
+}
+
+/// Tauri command to restore an exported app state into a save directory on this machine.
+///
+/// Applies the imported settings, then points the watcher at `target_save_dir`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_app_state_command(
+    state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    source_path: String,
+    target_save_dir: String,
+) -> Result<(), String> {
+    let source = PathBuf::from(source_path);
+    let target_dir = crate::filename_utils::normalize_to_directory(Path::new(&target_save_dir))?;
+    let import_dir = target_dir.clone();
+    let settings = extract_backup_settings(&state)?;
+    let imported =
+        run_blocking(move || crate::app_state::import_app_state(&source, &import_dir, &settings))
+            .await?;
+
+    {
+        let mut config = state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock config: {}", e))?;
+        let current_save_path = config.save_path.take();
+        *config = imported.clone();
+        config.save_path = current_save_path;
+    }
+    crate::config::apply_backup_settings(&imported, &watcher);
+    watcher.set_max_events_per_second(imported.max_events_per_second);
+    watcher.set_restore_suppression_seconds(imported.restore_suppression_seconds);
+    watcher.set_initial_scan_mode(imported.initial_scan_mode);
+    watcher.set_watch_recursive(imported.watch_recursive);
+    watcher.set_disabled_games(&imported.disabled_games);
+    watcher.set_periodic_backup_minutes(imported.periodic_backup_minutes);
+    watcher.set_params(crate::watcher::WatcherParams {
+        debounce_seconds: imported.debounce_seconds,
+        limit: imported.effective_backup_limit(),
+    });
+
+    let target_str = target_dir.to_string_lossy().to_string();
+    crate::config::replace_watcher_path(
+        &state,
+        &watcher,
+        target_dir,
+        target_str,
+        &crate::config::get_config_path(),
+    )
+}
+
+/// Tauri command to check whether auto-backup is working end to end.
+///
+/// Gathers the save path, save directory contents, watcher state, and existing
+/// backups into a single diagnosis with per-signal detail.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diagnose_auto_backup_command(
+    state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+) -> Result<AutoBackupDiagnosis, String> {
+    let settings = extract_backup_settings(&state)?;
+    let save_path = extract_save_path(&state)?;
+    let watcher_active = watcher.is_active();
+    let events_received = watcher.events_received();
+
+    run_blocking(move || {
+        let mut signals = AutoBackupSignals {
+            save_path_set: save_path.is_some(),
+            watcher_active,
+            events_received,
+            ..AutoBackupSignals::default()
+        };
+        if let Some(path) = save_path.filter(|p| p.is_dir()) {
+            signals.save_dir_exists = true;
+            signals.save_slots = backup::discover_save_slots(&path)?.len();
+            signals.backup_count = backup::get_backups(&path, false, None, &settings)?.len();
+        }
+        Ok(diagnose_auto_backup(&signals))
+    })
+    .await
+}
+
+/// Tauri command to report whether the app started in wrapper mode and what it launched.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_launch_mode_command(
+    launch_mode: State<'_, LaunchModeState>,
+) -> Result<LaunchMode, String> {
+    Ok(launch_mode.0.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,14 +1663,90 @@ mod tests {
 
         fs::create_dir_all(&game_backup).expect("failed to create mock backup dir");
 
-        assert!(verify_backup_path(&save_path, &game_backup).is_ok());
+        assert!(verify_backup_path(&save_path, &game_backup, &BackupSettings::default()).is_ok());
 
         let malicious = temp_dir.path().join("malicious.exe");
         fs::File::create(&malicious).expect("failed to create mock malicious file");
-        assert!(verify_backup_path(&save_path, &malicious).is_err());
+        assert!(verify_backup_path(&save_path, &malicious, &BackupSettings::default()).is_err());
 
         let other = save_path.join("unauthorized_file.txt");
         fs::File::create(&other).expect("failed to create mock unauthorized file");
-        assert!(verify_backup_path(&save_path, &other).is_err());
+        assert!(verify_backup_path(&save_path, &other, &BackupSettings::default()).is_err());
+
+        // With an override, only the external root is accepted.
+        let settings = BackupSettings {
+            backup_root_override: Some(temp_dir.path().join("external")),
+            ..BackupSettings::default()
+        };
+        let external_root = backup::backup_root_for(&save_path, &settings);
+        let external_backup = external_root.join("Game 1 - 2024-01-01");
+        fs::create_dir_all(&external_backup).expect("failed to create external backup dir");
+        assert!(verify_backup_path(&save_path, &external_backup, &settings).is_ok());
+        assert!(verify_backup_path(&save_path, &game_backup, &settings).is_err());
+    }
+
+    /// Verifies that each prepare step issues a token the matching restore command accepts.
+    #[test]
+    fn test_restore_commands_redeem_prepared_tokens() {
+        use std::fs;
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let save_dir = temp_dir.path();
+        fs::write(save_dir.join("gamesave_0.sav"), "backed up").expect("failed to write save");
+        backup::create_session_snapshot(save_dir, "run", 100, &BackupSettings::default())
+            .expect("failed to snapshot session");
+        let folder = backup::get_backups(save_dir, false, Some(0), &BackupSettings::default())
+            .expect("failed to list")[0]
+            .path
+            .clone();
+        let folder = Path::new(&folder);
+        let pending = PendingRestores::default();
+
+        let prepared =
+            backup::prepare_restore(&pending, folder, save_dir, true, &BackupSettings::default())
+                .expect("failed to prepare restore");
+        let request = backup_restore_request(folder, save_dir);
+        assert!(pending.redeem(&prepared.token, &request).is_ok());
+
+        let confirmation = backup::prepare_restore_to_slot(&pending, folder, save_dir, 2)
+            .expect("failed to prepare slot restore");
+        assert!(pending.redeem(&confirmation.token, &request).is_err());
+        let confirmation = backup::prepare_restore_to_slot(&pending, folder, save_dir, 2)
+            .expect("failed to prepare slot restore");
+        let request = slot_restore_request(folder, save_dir, 2);
+        assert!(pending.redeem(&confirmation.token, &request).is_ok());
+
+        let confirmation =
+            backup::prepare_session_restore(&pending, save_dir, "run", &BackupSettings::default())
+                .expect("failed to prepare session restore");
+        let request =
+            backup::session_restore_request(save_dir, "run").expect("failed to build request");
+        assert!(pending.redeem(&confirmation.token, &request).is_ok());
+    }
+
+    /// Verifies that restore targets are only restricted when strict mode is enabled.
+    #[test]
+    fn test_verify_restore_target_restriction() {
+        use std::fs;
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let save_dir = temp_dir.path().join("saves");
+        let other_dir = temp_dir.path().join("elsewhere");
+        fs::create_dir_all(&save_dir).expect("failed to create save dir");
+        fs::create_dir_all(&other_dir).expect("failed to create other dir");
+        let save_file = save_dir.join("gamesave_0.sav");
+        fs::File::create(&save_file).expect("failed to create mock save");
+
+        // Flexible mode accepts any target, even without a configured save path
+        assert!(verify_restore_target(&other_dir, Some(&save_dir), false).is_ok());
+        assert!(verify_restore_target(&other_dir, None, false).is_ok());
+
+        // Strict mode accepts the save directory, also when configured as a file path
+        assert!(verify_restore_target(&save_dir, Some(&save_dir), true).is_ok());
+        assert!(verify_restore_target(&save_dir, Some(&save_file), true).is_ok());
+        assert!(verify_restore_target(&save_dir.join("."), Some(&save_dir), true).is_ok());
+
+        // Strict mode rejects other directories and a missing save path
+        assert!(verify_restore_target(&other_dir, Some(&save_dir), true).is_err());
+        assert!(verify_restore_target(&save_dir.join(".backups"), Some(&save_dir), true).is_err());
+        assert!(verify_restore_target(&save_dir, None, true).is_err());
     }
 }
@@ -1,12 +1,16 @@
 // ITD ODD Save Manager by andromarces
 
-use crate::watcher::FileWatcher;
+use crate::backup::{BackupSettings, HashAlgorithm, NamingScheme};
+use crate::filename_utils::BackupNameFormat;
+use crate::notifications::{self, NotificationLevel};
+use crate::watcher::{FileWatcher, InitialScanMode, WatcherParams};
 use crate::MonitorInvalidator;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::Mutex;
+use std::time::Instant;
 use tauri::State;
 
 /// Configuration structure for the application.
@@ -20,9 +24,101 @@ pub struct AppConfig {
     /// Whether to automatically close the app when the game exits.
     #[serde(default)]
     pub auto_close: bool,
-    /// Maximum number of backups to keep per game.
+    /// Maximum number of backups to keep per game. Ignored while `unlimited_backups` is set.
     #[serde(default = "default_max_backups")]
     pub max_backups_per_game: usize,
+    /// Whether backups are kept without a per-game count limit.
+    #[serde(default)]
+    pub unlimited_backups: bool,
+    /// Age in days after which unlocked backups are deleted; `None` keeps them regardless of age.
+    #[serde(default)]
+    pub max_backup_age_days: Option<u32>,
+    /// Cap in bytes on the whole backup folder; `None` leaves its size unlimited.
+    #[serde(default)]
+    pub max_total_backup_bytes: Option<u64>,
+    /// Buffer size in KiB used when copying and hashing save files.
+    #[serde(default = "default_copy_buffer_kb")]
+    pub copy_buffer_kb: usize,
+    /// Maximum watcher event wakes handled per second before bursts are coalesced (0 = unlimited).
+    #[serde(default = "default_max_events_per_second")]
+    pub max_events_per_second: u32,
+    /// Quiet period in seconds after the last save change before backing up.
+    #[serde(default = "default_debounce_seconds")]
+    pub debounce_seconds: u64,
+    /// Seconds after a restore during which save changes are not backed up.
+    #[serde(default = "default_restore_suppression_seconds")]
+    pub restore_suppression_seconds: u64,
+    /// Whether the one-time "minimized to tray" notification has already been shown.
+    #[serde(default)]
+    pub tray_minimize_notified: bool,
+    /// Whether deletion paths must keep the newest backup of each slot.
+    #[serde(default = "default_never_delete_last_backup")]
+    pub never_delete_last_backup: bool,
+    /// Path to the game executable, used when launching through Steam fails.
+    #[serde(default)]
+    pub game_exe_path: Option<String>,
+    /// Part of the game's process name that auto-close watches for, matched
+    /// case-insensitively; `None` matches "intothedead".
+    #[serde(default)]
+    pub game_process_name: Option<String>,
+    /// Secondary directory that every new backup is also copied into.
+    #[serde(default)]
+    pub mirror_directory: Option<String>,
+    /// Directory that holds backups instead of each save directory's `.backups` folder.
+    #[serde(default)]
+    pub backup_root_override: Option<String>,
+    /// Whether new backup folders are named by timestamp or by a per-slot counter.
+    #[serde(default)]
+    pub naming_scheme: NamingScheme,
+    /// Timestamp format used in the names of new timestamp-named backup folders.
+    #[serde(default)]
+    pub backup_name_format: BackupNameFormat,
+    /// Algorithm used to hash saves for duplicate detection.
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// Backup attempts per slot after which a save is rehashed even when its size and
+    /// modification time match; `0` always trusts the metadata.
+    #[serde(default = "default_hash_recheck_interval")]
+    pub hash_recheck_interval: u32,
+    /// Days deleted backups stay in the trash before being removed for good; `0` keeps them.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Whether the live save is backed up before a restore overwrites it.
+    #[serde(default = "default_snapshot_before_restore")]
+    pub snapshot_before_restore: bool,
+    /// Whether restores may only target the configured save directory.
+    #[serde(default)]
+    pub restrict_restore_target: bool,
+    /// Whether new backups hardlink to byte-identical backups from any slot.
+    #[serde(default)]
+    pub cross_slot_dedup: bool,
+    /// Whether new backups store their save files gzip-compressed.
+    #[serde(default)]
+    pub compress_backups: bool,
+    /// Whether the watcher's initial scan backs up existing saves or only indexes them.
+    #[serde(default)]
+    pub initial_scan_mode: InitialScanMode,
+    /// Whether saves in subdirectories of the save path are watched and backed up too.
+    #[serde(default)]
+    pub watch_recursive: bool,
+    /// Game slots (0-based) the watcher does not back up.
+    #[serde(default)]
+    pub disabled_games: Vec<u32>,
+    /// Whether `.bak` files are backed up and restored alongside the main save.
+    #[serde(default = "default_include_bak_files")]
+    pub include_bak_files: bool,
+    /// Whether new backups of a changed save are stored as a delta against the previous one.
+    #[serde(default)]
+    pub delta_backups: bool,
+    /// Minutes between save scans that run without a file event; `None` relies on events alone.
+    #[serde(default)]
+    pub periodic_backup_minutes: Option<u32>,
+    /// Which desktop notifications are shown.
+    #[serde(default)]
+    pub notification_level: NotificationLevel,
+    /// Until when duplicate detection is skipped; runtime only, never persisted.
+    #[serde(skip)]
+    pub dedup_disabled_until: Option<Instant>,
 }
 
 impl Default for AppConfig {
@@ -32,6 +128,36 @@ impl Default for AppConfig {
             auto_launch_game: false,
             auto_close: false,
             max_backups_per_game: default_max_backups(),
+            unlimited_backups: false,
+            max_backup_age_days: None,
+            max_total_backup_bytes: None,
+            copy_buffer_kb: default_copy_buffer_kb(),
+            max_events_per_second: default_max_events_per_second(),
+            debounce_seconds: default_debounce_seconds(),
+            restore_suppression_seconds: default_restore_suppression_seconds(),
+            tray_minimize_notified: false,
+            never_delete_last_backup: default_never_delete_last_backup(),
+            game_exe_path: None,
+            game_process_name: None,
+            mirror_directory: None,
+            backup_root_override: None,
+            naming_scheme: NamingScheme::default(),
+            backup_name_format: BackupNameFormat::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            hash_recheck_interval: default_hash_recheck_interval(),
+            trash_retention_days: default_trash_retention_days(),
+            snapshot_before_restore: default_snapshot_before_restore(),
+            restrict_restore_target: false,
+            cross_slot_dedup: false,
+            compress_backups: false,
+            initial_scan_mode: InitialScanMode::default(),
+            watch_recursive: false,
+            disabled_games: Vec::new(),
+            include_bak_files: default_include_bak_files(),
+            delta_backups: false,
+            periodic_backup_minutes: None,
+            notification_level: NotificationLevel::default(),
+            dedup_disabled_until: None,
         }
     }
 }
@@ -40,6 +166,87 @@ fn default_max_backups() -> usize {
     100
 }
 
+impl AppConfig {
+    /// Returns the limit passed to backup enforcement, where 0 means unlimited.
+    pub fn effective_backup_limit(&self) -> usize {
+        if self.unlimited_backups {
+            0
+        } else {
+            self.max_backups_per_game
+        }
+    }
+
+    /// Returns the settings passed to backup operations.
+    pub fn backup_settings(&self) -> BackupSettings {
+        BackupSettings {
+            copy_buffer_kb: self.copy_buffer_kb,
+            never_delete_last_backup: self.never_delete_last_backup,
+            max_backup_age_days: self.max_backup_age_days,
+            max_total_backup_bytes: self.max_total_backup_bytes,
+            naming_scheme: self.naming_scheme,
+            backup_name_format: self.backup_name_format,
+            hash_algorithm: self.hash_algorithm,
+            hash_recheck_interval: self.hash_recheck_interval,
+            trash_retention_days: self.trash_retention_days,
+            compress_backups: self.compress_backups,
+            include_bak_files: self.include_bak_files,
+            cross_slot_dedup: self.cross_slot_dedup,
+            delta_backups: self.delta_backups,
+            mirror_directory: self.mirror_directory.as_ref().map(PathBuf::from),
+            backup_root_override: self.backup_root_override.as_ref().map(PathBuf::from),
+            dedup_disabled_until: self.dedup_disabled_until,
+        }
+    }
+
+    /// Upgrades configs written before `unlimited_backups` existed.
+    ///
+    /// Older versions stored "unlimited" as `max_backups_per_game == 0`; that is
+    /// converted to the explicit flag with the default count restored.
+    pub(crate) fn migrate(mut self) -> Self {
+        if self.max_backups_per_game == 0 {
+            self.unlimited_backups = true;
+            self.max_backups_per_game = default_max_backups();
+        }
+        self
+    }
+}
+
+fn default_copy_buffer_kb() -> usize {
+    crate::backup::fileutil::DEFAULT_COPY_BUFFER_KB
+}
+
+fn default_max_events_per_second() -> u32 {
+    crate::watcher::DEFAULT_MAX_EVENTS_PER_SECOND
+}
+
+fn default_debounce_seconds() -> u64 {
+    crate::watcher::DEFAULT_DEBOUNCE_SECONDS
+}
+
+fn default_restore_suppression_seconds() -> u64 {
+    crate::watcher::DEFAULT_RESTORE_SUPPRESSION_SECONDS
+}
+
+fn default_never_delete_last_backup() -> bool {
+    true
+}
+
+fn default_include_bak_files() -> bool {
+    true
+}
+
+fn default_hash_recheck_interval() -> u32 {
+    crate::backup::create::DEFAULT_HASH_RECHECK_INTERVAL
+}
+
+fn default_trash_retention_days() -> u32 {
+    crate::backup::trash::DEFAULT_TRASH_RETENTION_DAYS
+}
+
+fn default_snapshot_before_restore() -> bool {
+    true
+}
+
 /// State wrapper for the application configuration.
 pub struct ConfigState(pub Mutex<AppConfig>);
 
@@ -69,10 +276,10 @@ pub fn load_config_from_path(path: &Path) -> AppConfig {
     log::info!("Loading configuration from: {:?}", path);
     if path.exists() {
         match fs::read_to_string(path) {
-            Ok(content) => match serde_json::from_str(&content) {
+            Ok(content) => match serde_json::from_str::<AppConfig>(&content) {
                 Ok(config) => {
                     log::info!("Configuration loaded successfully");
-                    return config;
+                    return config.migrate();
                 }
                 Err(e) => log::error!("Failed to parse configuration: {}", e),
             },
@@ -84,11 +291,67 @@ pub fn load_config_from_path(path: &Path) -> AppConfig {
     AppConfig::default()
 }
 
+/// Pushes the backup settings from `config` into the watcher.
+pub(crate) fn apply_backup_settings(config: &AppConfig, watcher: &FileWatcher) {
+    watcher.set_backup_settings(config.backup_settings());
+}
+
+/// A save path that was replaced at startup because the stored one no longer exists.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SavePathRelocation {
+    /// The stored save path that could not be found.
+    pub old_path: String,
+    /// The newly detected save path now in use.
+    pub new_path: String,
+}
+
+/// Holds the startup relocation until the frontend has been notified of it.
+pub struct SavePathRelocationState(pub Mutex<Option<SavePathRelocation>>);
+
+/// Re-detects the save path when the stored one no longer exists.
+///
+/// Game updates can move the save directory. When the configured path is gone and
+/// `detect` finds a different existing location, the config is updated and persisted
+/// to `config_path`. The stored path is kept when nothing is detected.
+pub(crate) fn relocate_missing_save_path(
+    config: &mut AppConfig,
+    config_path: &Path,
+    detect: impl FnOnce() -> Option<PathBuf>,
+) -> Option<SavePathRelocation> {
+    let old_path = config.save_path.clone()?;
+    if Path::new(&old_path).exists() {
+        return None;
+    }
+
+    let Some(new_path) = detect() else {
+        log::warn!(
+            "Configured save path {} no longer exists and no replacement was detected",
+            old_path
+        );
+        return None;
+    };
+    let new_path = new_path.to_string_lossy().to_string();
+    if new_path == old_path {
+        return None;
+    }
+
+    log::info!(
+        "Configured save path {} no longer exists; switching to detected {}",
+        old_path,
+        new_path
+    );
+    config.save_path = Some(new_path.clone());
+    if let Err(e) = save_config_to_path(config, config_path) {
+        log::error!("Failed to save relocated save path: {}", e);
+    }
+    Some(SavePathRelocation { old_path, new_path })
+}
+
 /// Validates if the provided string is a valid path (file or directory).
 ///
 /// If the path itself does not exist, it only accepts it if it looks like a file
 /// (has an extension) and its parent directory exists.
-fn is_valid_path(path: &str) -> bool {
+pub(crate) fn is_valid_path(path: &str) -> bool {
     let p = Path::new(path);
     if p.exists() {
         return true;
@@ -148,6 +411,17 @@ fn update_config(
     update_config_with_path(config_state, &get_config_path(), mutator)
 }
 
+/// Hands the current backup settings to the watcher after a settings change.
+fn sync_backup_settings(config_state: &ConfigState, watcher: &FileWatcher) -> Result<(), String> {
+    let settings = config_state
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .backup_settings();
+    watcher.set_backup_settings(settings);
+    Ok(())
+}
+
 /// Restores the previous watcher state after a failed path swap.
 ///
 /// If the old path exists, restarts the watcher on it. If that also fails,
@@ -161,7 +435,7 @@ fn rollback_watcher(
 ) {
     match old_save_path {
         Some(old_path) => {
-            if let Err(re) = watcher.start(PathBuf::from(old_path), max_backups, None) {
+            if let Err(re) = watcher.start(PathBuf::from(old_path), max_backups, None, None) {
                 log::error!("Failed to restore previous watcher: {}", re);
                 watcher.stop();
                 let _ = update_config_with_path(config_state, config_path, |c| {
@@ -187,10 +461,10 @@ pub(crate) fn restart_watcher_with_limit(
     old_limit: usize,
     config_path: &Path,
 ) -> Result<(), String> {
-    if let Err(e) = watcher.start(path.clone(), new_limit, None) {
+    if let Err(e) = watcher.start(path.clone(), new_limit, None, None) {
         log::error!("Failed to restart watcher with new limit: {}", e);
 
-        let restore_failed = watcher.start(path, old_limit, None).is_err();
+        let restore_failed = watcher.start(path, old_limit, None, None).is_err();
         if restore_failed {
             log::error!("Failed to restore previous watcher after limit change");
         }
@@ -234,10 +508,10 @@ pub(crate) fn replace_watcher_path(
             log::error!("Failed to acquire lock on configuration state: {}", e);
             "Failed to acquire lock on configuration state".to_string()
         })?;
-        (guard.save_path.clone(), guard.max_backups_per_game)
+        (guard.save_path.clone(), guard.effective_backup_limit())
     };
 
-    if let Err(e) = watcher.start(new_path, max_backups, None) {
+    if let Err(e) = watcher.start(new_path, max_backups, None, None) {
         log::error!("Failed to start watcher for new path: {}", e);
         rollback_watcher(
             config_state,
@@ -269,6 +543,18 @@ pub(crate) fn replace_watcher_path(
     Ok(())
 }
 
+/// The save path that was applied, with a caution about where it lives if any.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SavePathUpdate {
+    /// The normalized save directory now in use.
+    pub path: String,
+    /// A caution to show the user, such as the path being synced by Steam Cloud.
+    pub warning: Option<String>,
+}
+
+/// Warning shown when backups would be stored inside a Steam Cloud folder.
+const STEAM_CLOUD_WARNING: &str = "This save folder is synced by Steam Cloud, which may upload or delete the .backups folder inside it. Consider choosing a backup folder outside the save directory.";
+
 /// Sets the save path in the configuration, persists it, and updates the watcher.
 ///
 /// Normalizes the input path to a directory. If a file path is provided,
@@ -280,13 +566,15 @@ pub(crate) fn replace_watcher_path(
 ///
 /// # Returns
 ///
-/// * `Result<String, String>` - The normalized path string on success, or an error message.
+/// * `Result<SavePathUpdate, String>` - The normalized path and an optional warning on
+///   success, or an error message.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn set_save_path(
+    app: tauri::AppHandle,
     config_state: State<'_, ConfigState>,
     watcher: State<'_, FileWatcher>,
     path: String,
-) -> Result<String, String> {
+) -> Result<SavePathUpdate, String> {
     log::info!("Attempting to set save path to: {}", path);
 
     // Validate using the refined rule (path exists OR non-existent file with existing parent)
@@ -306,16 +594,39 @@ pub async fn set_save_path(
 
     let final_path_str = final_path.to_string_lossy().to_string();
     log::info!("Normalized save path to: {}", final_path_str);
+    let warning = steam_cloud_warning(&config_state, &final_path)?;
 
-    replace_watcher_path(
+    if let Err(e) = replace_watcher_path(
         &config_state,
         &watcher,
         final_path,
         final_path_str.clone(),
         &get_config_path(),
-    )?;
+    ) {
+        notifications::notify(&app, NotificationLevel::Errors, "Save path not applied", &e);
+        return Err(e);
+    }
 
-    Ok(final_path_str)
+    Ok(SavePathUpdate {
+        path: final_path_str,
+        warning,
+    })
+}
+
+/// Returns a warning when `save_dir` is a Steam Cloud folder that would also hold the backups.
+fn steam_cloud_warning(
+    config_state: &State<'_, ConfigState>,
+    save_dir: &Path,
+) -> Result<Option<String>, String> {
+    if !crate::save_paths::is_steam_cloud_path(save_dir) {
+        return Ok(None);
+    }
+    let config = config_state.0.lock().map_err(|e| e.to_string())?;
+    if config.backup_root_override.is_some() {
+        return Ok(None);
+    }
+    log::warn!("Save path {:?} is inside a Steam Cloud folder", save_dir);
+    Ok(Some(STEAM_CLOUD_WARNING.to_string()))
 }
 
 /// Signals the monitor invalidator when `auto_close` transitions from enabled to disabled.
@@ -341,6 +652,8 @@ pub(crate) fn signal_invalidator_if_disabled(
 /// * `auto_launch_game` - Enable/disable auto-launch.
 /// * `auto_close` - Enable/disable auto-close.
 /// * `max_backups_per_game` - The limit for backups per game.
+/// * `max_backup_age_days` - Age limit in days applied before the count limit; `0`
+///   removes it and omitting it keeps the current value.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn set_game_settings(
     config_state: State<'_, ConfigState>,
@@ -349,6 +662,7 @@ pub async fn set_game_settings(
     auto_launch_game: bool,
     auto_close: bool,
     max_backups_per_game: usize,
+    max_backup_age_days: Option<u32>,
 ) -> Result<(), String> {
     log::info!(
         "Setting game settings: auto_launch={}, auto_close={}, max_backups={}",
@@ -360,17 +674,22 @@ pub async fn set_game_settings(
     let (limit_changed, old_limit, auto_close_was_enabled) = {
         let guard = config_state.0.lock().map_err(|e| e.to_string())?;
         (
-            guard.max_backups_per_game != max_backups_per_game,
+            !guard.unlimited_backups && guard.max_backups_per_game != max_backups_per_game,
             guard.max_backups_per_game,
             guard.auto_close,
         )
     };
 
+    let max_age = max_backup_age_days.map(|days| (days > 0).then_some(days));
     update_config(&config_state, |config| {
         config.auto_launch_game = auto_launch_game;
         config.auto_close = auto_close;
         config.max_backups_per_game = max_backups_per_game;
+        if let Some(max_age) = max_age {
+            config.max_backup_age_days = max_age;
+        }
     })?;
+    sync_backup_settings(&config_state, &watcher)?;
 
     signal_invalidator_if_disabled(auto_close_was_enabled, auto_close, &invalidator);
 
@@ -397,6 +716,625 @@ pub async fn set_game_settings(
     Ok(())
 }
 
+/// Decides whether the "minimized to tray" notification should be shown.
+///
+/// The notification is only shown until it has been seen once.
+pub(crate) fn should_notify_tray_minimize(config: &AppConfig) -> bool {
+    !config.tray_minimize_notified
+}
+
+/// Claims the one-time tray notification, persisting the flag so later hides stay silent.
+///
+/// Returns `true` when the caller should show the notification. The flag is only
+/// recorded after a successful disk write, matching `update_config_with_path`.
+pub(crate) fn claim_tray_minimize_notification(
+    config_state: &ConfigState,
+    config_path: &Path,
+) -> Result<bool, String> {
+    let should_notify = {
+        let guard = config_state.0.lock().map_err(|e| e.to_string())?;
+        should_notify_tray_minimize(&guard)
+    };
+
+    if !should_notify {
+        return Ok(false);
+    }
+
+    update_config_with_path(config_state, config_path, |config| {
+        config.tray_minimize_notified = true;
+    })?;
+    Ok(true)
+}
+
+/// Enables or disables the "minimized to tray" notification.
+///
+/// Enabling clears the shown flag so the notification appears again on the next hide.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the notification should be shown on the next minimize.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_tray_notification_enabled(
+    config_state: State<'_, ConfigState>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting tray minimize notification enabled={}", enabled);
+    update_config(&config_state, |config| {
+        config.tray_minimize_notified = !enabled;
+    })
+}
+
+/// Enables or disables the "keep at least one backup per slot" deletion safety.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the newest backup of each slot is protected from deletion.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_never_delete_last_backup(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting never_delete_last_backup={}", enabled);
+    update_config(&config_state, |config| {
+        config.never_delete_last_backup = enabled;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets the game executable path used as a fallback when Steam launches fail.
+///
+/// # Arguments
+///
+/// * `path` - The executable path, or `None` to clear it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_game_exe_path(
+    config_state: State<'_, ConfigState>,
+    path: Option<String>,
+) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = &path {
+        if !Path::new(p).is_file() {
+            return Err("The game executable path must point to an existing file.".to_string());
+        }
+    }
+    log::info!("Setting game executable path to: {:?}", path);
+    update_config(&config_state, |config| {
+        config.game_exe_path = path;
+    })
+}
+
+/// Sets the part of the process name the auto-close monitor looks for.
+///
+/// # Arguments
+///
+/// * `name` - Case-insensitive part of the game's process name, or `None` for the default.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_game_process_name(
+    config_state: State<'_, ConfigState>,
+    name: Option<String>,
+) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string());
+    if name.as_deref().is_some_and(str::is_empty) {
+        return Err("The game process name cannot be empty.".to_string());
+    }
+    log::info!("Setting game process name to: {:?}", name);
+    update_config(&config_state, |config| {
+        config.game_process_name = name;
+    })
+}
+
+/// Sets the secondary directory that new backups are mirrored into.
+///
+/// # Arguments
+///
+/// * `path` - An existing directory, or `None` to disable mirroring.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_mirror_directory(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    path: Option<String>,
+) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = &path {
+        if !Path::new(p).is_dir() {
+            return Err("The mirror directory must be an existing folder.".to_string());
+        }
+    }
+    log::info!("Setting mirror directory to: {:?}", path);
+    update_config(&config_state, |config| {
+        config.mirror_directory = path;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets the directory that holds backups instead of the save directory.
+///
+/// Each save path gets its own subfolder inside the override. Existing backups are
+/// not moved; they reappear when the override is cleared again.
+///
+/// # Arguments
+///
+/// * `path` - An existing directory, or `None` to keep backups in `.backups`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_backup_root_override(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    path: Option<String>,
+) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = &path {
+        if !Path::new(p).is_dir() {
+            return Err("The backup folder must be an existing folder.".to_string());
+        }
+    }
+    log::info!("Setting backup root override to: {:?}", path);
+    update_config(&config_state, |config| {
+        config.backup_root_override = path.clone();
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets how new backup folders are named.
+///
+/// Existing folders keep their names; both schemes are recognized when listing.
+///
+/// # Arguments
+///
+/// * `scheme` - Timestamp-based or counter-based folder names.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_naming_scheme(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    scheme: NamingScheme,
+) -> Result<(), String> {
+    log::info!("Setting backup naming scheme to: {:?}", scheme);
+    update_config(&config_state, |config| {
+        config.naming_scheme = scheme;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets the timestamp format used in new backup folder names.
+///
+/// Existing folders keep their names; every format is recognized when listing.
+///
+/// # Arguments
+///
+/// * `format` - `default`, `iso8601`, or `unix`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_backup_name_format(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    format: BackupNameFormat,
+) -> Result<(), String> {
+    log::info!("Setting backup name format to: {:?}", format);
+    update_config(&config_state, |config| {
+        config.backup_name_format = format;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets the algorithm used to hash saves for duplicate detection.
+///
+/// Existing backups keep their recorded algorithm. The first backup of each slot after
+/// a change is always taken, since hashes from different algorithms never match.
+///
+/// # Arguments
+///
+/// * `algorithm` - `sha256`, `blake3`, or `xxhash`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_hash_algorithm(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    algorithm: HashAlgorithm,
+) -> Result<(), String> {
+    log::info!("Setting hash algorithm to: {:?}", algorithm);
+    update_config(&config_state, |config| {
+        config.hash_algorithm = algorithm;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets every how many backup attempts a slot is rehashed despite matching metadata.
+///
+/// Guards against filesystems with coarse modification times, where two different
+/// saves of the same size can look identical without hashing.
+///
+/// # Arguments
+///
+/// * `attempts` - The recheck interval; `0` always trusts size and modification time.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_hash_recheck_interval(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    attempts: u32,
+) -> Result<(), String> {
+    log::info!("Setting hash_recheck_interval={}", attempts);
+    update_config(&config_state, |config| {
+        config.hash_recheck_interval = attempts;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets how many days deleted backups stay in the trash.
+///
+/// Expired trash is emptied during the cleanup after the next backup.
+///
+/// # Arguments
+///
+/// * `days` - Retention in days; `0` keeps trashed backups until emptied manually.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_trash_retention_days(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    days: u32,
+) -> Result<(), String> {
+    log::info!("Setting trash_retention_days={}", days);
+    update_config(&config_state, |config| {
+        config.trash_retention_days = days;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Enables or disables backing up the live save before each restore.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether a safety backup is taken before a restore overwrites the save.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_snapshot_before_restore(
+    config_state: State<'_, ConfigState>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting snapshot_before_restore={}", enabled);
+    update_config(&config_state, |config| {
+        config.snapshot_before_restore = enabled;
+    })
+}
+
+/// Enables or disables restricting restores to the configured save directory.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether restore targets other than the save directory are rejected.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_restrict_restore_target(
+    config_state: State<'_, ConfigState>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting restrict_restore_target={}", enabled);
+    update_config(&config_state, |config| {
+        config.restrict_restore_target = enabled;
+    })
+}
+
+/// Enables or disables sharing identical backup files across slots via hardlinks.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether new backups hardlink to identical backups from any slot.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_cross_slot_dedup(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting cross_slot_dedup={}", enabled);
+    update_config(&config_state, |config| {
+        config.cross_slot_dedup = enabled;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Enables or disables storing new backups gzip-compressed.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether new backups store their save files compressed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_compress_backups(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting compress_backups={}", enabled);
+    update_config(&config_state, |config| {
+        config.compress_backups = enabled;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets whether `.bak` files are backed up and restored.
+///
+/// Existing backups keep their `.bak` files; while excluded they are just not restored.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether `.bak` files are included in backups and restores.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_include_bak_files(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting include_bak_files={}", enabled);
+    update_config(&config_state, |config| {
+        config.include_bak_files = enabled;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets which desktop notifications are shown.
+///
+/// # Arguments
+///
+/// * `level` - The least important kind of notification still shown.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_notification_level(
+    config_state: State<'_, ConfigState>,
+    level: NotificationLevel,
+) -> Result<(), String> {
+    log::info!("Setting notification_level={:?}", level);
+    update_config(&config_state, |config| {
+        config.notification_level = level;
+    })
+}
+
+/// Enables or disables storing new backups as deltas against the previous backup.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the main save of new backups is stored as a delta when smaller.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_delta_backups(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting delta_backups={}", enabled);
+    update_config(&config_state, |config| {
+        config.delta_backups = enabled;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets whether the initial scan backs up existing saves or only indexes them.
+///
+/// Applies from the next watcher start, e.g. when a new save path is chosen.
+///
+/// # Arguments
+///
+/// * `mode` - Back up all existing saves, or only record them for future dedup.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_initial_scan_mode(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    mode: InitialScanMode,
+) -> Result<(), String> {
+    log::info!("Setting initial scan mode to: {:?}", mode);
+    update_config(&config_state, |config| {
+        config.initial_scan_mode = mode;
+    })?;
+    watcher.set_initial_scan_mode(mode);
+    Ok(())
+}
+
+/// Sets whether saves nested in subdirectories of the save path are watched.
+///
+/// Applies from the next watcher start, e.g. when a new save path is chosen.
+///
+/// # Arguments
+///
+/// * `enabled` - Watch and scan subdirectories instead of only the save directory itself.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_watch_recursive(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting recursive watching to: {}", enabled);
+    update_config(&config_state, |config| {
+        config.watch_recursive = enabled;
+    })?;
+    watcher.set_watch_recursive(enabled);
+    Ok(())
+}
+
+/// Enables or disables automatic backups for a single game slot.
+///
+/// Takes effect immediately, including for a running watcher. Manual backups of a
+/// disabled slot still work.
+///
+/// # Arguments
+///
+/// * `game_number` - The 0-based slot to toggle.
+/// * `enabled` - Whether the watcher should back up this slot.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_game_backup_enabled(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    game_number: u32,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!(
+        "Setting auto-backups for game {} to: {}",
+        game_number + 1,
+        enabled
+    );
+    let mut disabled_games = Vec::new();
+    update_config(&config_state, |config| {
+        config.disabled_games.retain(|&game| game != game_number);
+        if !enabled {
+            config.disabled_games.push(game_number);
+            config.disabled_games.sort_unstable();
+        }
+        disabled_games = config.disabled_games.clone();
+    })?;
+    watcher.set_disabled_games(&disabled_games);
+    Ok(())
+}
+
+/// Sets the cap on the size of the backup folder, trash and quarantine included.
+///
+/// Checked after each new backup, which empties the trash and then deletes the oldest
+/// unlocked, non-favorite backups across all games until the folder fits again.
+///
+/// # Arguments
+///
+/// * `max_bytes` - Size cap in bytes; `None` or `0` removes it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_max_total_backup_bytes(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    max_bytes: Option<u64>,
+) -> Result<(), String> {
+    let max_bytes = max_bytes.filter(|&max_bytes| max_bytes > 0);
+    log::info!("Setting max total backup size to: {:?} byte(s)", max_bytes);
+    update_config(&config_state, |config| {
+        config.max_total_backup_bytes = max_bytes;
+    })?;
+    sync_backup_settings(&config_state, &watcher)?;
+    Ok(())
+}
+
+/// Sets how often the watcher rescans the save directory regardless of file events.
+///
+/// Useful on network drives and other storage that does not report changes reliably.
+/// Takes effect within a minute, including for a running watcher.
+///
+/// # Arguments
+///
+/// * `minutes` - Interval between scans; `None` or `0` relies on file events alone.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_periodic_backup_minutes(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    minutes: Option<u32>,
+) -> Result<(), String> {
+    let minutes = minutes.filter(|&minutes| minutes > 0);
+    log::info!(
+        "Setting periodic backup interval to: {:?} minute(s)",
+        minutes
+    );
+    update_config(&config_state, |config| {
+        config.periodic_backup_minutes = minutes;
+    })?;
+    watcher.set_periodic_backup_minutes(minutes);
+    Ok(())
+}
+
+/// Switches between unlimited backups and the configured per-game count limit.
+///
+/// Restarts the watcher with the new effective limit before persisting; if the
+/// restart fails the previous watcher is restored and the flag is left unchanged.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether backups should be kept without a count limit.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_unlimited_backups(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting unlimited_backups={}", enabled);
+
+    let (path_buf, old_limit, new_limit) = {
+        let guard = config_state.0.lock().map_err(|e| e.to_string())?;
+        let new_limit = if enabled {
+            0
+        } else {
+            guard.max_backups_per_game
+        };
+        (
+            guard.save_path.as_deref().map(PathBuf::from),
+            guard.effective_backup_limit(),
+            new_limit,
+        )
+    };
+
+    if let Some(path) = path_buf.filter(|_| old_limit != new_limit) {
+        if let Err(e) = watcher.start(path.clone(), new_limit, None, None) {
+            log::error!("Failed to restart watcher with new limit: {}", e);
+            if watcher.start(path, old_limit, None, None).is_err() {
+                log::error!("Failed to restore previous watcher after limit change");
+            }
+            return Err(format!(
+                "Failed to restart watcher with new backup limit. Error: {}",
+                e
+            ));
+        }
+    }
+
+    update_config(&config_state, |config| {
+        config.unlimited_backups = enabled;
+    })
+}
+
+/// Persists only the watcher tuning fields, leaving the rest of the config untouched.
+///
+/// A `limit` of 0 is stored as `unlimited_backups`; any other value becomes the
+/// per-game count limit.
+pub(crate) fn persist_watcher_params(
+    config_state: &ConfigState,
+    config_path: &Path,
+    params: WatcherParams,
+) -> Result<(), String> {
+    update_config_with_path(config_state, config_path, |config| {
+        config.debounce_seconds = params.debounce_seconds;
+        if params.limit == 0 {
+            config.unlimited_backups = true;
+        } else {
+            config.unlimited_backups = false;
+            config.max_backups_per_game = params.limit;
+        }
+    })
+}
+
+/// Updates the running watcher's debounce period and backup limit immediately.
+///
+/// # Arguments
+///
+/// * `debounce_seconds` - Quiet period after the last change before backing up.
+/// * `limit` - Backup limit per game (0 = unlimited).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_watcher_params_command(
+    config_state: State<'_, ConfigState>,
+    watcher: State<'_, FileWatcher>,
+    debounce_seconds: u64,
+    limit: usize,
+) -> Result<(), String> {
+    let params = WatcherParams {
+        debounce_seconds,
+        limit,
+    };
+    log::info!("Setting watcher params: {:?}", params);
+    persist_watcher_params(&config_state, &get_config_path(), params)?;
+    watcher.set_params(params);
+    Ok(())
+}
+
+/// Returns the debounce period and backup limit the watcher is currently using.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_watcher_params_command(
+    watcher: State<'_, FileWatcher>,
+) -> Result<WatcherParams, String> {
+    Ok(watcher.params())
+}
+
 /// Serializes and writes the configuration to a specific path.
 pub(crate) fn save_config_to_path(config: &AppConfig, path: &Path) -> Result<(), String> {
     let json = serde_json::to_string_pretty(config).map_err(|e| {
@@ -435,6 +1373,7 @@ mod tests {
             auto_launch_game: true,
             auto_close: true,
             max_backups_per_game: 50,
+            ..AppConfig::default()
         };
         let json = serde_json::to_string(&config).unwrap();
         // Field order depends on struct definition or serde implementation.
@@ -453,6 +1392,15 @@ mod tests {
         assert!(!config.auto_launch_game);
         assert!(!config.auto_close);
         assert_eq!(config.max_backups_per_game, 100);
+        assert_eq!(config.copy_buffer_kb, 8);
+        assert_eq!(config.max_events_per_second, 20);
+        assert_eq!(config.restore_suppression_seconds, 5);
+        assert!(config.never_delete_last_backup);
+        assert!(config.snapshot_before_restore);
+        assert!(config.include_bak_files);
+        assert_eq!(config.notification_level, NotificationLevel::Important);
+        assert_eq!(config.hash_recheck_interval, 20);
+        assert!(!config.unlimited_backups);
     }
 
     /// Tests that an invalid path string returns false.
@@ -508,6 +1456,7 @@ mod tests {
             auto_launch_game: true,
             auto_close: false,
             max_backups_per_game: 200,
+            ..AppConfig::default()
         };
         let json = serde_json::to_string(&config).unwrap();
         fs::write(&config_path, json).expect("failed to write config");
@@ -621,7 +1570,7 @@ mod tests {
             ..AppConfig::default()
         }));
         let watcher = FileWatcher::new();
-        watcher.start(save_dir.clone(), 50, None).unwrap();
+        watcher.start(save_dir.clone(), 50, None, None).unwrap();
 
         let result = restart_watcher_with_limit(&cs, &watcher, save_dir, 100, 50, &config_path);
 
@@ -681,7 +1630,7 @@ mod tests {
         let watcher = FileWatcher::new();
         // Start the watcher on the old path so there is a prior active watcher
         watcher
-            .start(old_save_dir.clone(), 100, None)
+            .start(old_save_dir.clone(), 100, None, None)
             .expect("initial watcher start failed");
 
         let result = replace_watcher_path(
@@ -769,4 +1718,161 @@ mod tests {
         signal_invalidator_if_disabled(true, true, &inv);
         assert!(!inv.0.load(Ordering::Relaxed));
     }
+
+    /// Tests that the tray notification is only requested while the flag is unset.
+    #[test]
+    fn test_should_notify_tray_minimize_follows_flag() {
+        let mut config = AppConfig::default();
+        assert!(should_notify_tray_minimize(&config));
+
+        config.tray_minimize_notified = true;
+        assert!(!should_notify_tray_minimize(&config));
+    }
+
+    /// Tests that the tray notification is claimed once and the flag is persisted.
+    #[test]
+    fn test_claim_tray_minimize_notification_only_once() {
+        let temp_dir = tempdir().expect("failed to create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        let state = make_config_state(None);
+
+        assert!(claim_tray_minimize_notification(&state, &config_path).unwrap());
+        assert!(!claim_tray_minimize_notification(&state, &config_path).unwrap());
+
+        let saved = load_config_from_path(&config_path);
+        assert!(saved.tray_minimize_notified);
+    }
+
+    /// Tests that the unlimited flag maps to the enforcement sentinel of 0.
+    #[test]
+    fn test_effective_backup_limit_respects_unlimited_flag() {
+        let mut config = AppConfig {
+            max_backups_per_game: 25,
+            ..AppConfig::default()
+        };
+        assert_eq!(config.effective_backup_limit(), 25);
+
+        config.unlimited_backups = true;
+        assert_eq!(config.effective_backup_limit(), 0);
+    }
+
+    /// Tests that legacy configs storing 0 as "unlimited" migrate to the explicit flag.
+    #[test]
+    fn test_load_config_migrates_zero_limit_to_unlimited() {
+        let temp_dir = tempdir().expect("failed to create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(
+            &config_path,
+            r#"{"save_path":null,"max_backups_per_game":0}"#,
+        )
+        .unwrap();
+
+        let loaded = load_config_from_path(&config_path);
+        assert!(loaded.unlimited_backups);
+        assert_eq!(loaded.max_backups_per_game, 100);
+        assert_eq!(loaded.effective_backup_limit(), 0);
+    }
+
+    /// Tests that persisting watcher params changes only the tuning fields.
+    #[test]
+    fn test_persist_watcher_params_updates_only_tuning_fields() {
+        let temp_dir = tempdir().expect("failed to create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        let state = ConfigState(Mutex::new(AppConfig {
+            save_path: Some("SavePath".to_string()),
+            auto_launch_game: true,
+            max_backups_per_game: 50,
+            ..AppConfig::default()
+        }));
+
+        persist_watcher_params(
+            &state,
+            &config_path,
+            WatcherParams {
+                debounce_seconds: 7,
+                limit: 12,
+            },
+        )
+        .unwrap();
+
+        let saved = load_config_from_path(&config_path);
+        assert_eq!(saved.debounce_seconds, 7);
+        assert_eq!(saved.max_backups_per_game, 12);
+        assert!(!saved.unlimited_backups);
+        assert_eq!(saved.save_path, Some("SavePath".to_string()));
+        assert!(saved.auto_launch_game);
+
+        persist_watcher_params(
+            &state,
+            &config_path,
+            WatcherParams {
+                debounce_seconds: 7,
+                limit: 0,
+            },
+        )
+        .unwrap();
+        let saved = load_config_from_path(&config_path);
+        assert!(saved.unlimited_backups);
+        assert_eq!(saved.max_backups_per_game, 12);
+    }
+
+    /// Tests that a missing stored save path is replaced by a detected one and persisted.
+    #[test]
+    fn test_relocate_missing_save_path_uses_detected_path() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        let missing = dir.path().join("OldSaves");
+        let detected = dir.path().join("NewSaves");
+        fs::create_dir_all(&detected).unwrap();
+
+        let mut config = AppConfig {
+            save_path: Some(missing.to_string_lossy().to_string()),
+            ..AppConfig::default()
+        };
+        let relocation =
+            relocate_missing_save_path(&mut config, &config_path, || Some(detected.clone()));
+
+        assert_eq!(
+            relocation,
+            Some(SavePathRelocation {
+                old_path: missing.to_string_lossy().to_string(),
+                new_path: detected.to_string_lossy().to_string(),
+            })
+        );
+        assert_eq!(
+            config.save_path.as_deref(),
+            Some(detected.to_string_lossy().as_ref())
+        );
+        assert_eq!(
+            load_config_from_path(&config_path).save_path,
+            config.save_path
+        );
+    }
+
+    /// Tests that the stored save path is kept when it exists or nothing is detected.
+    #[test]
+    fn test_relocate_missing_save_path_keeps_path_without_replacement() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        let missing = dir.path().join("OldSaves").to_string_lossy().to_string();
+
+        let mut config = AppConfig {
+            save_path: Some(missing.clone()),
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            relocate_missing_save_path(&mut config, &config_path, || None),
+            None
+        );
+        assert_eq!(config.save_path, Some(missing));
+
+        let existing = dir.path().to_string_lossy().to_string();
+        config.save_path = Some(existing.clone());
+        let relocation = relocate_missing_save_path(&mut config, &config_path, || {
+            panic!("detection should not run for an existing path")
+        });
+        assert_eq!(relocation, None);
+        assert_eq!(config.save_path, Some(existing));
+        assert!(!config_path.exists());
+    }
 }
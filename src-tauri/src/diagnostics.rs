@@ -0,0 +1,182 @@
+// ITD ODD Save Manager by andromarces
+
+use serde::Serialize;
+
+/// Raw signals gathered from config, the save directory, the watcher, and backups.
+#[derive(Debug, Clone, Default)]
+pub struct AutoBackupSignals {
+    /// Whether a save path is configured.
+    pub save_path_set: bool,
+    /// Whether the configured save path exists and is a directory.
+    pub save_dir_exists: bool,
+    /// Number of game slots with a main save file in the save directory.
+    pub save_slots: usize,
+    /// Whether the file watcher is running.
+    pub watcher_active: bool,
+    /// File system events the watcher has received since startup.
+    pub events_received: u64,
+    /// Number of backups found for the save directory.
+    pub backup_count: usize,
+}
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DiagnosisCheck {
+    /// Stable identifier of the check, e.g. "watcher_active".
+    pub name: String,
+    /// Whether the check passed.
+    pub ok: bool,
+    /// Whether a failure makes auto-backup unhealthy, as opposed to being informational.
+    pub required: bool,
+    /// Human-readable explanation of the result.
+    pub detail: String,
+}
+
+/// One-click answer to "is auto-backup working?".
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct AutoBackupDiagnosis {
+    /// True when every required check passed.
+    pub healthy: bool,
+    /// Per-signal results in evaluation order.
+    pub checks: Vec<DiagnosisCheck>,
+}
+
+fn check(name: &str, ok: bool, required: bool, detail: String) -> DiagnosisCheck {
+    DiagnosisCheck {
+        name: name.to_string(),
+        ok,
+        required,
+        detail,
+    }
+}
+
+/// Combines the gathered signals into per-check results and an overall verdict.
+///
+/// Receiving watcher events is informational only: no events is expected until the
+/// game writes a save after the watcher started.
+pub fn diagnose_auto_backup(signals: &AutoBackupSignals) -> AutoBackupDiagnosis {
+    let checks = vec![
+        check(
+            "save_path_set",
+            signals.save_path_set,
+            true,
+            if signals.save_path_set {
+                "A save path is configured.".to_string()
+            } else {
+                "No save path is configured.".to_string()
+            },
+        ),
+        check(
+            "save_dir_exists",
+            signals.save_dir_exists,
+            true,
+            if signals.save_dir_exists {
+                "The save directory exists.".to_string()
+            } else {
+                "The save directory does not exist or is not a folder.".to_string()
+            },
+        ),
+        check(
+            "saves_present",
+            signals.save_slots > 0,
+            true,
+            format!("Found {} save slot(s).", signals.save_slots),
+        ),
+        check(
+            "watcher_active",
+            signals.watcher_active,
+            true,
+            if signals.watcher_active {
+                "The save directory is being watched.".to_string()
+            } else {
+                "The save directory is not being watched.".to_string()
+            },
+        ),
+        check(
+            "events_received",
+            signals.events_received > 0,
+            false,
+            format!(
+                "Received {} file change event(s) since startup.",
+                signals.events_received
+            ),
+        ),
+        check(
+            "backups_exist",
+            signals.backup_count > 0,
+            true,
+            format!("Found {} backup(s).", signals.backup_count),
+        ),
+    ];
+
+    AutoBackupDiagnosis {
+        healthy: checks.iter().all(|c| c.ok || !c.required),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy_signals() -> AutoBackupSignals {
+        AutoBackupSignals {
+            save_path_set: true,
+            save_dir_exists: true,
+            save_slots: 2,
+            watcher_active: true,
+            events_received: 5,
+            backup_count: 3,
+        }
+    }
+
+    fn check_ok(diagnosis: &AutoBackupDiagnosis, name: &str) -> bool {
+        diagnosis
+            .checks
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.ok)
+            .expect("check should be present")
+    }
+
+    /// Tests that all passing signals produce a healthy diagnosis.
+    #[test]
+    fn diagnose_auto_backup_healthy_when_all_signals_pass() {
+        let diagnosis = diagnose_auto_backup(&healthy_signals());
+        assert!(diagnosis.healthy);
+        assert!(diagnosis.checks.iter().all(|c| c.ok));
+    }
+
+    /// Tests that a stopped watcher or missing backups make the diagnosis unhealthy.
+    #[test]
+    fn diagnose_auto_backup_unhealthy_on_required_failure() {
+        let diagnosis = diagnose_auto_backup(&AutoBackupSignals {
+            watcher_active: false,
+            ..healthy_signals()
+        });
+        assert!(!diagnosis.healthy);
+        assert!(!check_ok(&diagnosis, "watcher_active"));
+
+        let diagnosis = diagnose_auto_backup(&AutoBackupSignals {
+            backup_count: 0,
+            ..healthy_signals()
+        });
+        assert!(!diagnosis.healthy);
+        assert!(!check_ok(&diagnosis, "backups_exist"));
+
+        let diagnosis = diagnose_auto_backup(&AutoBackupSignals::default());
+        assert!(!diagnosis.healthy);
+        assert!(diagnosis.checks.iter().all(|c| !c.ok));
+    }
+
+    /// Tests that no watcher events yet is reported without failing the diagnosis.
+    #[test]
+    fn diagnose_auto_backup_events_are_informational() {
+        let diagnosis = diagnose_auto_backup(&AutoBackupSignals {
+            events_received: 0,
+            ..healthy_signals()
+        });
+        assert!(diagnosis.healthy);
+        assert!(!check_ok(&diagnosis, "events_received"));
+    }
+}
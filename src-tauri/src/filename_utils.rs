@@ -1,4 +1,5 @@
 use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Represents parsed information from a game save filename.
@@ -53,6 +54,25 @@ pub fn parse_filename(filename: &str) -> Option<SaveFileInfo> {
     }
 }
 
+/// Parses a loosely named legacy save file to extract its game number.
+///
+/// Tolerates suffixes added by manual copies or file managers between the number
+/// and the extension, e.g. `gamesave_0 (1).sav` or `gamesave_2 - Copy.sav`.
+/// `.bak` files are not accepted.
+pub fn parse_legacy_save_filename(filename: &str) -> Option<u32> {
+    let rest = filename.strip_prefix("gamesave_")?;
+    let stem = rest.strip_suffix(".sav")?;
+
+    let digits_end = stem
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(stem.len());
+    if digits_end == 0 {
+        return None;
+    }
+
+    stem[..digits_end].parse::<u32>().ok()
+}
+
 /// Parses a file path to extract game save information.
 ///
 /// # Arguments
@@ -99,32 +119,130 @@ pub fn normalize_to_directory(path: &Path) -> Result<PathBuf, String> {
 const BACKUP_FOLDER_PREFIX: &str = "Game ";
 const BACKUP_FOLDER_SEPARATOR: &str = " - ";
 const BACKUP_TIMESTAMP_FORMAT: &str = "%d-%b-%Y %I-%M-%S %p";
+// Hyphens instead of colons keep the name valid on Windows.
+const BACKUP_ISO8601_FORMAT: &str = "%Y-%m-%dT%H-%M-%S";
+const BACKUP_COUNTER_MARKER: &str = "#";
+const BACKUP_LABEL_OPEN: &str = " [";
+const BACKUP_LABEL_CLOSE: &str = "]";
+
+/// Timestamp format used in the names of new timestamp-named backup folders.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupNameFormat {
+    /// "dd-MMM-yyyy hh-mm-ss AM", e.g. "25-Jan-2024 09-00-00 AM".
+    #[default]
+    Default,
+    /// "yyyy-mm-ddThh-mm-ss" in local time, e.g. "2024-01-25T09-00-00".
+    Iso8601,
+    /// Seconds since the Unix epoch, e.g. "1706173200".
+    Unix,
+}
+
+impl BackupNameFormat {
+    const ALL: [Self; 3] = [Self::Default, Self::Iso8601, Self::Unix];
+
+    fn format_timestamp(self, timestamp: DateTime<Local>) -> String {
+        match self {
+            Self::Default => timestamp.format(BACKUP_TIMESTAMP_FORMAT).to_string(),
+            Self::Iso8601 => timestamp.format(BACKUP_ISO8601_FORMAT).to_string(),
+            Self::Unix => timestamp.timestamp().to_string(),
+        }
+    }
+
+    fn parse_timestamp(self, text: &str) -> Option<DateTime<Local>> {
+        let naive_format = match self {
+            Self::Default => BACKUP_TIMESTAMP_FORMAT,
+            Self::Iso8601 => BACKUP_ISO8601_FORMAT,
+            Self::Unix => {
+                if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                return Local.timestamp_opt(text.parse().ok()?, 0).single();
+            }
+        };
+        let naive_dt = NaiveDateTime::parse_from_str(text, naive_format).ok()?;
+        match Local.from_local_datetime(&naive_dt) {
+            chrono::LocalResult::Single(dt) => Some(dt),
+            chrono::LocalResult::Ambiguous(dt1, _) => Some(dt1),
+            chrono::LocalResult::None => None,
+        }
+    }
+}
 
 /// Formats a backup folder name for a specific game and timestamp.
 ///
 /// Format: "Game {N} - {Timestamp}"
-/// where Timestamp is "dd-MMM-yyyy hh-mm-ss AM"
+/// where Timestamp follows the default `BackupNameFormat`.
 pub fn format_backup_folder_name(game_number: u32, timestamp: DateTime<Local>) -> String {
+    format_backup_folder_name_with(BackupNameFormat::default(), game_number, timestamp)
+}
+
+/// Formats a backup folder name using an explicit timestamp format.
+pub fn format_backup_folder_name_with(
+    format: BackupNameFormat,
+    game_number: u32,
+    timestamp: DateTime<Local>,
+) -> String {
     let display_number = game_number + 1;
-    let timestamp_str = timestamp.format(BACKUP_TIMESTAMP_FORMAT).to_string();
+    let timestamp_str = format.format_timestamp(timestamp);
     format!(
         "{}{}{}{}",
         BACKUP_FOLDER_PREFIX, display_number, BACKUP_FOLDER_SEPARATOR, timestamp_str
     )
 }
 
+/// Formats a backup folder name for a specific game and sequence counter.
+///
+/// Format: "Game {N} - #{Counter}" where Counter is zero-padded to four digits.
+pub fn format_counter_backup_folder_name(game_number: u32, counter: u32) -> String {
+    let display_number = game_number + 1;
+    format!(
+        "{}{}{}{}{:04}",
+        BACKUP_FOLDER_PREFIX,
+        display_number,
+        BACKUP_FOLDER_SEPARATOR,
+        BACKUP_COUNTER_MARKER,
+        counter
+    )
+}
+
 /// Parsed result from a backup folder name.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BackupFolderInfo {
     pub game_number: u32,
-    pub timestamp: DateTime<Local>,
+    /// Timestamp encoded in the name; `None` for counter-named folders.
+    pub timestamp: Option<DateTime<Local>>,
+    /// Sequence counter encoded in the name; `None` for timestamp-named folders.
+    pub counter: Option<u32>,
 }
 
-/// Parses a backup folder name to extract game number and timestamp.
+/// Splits a trailing " [label]" added by a rename off a backup folder name.
 ///
-/// Tries to parse the timestamp from the folder name. If parsing fails,
-/// returns None.
+/// Returns the name without the label, and the label if there is one.
+pub fn split_backup_label(folder_name: &str) -> (&str, Option<&str>) {
+    folder_name
+        .strip_suffix(BACKUP_LABEL_CLOSE)
+        .and_then(|rest| rest.rsplit_once(BACKUP_LABEL_OPEN))
+        .filter(|(_, label)| !label.is_empty())
+        .map_or((folder_name, None), |(base, label)| (base, Some(label)))
+}
+
+/// Appends a user label to a backup folder name that has none.
+pub fn with_backup_label(base_name: &str, label: &str) -> String {
+    format!(
+        "{}{}{}{}",
+        base_name, BACKUP_LABEL_OPEN, label, BACKUP_LABEL_CLOSE
+    )
+}
+
+/// Parses a backup folder name to extract game number and timestamp or counter.
+///
+/// Accepts both "Game {N} - {Timestamp}" and "Game {N} - #{Counter}", optionally
+/// followed by a " [label]". The timestamp may be in any `BackupNameFormat`, so
+/// folders keep parsing after the format changes.
+/// Returns None if the suffix is neither a valid timestamp nor a counter.
 pub fn parse_backup_folder_name(folder_name: &str) -> Option<BackupFolderInfo> {
+    let (folder_name, _) = split_backup_label(folder_name);
     let (prefix, date_part) = folder_name.split_once(BACKUP_FOLDER_SEPARATOR)?;
 
     let stripped_prefix = prefix.strip_prefix(BACKUP_FOLDER_PREFIX)?;
@@ -132,21 +250,25 @@ pub fn parse_backup_folder_name(folder_name: &str) -> Option<BackupFolderInfo> {
     // Internal game number is 0-based
     let game_number = display_number.saturating_sub(1);
 
-    if let Ok(naive_dt) = NaiveDateTime::parse_from_str(date_part, BACKUP_TIMESTAMP_FORMAT) {
-        match Local.from_local_datetime(&naive_dt) {
-            chrono::LocalResult::Single(dt) => Some(BackupFolderInfo {
-                game_number,
-                timestamp: dt,
-            }),
-            chrono::LocalResult::Ambiguous(dt1, _) => Some(BackupFolderInfo {
-                game_number,
-                timestamp: dt1,
-            }),
-            chrono::LocalResult::None => None,
+    if let Some(counter_str) = date_part.strip_prefix(BACKUP_COUNTER_MARKER) {
+        if counter_str.is_empty() || !counter_str.chars().all(|c| c.is_ascii_digit()) {
+            return None;
         }
-    } else {
-        None
+        return Some(BackupFolderInfo {
+            game_number,
+            timestamp: None,
+            counter: Some(counter_str.parse::<u32>().ok()?),
+        });
     }
+
+    let timestamp = BackupNameFormat::ALL
+        .into_iter()
+        .find_map(|format| format.parse_timestamp(date_part))?;
+    Some(BackupFolderInfo {
+        game_number,
+        timestamp: Some(timestamp),
+        counter: None,
+    })
 }
 
 #[cfg(test)]
@@ -204,6 +326,20 @@ mod tests {
         assert_eq!(parse_filename("gamesave_0.sav.other"), None);
     }
 
+    /// Tests that legacy save filenames with copy suffixes are parsed leniently.
+    #[test]
+    fn test_parse_legacy_save_filename() {
+        assert_eq!(parse_legacy_save_filename("gamesave_0.sav"), Some(0));
+        assert_eq!(parse_legacy_save_filename("gamesave_0 (1).sav"), Some(0));
+        assert_eq!(
+            parse_legacy_save_filename("gamesave_12 - Copy.sav"),
+            Some(12)
+        );
+        assert_eq!(parse_legacy_save_filename("gamesave_ (1).sav"), None);
+        assert_eq!(parse_legacy_save_filename("gamesave_0 (1).sav.bak"), None);
+        assert_eq!(parse_legacy_save_filename("other_0 (1).sav"), None);
+    }
+
     /// Tests parsing from a full path.
     #[test]
     fn test_parse_path() {
@@ -263,6 +399,91 @@ mod tests {
         let parsed = parse_backup_folder_name(&folder_name).expect("Failed to parse");
         assert_eq!(parsed.game_number, game_number);
         // Allow for some second precision loss if any, but string format is second precise
-        assert_eq!(parsed.timestamp.timestamp(), dt.timestamp());
+        assert_eq!(
+            parsed.timestamp.map(|t| t.timestamp()),
+            Some(dt.timestamp())
+        );
+        assert_eq!(parsed.counter, None);
+    }
+
+    /// Tests that every name format round-trips and parses regardless of the active one.
+    #[test]
+    fn test_backup_name_formats_round_trip() {
+        let dt = Local.timestamp_opt(1706173200, 0).unwrap();
+
+        assert_eq!(
+            format_backup_folder_name_with(BackupNameFormat::Unix, 2, dt),
+            "Game 3 - 1706173200"
+        );
+        assert_eq!(
+            format_backup_folder_name_with(BackupNameFormat::Iso8601, 2, dt),
+            format!("Game 3 - {}", dt.format("%Y-%m-%dT%H-%M-%S"))
+        );
+
+        for format in BackupNameFormat::ALL {
+            let folder_name = format_backup_folder_name_with(format, 2, dt);
+            let parsed = parse_backup_folder_name(&folder_name)
+                .unwrap_or_else(|| panic!("Failed to parse {}", folder_name));
+            assert_eq!(parsed.game_number, 2, "{:?}", format);
+            assert_eq!(parsed.timestamp, Some(dt), "{:?}", format);
+            assert_eq!(parsed.counter, None);
+        }
+
+        assert_eq!(
+            parse_backup_folder_name("Game 1 - 2024-13-01T00-00-00"),
+            None
+        );
+        assert_eq!(parse_backup_folder_name("Game 1 - 17061732a0"), None);
+    }
+
+    #[test]
+    fn test_counter_backup_folder_formatting_and_parsing() {
+        let folder_name = format_counter_backup_folder_name(1, 7);
+        assert_eq!(folder_name, "Game 2 - #0007");
+
+        let parsed = parse_backup_folder_name(&folder_name).expect("Failed to parse");
+        assert_eq!(parsed.game_number, 1);
+        assert_eq!(parsed.counter, Some(7));
+        assert_eq!(parsed.timestamp, None);
+
+        // Counters past the padding width keep all their digits
+        assert_eq!(
+            format_counter_backup_folder_name(0, 12345),
+            "Game 1 - #12345"
+        );
+        assert_eq!(
+            parse_backup_folder_name("Game 1 - #12345").and_then(|i| i.counter),
+            Some(12345)
+        );
+
+        assert_eq!(parse_backup_folder_name("Game 1 - #"), None);
+        assert_eq!(parse_backup_folder_name("Game 1 - #12a"), None);
+        assert_eq!(parse_backup_folder_name("Game 1 - #-1"), None);
+    }
+
+    #[test]
+    fn test_labeled_backup_folder_names_still_parse() {
+        let base = format_counter_backup_folder_name(1, 7);
+        let labeled = with_backup_label(&base, "Final Boss");
+        assert_eq!(labeled, "Game 2 - #0007 [Final Boss]");
+        assert_eq!(
+            split_backup_label(&labeled),
+            (base.as_str(), Some("Final Boss"))
+        );
+        assert_eq!(
+            parse_backup_folder_name(&labeled).and_then(|i| i.counter),
+            Some(7)
+        );
+
+        let timestamped = "Game 3 - 14-Jun-2024 09-30-00 PM [Boss]";
+        let parsed = parse_backup_folder_name(timestamped).expect("Failed to parse");
+        assert_eq!(parsed.game_number, 2);
+        assert!(parsed.timestamp.is_some());
+
+        assert_eq!(
+            split_backup_label("Game 1 - #0001 []"),
+            ("Game 1 - #0001 []", None)
+        );
+        assert_eq!(parse_backup_folder_name("Game 1 - #0001 [x] junk"), None);
     }
 }
@@ -1,7 +1,9 @@
 // ITD ODD Save Manager by andromarces
 
 use crate::config::ConfigState;
+use crate::wrapper_launch::{launch_wrapper_plan, LaunchModeState, WrapperLaunchPlan};
 use crate::MonitorInvalidator;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -12,32 +14,115 @@ use tauri_plugin_opener::OpenerExt;
 
 const GAME_APP_ID: &str = "2239710";
 // Matches "IntoTheDead" case-insensitively. This is a heuristic and relies on the
-// executable name containing this substring. If the game executable is renamed
-// or differs significantly, detection will fail.
+// executable name containing this substring. A renamed executable can be matched
+// by setting `game_process_name` in the config.
 const PROCESS_NAME_PART: &str = "intothedead"; // Lowercase match
 
+/// Returns the lowercase process name part to look for, defaulting to the game's own.
+fn process_name_pattern(configured: Option<&str>) -> String {
+    configured
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(PROCESS_NAME_PART)
+        .to_ascii_lowercase()
+}
+
 /// Checks if the process name matches the game we are looking for.
 ///
-/// This is a heuristic that checks if the process name contains the target string (case-insensitive).
-fn is_game_process(name: &str) -> bool {
-    name.to_ascii_lowercase().contains(PROCESS_NAME_PART)
+/// This is a heuristic that checks if the process name contains `pattern`, which must
+/// already be lowercase (case-insensitive).
+fn is_game_process(name: &str, pattern: &str) -> bool {
+    name.to_ascii_lowercase().contains(pattern)
+}
+
+/// How a game launch should proceed after the Steam protocol attempt.
+#[derive(Debug, PartialEq)]
+pub(crate) enum LaunchFallback {
+    /// Steam accepted the launch; nothing more to do.
+    SteamLaunched,
+    /// Steam failed; spawn the game executable directly.
+    DirectExe(PathBuf),
+    /// Steam failed and no executable is known; report the Steam error.
+    Failed(String),
+}
+
+/// Chooses the fallback after a Steam protocol launch attempt.
+pub(crate) fn select_launch_fallback(
+    steam_result: Result<(), String>,
+    game_exe: Option<PathBuf>,
+) -> LaunchFallback {
+    match (steam_result, game_exe) {
+        (Ok(()), _) => LaunchFallback::SteamLaunched,
+        (Err(_), Some(exe)) => LaunchFallback::DirectExe(exe),
+        (Err(e), None) => LaunchFallback::Failed(e),
+    }
+}
+
+/// Finds a usable game executable for direct launches.
+///
+/// Prefers the configured `game_exe_path`, then the executable Steam handed to the
+/// manager in wrapper mode. Candidates that no longer exist on disk are ignored.
+pub(crate) fn find_game_executable(
+    configured: Option<&str>,
+    wrapper_program: Option<&str>,
+) -> Option<PathBuf> {
+    [configured, wrapper_program]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+}
+
+/// Spawns the game executable directly, bypassing Steam.
+fn launch_game_exe(exe: &Path) -> Result<(), String> {
+    launch_wrapper_plan(&WrapperLaunchPlan {
+        program: exe.as_os_str().to_os_string(),
+        args: Vec::new(),
+    })
 }
 
 /// Initiates game launch via Steam protocol.
+///
+/// If the Steam protocol launch fails (Steam not running or the protocol is not
+/// registered), falls back to spawning the game executable directly when one is known.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn launch_game<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
     log::info!("Launching game via Steam...");
-    match app
+    let steam_result = app
         .opener()
         .open_url(format!("steam://run/{}", GAME_APP_ID), None::<&str>)
-    {
-        Ok(_) => {
+        .map_err(|e| format!("Failed to launch game: {}", e));
+
+    let game_exe = if steam_result.is_err() {
+        let configured = app
+            .state::<ConfigState>()
+            .0
+            .lock()
+            .ok()
+            .and_then(|c| c.game_exe_path.clone());
+        let wrapper_program = app
+            .try_state::<LaunchModeState>()
+            .and_then(|s| s.0.game_program.clone());
+        find_game_executable(configured.as_deref(), wrapper_program.as_deref())
+    } else {
+        None
+    };
+
+    match select_launch_fallback(steam_result, game_exe) {
+        LaunchFallback::SteamLaunched => {
             log::info!("Game launch command sent successfully.");
             Ok(())
         }
-        Err(e) => {
-            log::error!("Failed to launch game: {}", e);
-            Err(format!("Failed to launch game: {}", e))
+        LaunchFallback::DirectExe(exe) => {
+            log::warn!(
+                "Steam launch failed, launching game executable directly: {:?}",
+                exe
+            );
+            launch_game_exe(&exe).inspect_err(|e| log::error!("Failed to launch game: {}", e))
+        }
+        LaunchFallback::Failed(e) => {
+            log::error!("{}", e);
+            Err(e)
         }
     }
 }
@@ -85,7 +170,8 @@ pub(crate) fn apply_monitor_tick(
 /// Initiates the background process monitor.
 ///
 /// The process scan only executes when `auto_close` is enabled. When disabled,
-/// the thread sleeps at a reduced rate without incurring scan cost.
+/// the thread sleeps at a reduced rate without incurring scan cost. The configured
+/// `game_process_name` is re-read on every scan, so a change applies without a restart.
 ///
 /// `game_was_running` is cleared atomically whenever `set_game_settings` disables
 /// `auto_close`, including fast disable/re-enable cycles that complete entirely
@@ -103,9 +189,18 @@ pub fn start_monitor<R: Runtime>(app: AppHandle<R>) {
         let mut game_was_running = false;
 
         loop {
-            let should_auto_close = {
+            let (should_auto_close, pattern) = {
                 let state = app.state::<ConfigState>();
-                state.0.lock().map(|c| c.auto_close).unwrap_or(false)
+                state
+                    .0
+                    .lock()
+                    .map(|c| {
+                        (
+                            c.auto_close,
+                            process_name_pattern(c.game_process_name.as_deref()),
+                        )
+                    })
+                    .unwrap_or_else(|_| (false, process_name_pattern(None)))
             };
 
             if should_auto_close {
@@ -114,14 +209,15 @@ pub fn start_monitor<R: Runtime>(app: AppHandle<R>) {
                     true,
                     ProcessRefreshKind::nothing(),
                 );
-                let game_running = sys
-                    .processes()
-                    .values()
-                    .any(|p| p.name().to_str().map(is_game_process).unwrap_or(false));
+                let game_running = sys.processes().values().any(|p| {
+                    p.name()
+                        .to_str()
+                        .is_some_and(|name| is_game_process(name, &pattern))
+                });
 
                 match apply_monitor_tick(&mut game_was_running, game_running, &invalidator) {
                     MonitorAction::GameDetected => {
-                        log::info!("Game process detected: {}", PROCESS_NAME_PART);
+                        log::info!("Game process detected: {}", pattern);
                     }
                     MonitorAction::GameExited => {
                         log::info!("Game process exited.");
@@ -143,7 +239,10 @@ pub fn start_monitor<R: Runtime>(app: AppHandle<R>) {
 
 #[cfg(test)]
 mod tests {
-    use super::{apply_monitor_tick, is_game_process, MonitorAction};
+    use super::{
+        apply_monitor_tick, find_game_executable, is_game_process, process_name_pattern,
+        select_launch_fallback, LaunchFallback, MonitorAction, PROCESS_NAME_PART,
+    };
     use crate::config::signal_invalidator_if_disabled;
     use crate::MonitorInvalidator;
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -156,14 +255,26 @@ mod tests {
     /// Tests that process matching is case-insensitive.
     #[test]
     fn test_is_game_process_case_insensitive() {
-        assert!(is_game_process("IntoTheDead.exe"));
-        assert!(is_game_process("intothedead"));
+        assert!(is_game_process("IntoTheDead.exe", PROCESS_NAME_PART));
+        assert!(is_game_process("intothedead", PROCESS_NAME_PART));
     }
 
     /// Tests that unrelated processes are rejected.
     #[test]
     fn test_is_game_process_rejects_unrelated() {
-        assert!(!is_game_process("notepad.exe"));
+        assert!(!is_game_process("notepad.exe", PROCESS_NAME_PART));
+    }
+
+    /// Tests that a configured process name replaces the default and blank ones fall back.
+    #[test]
+    fn test_process_name_pattern_uses_configured_name() {
+        let pattern = process_name_pattern(Some(" ModdedDead "));
+        assert_eq!(pattern, "moddeddead");
+        assert!(is_game_process("ModdedDead-Win64.exe", &pattern));
+        assert!(!is_game_process("IntoTheDead.exe", &pattern));
+
+        assert_eq!(process_name_pattern(Some("  ")), PROCESS_NAME_PART);
+        assert_eq!(process_name_pattern(None), PROCESS_NAME_PART);
     }
 
     /// Game first detected returns GameDetected and sets game_was_running.
@@ -272,4 +383,60 @@ mod tests {
         assert!(!gwr);
         assert!(matches!(action, MonitorAction::GameExited));
     }
+
+    /// A successful Steam launch never falls back to the executable.
+    #[test]
+    fn test_select_launch_fallback_steam_success() {
+        let exe = std::path::PathBuf::from("Game.exe");
+        assert_eq!(
+            select_launch_fallback(Ok(()), Some(exe)),
+            LaunchFallback::SteamLaunched
+        );
+    }
+
+    /// A failed Steam launch falls back to a known executable.
+    #[test]
+    fn test_select_launch_fallback_uses_exe_on_steam_failure() {
+        let exe = std::path::PathBuf::from("Game.exe");
+        assert_eq!(
+            select_launch_fallback(
+                Err("protocol not registered".to_string()),
+                Some(exe.clone())
+            ),
+            LaunchFallback::DirectExe(exe)
+        );
+    }
+
+    /// A failed Steam launch without a known executable reports the Steam error.
+    #[test]
+    fn test_select_launch_fallback_without_exe_reports_error() {
+        assert_eq!(
+            select_launch_fallback(Err("protocol not registered".to_string()), None),
+            LaunchFallback::Failed("protocol not registered".to_string())
+        );
+    }
+
+    /// The configured path wins over the wrapper program, and missing files are skipped.
+    #[test]
+    fn test_find_game_executable_prefers_existing_configured_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured = dir.path().join("IntoTheDead.exe");
+        let wrapper = dir.path().join("Wrapper.exe");
+        std::fs::write(&wrapper, "").unwrap();
+
+        let configured_str = configured.to_string_lossy().to_string();
+        let wrapper_str = wrapper.to_string_lossy().to_string();
+
+        assert_eq!(
+            find_game_executable(Some(&configured_str), Some(&wrapper_str)),
+            Some(wrapper.clone())
+        );
+
+        std::fs::write(&configured, "").unwrap();
+        assert_eq!(
+            find_game_executable(Some(&configured_str), Some(&wrapper_str)),
+            Some(configured)
+        );
+        assert_eq!(find_game_executable(None, None), None);
+    }
 }
@@ -1,10 +1,14 @@
 // ITD ODD Save Manager by andromarces
 
+mod app_state;
 mod backup;
 mod commands;
 mod config;
+mod diagnostics;
 pub mod filename_utils;
 mod game_manager;
+mod logs;
+mod notifications;
 mod save_paths;
 mod tray;
 mod watcher;
@@ -12,11 +16,11 @@ mod window;
 mod wrapper_launch;
 
 use config::{AppConfig, ConfigState};
+use notifications::NotificationLevel;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tauri::{async_runtime, Manager};
-use tauri_plugin_notification::NotificationExt;
 use watcher::FileWatcher;
 use window::show_main_window;
 
@@ -29,15 +33,11 @@ pub struct MonitorInvalidator(pub Arc<AtomicBool>);
 
 /// Initializes the configuration, performing auto-detection if necessary.
 fn bootstrap_config(config_path: &Path) -> AppConfig {
-    #[cfg(target_os = "windows")]
     let mut config = config::load_config_from_path(config_path);
-    #[cfg(not(target_os = "windows"))]
-    let config = config::load_config_from_path(config_path);
 
-    // Auto-detect save path if not set (Windows only)
-    #[cfg(target_os = "windows")]
+    // Auto-detect save path if not set
     if config.save_path.is_none() {
-        if let Some(path) = save_paths::detect_windows_local_save_path() {
+        if let Some(path) = save_paths::detect_local_save_path() {
             let path_str = path.to_string_lossy().to_string();
             log::info!("Auto-detected save path: {}", path_str);
             config.save_path = Some(path_str);
@@ -54,11 +54,28 @@ fn bootstrap_config(config_path: &Path) -> AppConfig {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let config_path = config::get_config_path();
-    let initial_config = bootstrap_config(&config_path);
+    let mut initial_config = bootstrap_config(&config_path);
+    let relocation = config::relocate_missing_save_path(
+        &mut initial_config,
+        &config_path,
+        save_paths::detect_local_save_path,
+    );
     let watcher = FileWatcher::new();
+    config::apply_backup_settings(&initial_config, &watcher);
+    watcher.set_max_events_per_second(initial_config.max_events_per_second);
+    watcher.set_restore_suppression_seconds(initial_config.restore_suppression_seconds);
+    watcher.set_initial_scan_mode(initial_config.initial_scan_mode);
+    watcher.set_watch_recursive(initial_config.watch_recursive);
+    watcher.set_disabled_games(&initial_config.disabled_games);
+    watcher.set_periodic_backup_minutes(initial_config.periodic_backup_minutes);
+    watcher.set_params(watcher::WatcherParams {
+        debounce_seconds: initial_config.debounce_seconds,
+        limit: initial_config.effective_backup_limit(),
+    });
 
     // Check for wrapper mode (Steam Launch Options: "Manager.exe" %command%)
-    let launched_via_wrapper = wrapper_launch::maybe_launch_from_wrapper_args();
+    let launch_mode = wrapper_launch::maybe_launch_from_wrapper_args();
+    let launched_via_wrapper = launch_mode.wrapper_mode;
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -69,14 +86,22 @@ pub fn run() {
         .manage(ConfigState(std::sync::Mutex::new(initial_config.clone())))
         .manage(watcher)
         .manage(MonitorInvalidator(Arc::new(AtomicBool::new(false))))
+        .manage(backup::RestoreCancelToken::default())
+        .manage(backup::PendingRestores::default())
+        .manage(wrapper_launch::LaunchModeState(launch_mode))
+        .manage(config::SavePathRelocationState(std::sync::Mutex::new(
+            relocation,
+        )))
         .setup(move |app| {
-            // Logger setup
+            // Logger setup: the log plugin in debug builds, the in-memory sink otherwise
             if cfg!(debug_assertions) {
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
                         .level(log::LevelFilter::Info)
                         .build(),
                 )?;
+            } else {
+                logs::init()?;
             }
 
             // Tray setup
@@ -102,13 +127,27 @@ pub fn run() {
 
                 match window.hide() {
                     Ok(_) => {
-                        let _ = window
-                            .app_handle()
-                            .notification()
-                            .builder()
-                            .title("ITD ODD Save Manager")
-                            .body("App minimized into the tray")
-                            .show();
+                        let app = window.app_handle();
+                        // Leave the one-time notice unclaimed while the level hides it.
+                        let should_notify =
+                            notifications::is_enabled(app, NotificationLevel::Important)
+                                && config::claim_tray_minimize_notification(
+                                    &app.state::<ConfigState>(),
+                                    &config::get_config_path(),
+                                )
+                                .unwrap_or_else(|e| {
+                                    log::error!("Failed to record tray notification state: {}", e);
+                                    false
+                                });
+
+                        if should_notify {
+                            notifications::notify(
+                                app,
+                                NotificationLevel::Important,
+                                "ITD ODD Save Manager",
+                                "App minimized into the tray",
+                            );
+                        }
                     }
                     Err(e) => {
                         log::error!("Failed to hide window: {}", e);
@@ -118,17 +157,112 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             save_paths::detect_steam_save_paths,
+            save_paths::detect_all_save_paths_command,
             save_paths::is_auto_detection_supported,
+            logs::set_log_level_command,
+            logs::get_recent_logs_command,
             config::get_config,
             config::set_save_path,
             config::set_game_settings,
+            config::set_tray_notification_enabled,
+            config::set_never_delete_last_backup,
+            config::set_game_exe_path,
+            config::set_game_process_name,
+            config::set_mirror_directory,
+            config::set_backup_root_override,
+            config::set_naming_scheme,
+            config::set_backup_name_format,
+            config::set_hash_algorithm,
+            config::set_hash_recheck_interval,
+            config::set_trash_retention_days,
+            config::set_snapshot_before_restore,
+            config::set_restrict_restore_target,
+            config::set_cross_slot_dedup,
+            config::set_compress_backups,
+            config::set_delta_backups,
+            config::set_include_bak_files,
+            config::set_notification_level,
+            config::set_initial_scan_mode,
+            config::set_watch_recursive,
+            config::set_game_backup_enabled,
+            config::set_periodic_backup_minutes,
+            config::set_max_total_backup_bytes,
+            config::set_unlimited_backups,
+            config::set_watcher_params_command,
+            config::get_watcher_params_command,
             commands::get_backups_command,
+            commands::scan_backups_streaming_command,
+            commands::get_last_backup_time_command,
+            commands::pause_watcher_command,
+            commands::resume_watcher_command,
+            commands::get_backups_page_with_size_command,
+            commands::prepare_restore_command,
             commands::restore_backup_command,
+            commands::prepare_restore_to_slot_command,
+            commands::restore_backup_to_slot_command,
+            commands::cancel_restore_command,
+            commands::validate_restore_target_command,
+            commands::plan_restore_full_command,
             commands::toggle_backup_lock_command,
+            commands::set_backup_archived_command,
             commands::set_backup_note_command,
+            commands::set_backup_tags_command,
+            commands::toggle_backup_favorite_command,
+            commands::add_tag_batch_command,
+            commands::remove_tag_batch_command,
+            commands::compress_backup_command,
+            commands::decompress_backup_command,
+            commands::reveal_backup_in_explorer_command,
+            commands::get_backup_id_command,
+            commands::get_backup_details_command,
+            commands::read_save_metadata_command,
+            commands::verify_backup_command,
+            commands::compare_backups_command,
+            commands::verify_all_backups_command,
+            commands::audit_timestamp_consistency_command,
+            commands::get_backup_storage_stats_command,
+            commands::quarantine_backup_command,
+            commands::list_quarantined_command,
+            commands::release_from_quarantine_command,
+            commands::aggregate_backups_hash_command,
             commands::delete_backup_command,
             commands::batch_delete_backups_command,
+            commands::list_trash_command,
+            commands::restore_from_trash_command,
+            commands::rename_backup_command,
+            commands::empty_trash_command,
+            commands::preview_batch_delete_command,
+            commands::suggest_retention_command,
+            commands::find_dangling_locks_command,
+            commands::remove_dangling_locks_command,
+            commands::compact_index_command,
+            commands::find_incomplete_backups_command,
+            commands::remove_incomplete_backups_command,
+            commands::perform_backup_with_progress_command,
+            commands::trigger_manual_backup,
+            commands::backup_all_now_command,
+            commands::create_session_snapshot_command,
+            commands::prepare_session_restore_command,
+            commands::restore_session_command,
+            commands::check_clock_skew_command,
+            commands::import_legacy_saves_command,
+            commands::prime_index_command,
+            commands::rebuild_index_command,
+            commands::set_dedup_disabled_command,
+            commands::backup_activity_histogram_command,
+            commands::get_backup_time_bounds_command,
+            commands::rank_backups_by_similarity_command,
+            commands::byte_diff_count_command,
+            commands::diff_backup_against_current_command,
+            commands::diff_backup_sets_command,
+            commands::remap_central_key_command,
+            commands::get_unannotated_backups_command,
+            commands::diagnose_auto_backup_command,
+            commands::export_backup_catalog_command,
+            commands::export_app_state_command,
+            commands::import_app_state_command,
             commands::init_watcher,
+            commands::get_launch_mode_command,
             game_manager::launch_game
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,186 @@
+// ITD ODD Save Manager by andromarces
+
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Number of recent log entries kept in memory.
+const LOG_BUFFER_CAPACITY: usize = 500;
+
+/// Level filters indexed by their stored `AtomicU8` value.
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LevelFilter::Info as u8);
+static LOGGER: OnceLock<RingBufferLogger> = OnceLock::new();
+
+/// A single captured log message.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// When the message was logged (ISO 8601).
+    pub timestamp: String,
+    /// The message level, e.g. "INFO".
+    pub level: String,
+    /// The module that logged the message.
+    pub target: String,
+    /// The formatted message.
+    pub message: String,
+}
+
+/// Log sink that keeps the most recent entries in a fixed-size in-memory buffer.
+///
+/// Honors the runtime level set by `set_log_level`, so verbosity can be raised in
+/// release builds to capture the messages needed to reproduce a bug.
+pub struct RingBufferLogger {
+    entries: Mutex<VecDeque<LogEntry>>,
+    capacity: usize,
+}
+
+impl RingBufferLogger {
+    /// Creates an empty buffer holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Returns the buffered entries, oldest first.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.entries
+            .lock()
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Log for RingBufferLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Returns the current runtime log level.
+pub fn log_level() -> LevelFilter {
+    LEVELS
+        .get(LOG_LEVEL.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or(LevelFilter::Info)
+}
+
+/// Sets the runtime log level honored by the in-memory sink and the `log` macros.
+pub fn set_log_level(level: LevelFilter) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+    log::set_max_level(level);
+}
+
+/// Installs the in-memory sink as the global logger.
+///
+/// Fails if another logger is already installed, e.g. the debug-build log plugin.
+pub fn init() -> Result<(), String> {
+    let logger = LOGGER.get_or_init(|| RingBufferLogger::new(LOG_BUFFER_CAPACITY));
+    log::set_logger(logger).map_err(|e| e.to_string())?;
+    log::set_max_level(log_level());
+    Ok(())
+}
+
+/// Sets the runtime log level from its name ("off", "error", "warn", "info", "debug", "trace").
+#[tauri::command(rename_all = "snake_case")]
+pub(crate) fn set_log_level_command(level: String) -> Result<(), String> {
+    let filter =
+        LevelFilter::from_str(level.trim()).map_err(|_| format!("Unknown log level: {}", level))?;
+    set_log_level(filter);
+    log::info!("Log level set to {}", filter);
+    Ok(())
+}
+
+/// Returns the log entries captured by the in-memory sink, oldest first.
+#[tauri::command(rename_all = "snake_case")]
+pub(crate) fn get_recent_logs_command() -> Vec<LogEntry> {
+    LOGGER.get().map(|l| l.entries()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    fn record_at(logger: &RingBufferLogger, level: Level, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target("test")
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    /// Tests that messages below the runtime threshold are dropped and the rest kept.
+    #[test]
+    fn ring_buffer_honors_runtime_level() {
+        let original = log_level();
+        let logger = RingBufferLogger::new(10);
+
+        set_log_level(LevelFilter::Warn);
+        record_at(&logger, Level::Error, "error kept");
+        record_at(&logger, Level::Warn, "warn kept");
+        record_at(&logger, Level::Info, "info dropped");
+        record_at(&logger, Level::Debug, "debug dropped");
+
+        set_log_level(LevelFilter::Debug);
+        record_at(&logger, Level::Debug, "debug kept");
+        record_at(&logger, Level::Trace, "trace dropped");
+
+        set_log_level(original);
+
+        let messages: Vec<String> = logger.entries().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["error kept", "warn kept", "debug kept"]);
+    }
+
+    /// Tests that the buffer evicts the oldest entries once full.
+    #[test]
+    fn ring_buffer_evicts_oldest_when_full() {
+        let logger = RingBufferLogger::new(2);
+        for message in ["one", "two", "three"] {
+            record_at(&logger, Level::Error, message);
+        }
+
+        let messages: Vec<String> = logger.entries().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["two", "three"]);
+    }
+
+    /// Tests that level names are validated by the command.
+    #[test]
+    fn set_log_level_command_rejects_unknown_level() {
+        assert!(set_log_level_command("verbose".to_string()).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+// ITD ODD Save Manager by andromarces
+
+use crate::config::ConfigState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+/// Which desktop notifications the app shows, from none at all to every backup.
+///
+/// Each level includes the ones before it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationLevel {
+    /// No notifications.
+    Off,
+    /// Failures only, such as the watcher failing to start.
+    Errors,
+    /// Failures plus notices like minimizing to the tray or the app already running.
+    #[default]
+    Important,
+    /// Everything, including each successful backup.
+    Verbose,
+}
+
+/// Returns whether the configured notification level includes `level`.
+pub(crate) fn is_enabled<R: Runtime>(app: &AppHandle<R>, level: NotificationLevel) -> bool {
+    let configured = app
+        .try_state::<ConfigState>()
+        .and_then(|state| state.0.lock().ok().map(|c| c.notification_level))
+        .unwrap_or_default();
+    configured >= level
+}
+
+/// Shows a desktop notification when the configured level includes `level`.
+pub(crate) fn notify<R: Runtime>(
+    app: &AppHandle<R>,
+    level: NotificationLevel,
+    title: &str,
+    body: &str,
+) {
+    if is_enabled(app, level) {
+        let _ = app.notification().builder().title(title).body(body).show();
+    }
+}
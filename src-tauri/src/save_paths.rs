@@ -1,11 +1,59 @@
 // ITD ODD Save Manager by andromarces
 
+use crate::filename_utils;
+use serde::Serialize;
 use std::env;
 use std::path::{Path, PathBuf};
 
 const LOCAL_SAVE_DIR_COMPONENTS: [&str; 4] =
     ["AppData", "LocalLow", "PikPok", "IntoTheDeadOurDarkestDays"];
 
+/// Steam app id of Into the Dead: Our Darkest Days.
+const STEAM_APP_ID: &str = "2239710";
+
+/// Path from a Steam root to the user profile inside the game's Proton prefix.
+const PROTON_PROFILE_COMPONENTS: [&str; 4] = ["pfx", "drive_c", "users", "steamuser"];
+
+/// Path from the home directory to the game's save directory on macOS.
+const MACOS_SAVE_DIR_COMPONENTS: [&str; 4] = [
+    "Library",
+    "Application Support",
+    "PikPok",
+    "IntoTheDeadOurDarkestDays",
+];
+
+/// Steam install locations below the home directory on Linux.
+const LINUX_STEAM_ROOTS: [&str; 3] = [
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+/// Which detector found a save directory candidate.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SavePathSource {
+    /// `AppData/LocalLow` under the Windows user profile.
+    WindowsLocalLow,
+    /// The game's folder inside a Proton or Wine prefix on Linux.
+    LinuxPrefix,
+    /// `Library/Application Support` under the macOS home directory.
+    MacosApplicationSupport,
+    /// The game's Proton prefix inside a Steam install.
+    SteamProton,
+    /// The Steam Cloud folder of a Steam account.
+    SteamUserdata,
+}
+
+/// A save directory found during detection.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct SavePathCandidate {
+    pub path: String,
+    pub source: SavePathSource,
+    /// Whether the directory currently holds any `gamesave_*.sav` files.
+    pub has_saves: bool,
+}
+
 /// Builds the expected local save path from a user profile directory.
 fn local_save_path_from_profile(user_profile: &Path) -> PathBuf {
     let mut path = PathBuf::from(user_profile);
@@ -26,19 +74,162 @@ pub(crate) fn detect_windows_local_save_path() -> Option<PathBuf> {
     }
 }
 
+/// Detects the local save path inside a Proton or Wine prefix using HOME and USER.
+///
+/// Steam's Proton prefixes are checked first, then the default `~/.wine` prefix.
+pub(crate) fn detect_linux_local_save_path() -> Option<PathBuf> {
+    let home = PathBuf::from(env::var_os("HOME")?);
+    let user = env::var("USER").ok();
+    linux_local_save_path_from_home(&home, user.as_deref())
+}
+
+/// Finds the first existing Proton or Wine prefix save directory below a home directory.
+fn linux_local_save_path_from_home(home: &Path, user: Option<&str>) -> Option<PathBuf> {
+    let proton_saves = linux_steam_roots(home)
+        .into_iter()
+        .map(|root| proton_save_path(&root));
+    let wine_save = user.map(|user| {
+        local_save_path_from_profile(&home.join(".wine").join("drive_c").join("users").join(user))
+    });
+    proton_saves.chain(wine_save).find(|path| path.is_dir())
+}
+
+/// Detects the local save path under `~/Library/Application Support` using HOME.
+pub(crate) fn detect_macos_local_save_path() -> Option<PathBuf> {
+    let home = PathBuf::from(env::var_os("HOME")?);
+    let path = macos_save_path_from_home(&home);
+    if path.is_dir() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Builds the expected macOS save path from a home directory.
+fn macos_save_path_from_home(home: &Path) -> PathBuf {
+    let mut path = PathBuf::from(home);
+    for component in MACOS_SAVE_DIR_COMPONENTS {
+        path = path.join(component);
+    }
+    path
+}
+
+/// Detects the local save path with the detector for the current platform.
+pub(crate) fn detect_local_save_path() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        detect_windows_local_save_path()
+    } else if cfg!(target_os = "linux") {
+        detect_linux_local_save_path()
+    } else if cfg!(target_os = "macos") {
+        detect_macos_local_save_path()
+    } else {
+        None
+    }
+}
+
+/// Lists the directories Steam may be installed in on this platform.
+///
+/// Only roots that exist are returned. On Linux this covers the native and flatpak
+/// installs; `~/.steam/steam` is usually a link to `~/.local/share/Steam`, so callers
+/// should expect the same install to appear more than once.
+fn candidate_steam_roots() -> Vec<PathBuf> {
+    let roots: Vec<PathBuf> = if cfg!(target_os = "windows") {
+        ["ProgramFiles(x86)", "ProgramFiles"]
+            .iter()
+            .filter_map(env::var_os)
+            .map(|dir| PathBuf::from(dir).join("Steam"))
+            .collect()
+    } else if cfg!(target_os = "linux") {
+        env::var_os("HOME")
+            .map(|home| linux_steam_roots(Path::new(&home)))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    roots.into_iter().filter(|root| root.is_dir()).collect()
+}
+
+/// Builds the Linux Steam install locations below a home directory.
+fn linux_steam_roots(home: &Path) -> Vec<PathBuf> {
+    LINUX_STEAM_ROOTS
+        .iter()
+        .map(|root| home.join(root))
+        .collect()
+}
+
+/// Builds the save path inside the game's Proton prefix of a Steam install.
+fn proton_save_path(steam_root: &Path) -> PathBuf {
+    let mut proton_profile = steam_root
+        .join("steamapps")
+        .join("compatdata")
+        .join(STEAM_APP_ID);
+    for component in PROTON_PROFILE_COMPONENTS {
+        proton_profile = proton_profile.join(component);
+    }
+    local_save_path_from_profile(&proton_profile)
+}
+
+/// Finds the game's save directories inside a Steam install.
+///
+/// Checks the Proton prefix the game runs in on Linux and the Steam Cloud folder
+/// (`userdata/<account>/2239710/remote`) of every account.
+fn find_steam_save_dirs(steam_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    let proton_save = proton_save_path(steam_root);
+    if proton_save.is_dir() {
+        dirs.push(proton_save);
+    }
+
+    if let Ok(accounts) = std::fs::read_dir(steam_root.join("userdata")) {
+        let mut remotes: Vec<PathBuf> = accounts
+            .flatten()
+            .map(|account| account.path().join(STEAM_APP_ID).join("remote"))
+            .filter(|remote| remote.is_dir())
+            .collect();
+        remotes.sort();
+        dirs.extend(remotes);
+    }
+
+    dirs
+}
+
+/// Reports whether `path` lies inside the game's Steam Cloud folder
+/// (`userdata/<account>/2239710/remote`), which Steam syncs and may clear.
+pub(crate) fn is_steam_cloud_path(path: &Path) -> bool {
+    let parts: Vec<_> = path.components().map(|c| c.as_os_str()).collect();
+    parts.windows(4).any(|window| {
+        let account = window[1].to_string_lossy();
+        window[0].eq_ignore_ascii_case("userdata")
+            && !account.is_empty()
+            && account.chars().all(|c| c.is_ascii_digit())
+            && window[2] == STEAM_APP_ID
+            && window[3].eq_ignore_ascii_case("remote")
+    })
+}
+
 /// Detects the save directory for the game and returns it as a string.
 #[tauri::command(rename_all = "snake_case")]
 pub(crate) async fn detect_steam_save_paths() -> Vec<String> {
     log::info!("Save path detection started");
 
-    if !cfg!(target_os = "windows") {
-        log::warn!("Save path detection is only supported on Windows in this build");
+    if !is_auto_detection_supported() {
+        log::warn!(
+            "Save path detection is only supported on Windows, Linux and macOS in this build"
+        );
         return Vec::new();
     }
 
-    let results = detect_windows_local_save_path()
-        .map(|path| vec![path])
-        .unwrap_or_default();
+    let mut results: Vec<PathBuf> = detect_local_save_path().into_iter().collect();
+    for root in candidate_steam_roots() {
+        for dir in find_steam_save_dirs(&root) {
+            // Linked Steam roots resolve to the same save directory.
+            let resolved = dir.canonicalize().unwrap_or(dir);
+            if !results.contains(&resolved) {
+                results.push(resolved);
+            }
+        }
+    }
 
     log::info!(
         "Save path detection completed with {} result(s)",
@@ -51,17 +242,100 @@ pub(crate) async fn detect_steam_save_paths() -> Vec<String> {
         .collect()
 }
 
+/// Runs every detector and returns the save directories found, best first.
+///
+/// Directories that hold saves come before empty ones; otherwise the local
+/// detector's result comes first, followed by Steam installs.
+#[tauri::command(rename_all = "snake_case")]
+pub(crate) async fn detect_all_save_paths_command() -> Vec<SavePathCandidate> {
+    if !is_auto_detection_supported() {
+        return Vec::new();
+    }
+
+    let local_source = if cfg!(target_os = "windows") {
+        SavePathSource::WindowsLocalLow
+    } else if cfg!(target_os = "linux") {
+        SavePathSource::LinuxPrefix
+    } else {
+        SavePathSource::MacosApplicationSupport
+    };
+    let mut found: Vec<(PathBuf, SavePathSource)> = detect_local_save_path()
+        .map(|path| (path, local_source))
+        .into_iter()
+        .collect();
+    for root in candidate_steam_roots() {
+        let proton_save = proton_save_path(&root);
+        for dir in find_steam_save_dirs(&root) {
+            let source = if dir == proton_save {
+                SavePathSource::SteamProton
+            } else {
+                SavePathSource::SteamUserdata
+            };
+            found.push((dir, source));
+        }
+    }
+
+    let candidates = rank_save_path_candidates(found);
+    log::info!(
+        "Detected {} save path candidate(s) across all detectors",
+        candidates.len()
+    );
+    candidates
+}
+
+/// Removes duplicate directories and moves the ones holding saves to the front.
+///
+/// The first detector to report a directory keeps it, and the order is otherwise
+/// preserved.
+fn rank_save_path_candidates(found: Vec<(PathBuf, SavePathSource)>) -> Vec<SavePathCandidate> {
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut candidates: Vec<SavePathCandidate> = Vec::new();
+    for (dir, source) in found {
+        // Linked Steam roots resolve to the same save directory.
+        let resolved = dir.canonicalize().unwrap_or(dir);
+        if seen.contains(&resolved) {
+            continue;
+        }
+        candidates.push(SavePathCandidate {
+            path: resolved.to_string_lossy().to_string(),
+            source,
+            has_saves: contains_game_saves(&resolved),
+        });
+        seen.push(resolved);
+    }
+    // Stable, so candidates with equal standing keep their detector order.
+    candidates.sort_by_key(|candidate| !candidate.has_saves);
+    candidates
+}
+
+/// Reports whether a directory holds at least one main `gamesave_*.sav` file.
+fn contains_game_saves(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten().any(|entry| {
+                entry.path().is_file()
+                    && filename_utils::parse_filename(&entry.file_name().to_string_lossy())
+                        .is_some_and(|info| !info.is_bak)
+            })
+        })
+        .unwrap_or(false)
+}
+
 /// Reports whether auto-detection is supported on this platform.
 #[tauri::command(rename_all = "snake_case")]
 pub(crate) fn is_auto_detection_supported() -> bool {
-    cfg!(target_os = "windows")
+    cfg!(any(
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "macos"
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::Mutex;
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
     use tauri::async_runtime::block_on;
 
     static ENV_MUTEX: Mutex<()> = Mutex::new(());
@@ -100,8 +374,153 @@ mod tests {
         assert!(detected.is_none());
     }
 
-    /// Verifies that detection is disabled for non-Windows builds.
-    #[cfg(not(target_os = "windows"))]
+    /// Verifies that the Linux Steam roots include the native and flatpak installs.
+    #[test]
+    fn linux_steam_roots_cover_native_and_flatpak_installs() {
+        let home = Path::new("/home/player");
+        let roots = linux_steam_roots(home);
+
+        assert_eq!(
+            roots,
+            vec![
+                home.join(".steam/steam"),
+                home.join(".local/share/Steam"),
+                home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+            ]
+        );
+    }
+
+    /// Verifies that Proton prefix and Steam Cloud save directories are found.
+    #[test]
+    fn find_steam_save_dirs_returns_proton_and_cloud_dirs() {
+        let temp_dir = tempfile::tempdir().expect("temp directory created");
+        let steam_root = temp_dir.path();
+        let proton_save = steam_root.join(
+            "steamapps/compatdata/2239710/pfx/drive_c/users/steamuser/AppData/LocalLow/PikPok/IntoTheDeadOurDarkestDays",
+        );
+        let cloud_save = steam_root.join("userdata/12345/2239710/remote");
+        std::fs::create_dir_all(&proton_save).expect("proton save directory created");
+        std::fs::create_dir_all(&cloud_save).expect("cloud save directory created");
+        std::fs::create_dir_all(steam_root.join("userdata/67890/440/remote"))
+            .expect("other game directory created");
+
+        assert_eq!(
+            find_steam_save_dirs(steam_root),
+            vec![proton_save, cloud_save]
+        );
+    }
+
+    /// Verifies that only the game's Steam Cloud folder counts as a cloud path.
+    #[test]
+    fn is_steam_cloud_path_matches_game_remote_folder() {
+        let steam_root = Path::new("/home/player/.local/share/Steam");
+
+        assert!(is_steam_cloud_path(
+            &steam_root.join("userdata/12345/2239710/remote")
+        ));
+        assert!(is_steam_cloud_path(
+            &steam_root.join("userdata/12345/2239710/Remote/profiles")
+        ));
+        assert!(!is_steam_cloud_path(
+            &steam_root.join("userdata/12345/440/remote")
+        ));
+        assert!(!is_steam_cloud_path(&proton_save_path(steam_root)));
+    }
+
+    /// Verifies that a Steam install without the game yields no save directories.
+    #[test]
+    fn find_steam_save_dirs_returns_empty_without_game() {
+        let temp_dir = tempfile::tempdir().expect("temp directory created");
+
+        assert!(find_steam_save_dirs(temp_dir.path()).is_empty());
+    }
+
+    /// Verifies that a Proton prefix save directory is preferred over a Wine prefix one.
+    #[test]
+    fn linux_local_save_path_prefers_proton_prefix() {
+        let temp_dir = tempfile::tempdir().expect("temp directory created");
+        let home = temp_dir.path();
+        let proton_save = proton_save_path(&home.join(".local/share/Steam"));
+        let wine_save = local_save_path_from_profile(&home.join(".wine/drive_c/users/player"));
+        std::fs::create_dir_all(&wine_save).expect("wine save directory created");
+
+        assert_eq!(
+            linux_local_save_path_from_home(home, Some("player")).as_deref(),
+            Some(wine_save.as_path())
+        );
+
+        std::fs::create_dir_all(&proton_save).expect("proton save directory created");
+
+        assert_eq!(
+            linux_local_save_path_from_home(home, Some("player")).as_deref(),
+            Some(proton_save.as_path())
+        );
+    }
+
+    /// Verifies that Linux detection fails when no prefix holds the save directory.
+    #[test]
+    fn linux_local_save_path_returns_none_when_missing() {
+        let temp_dir = tempfile::tempdir().expect("temp directory created");
+
+        assert!(linux_local_save_path_from_home(temp_dir.path(), Some("player")).is_none());
+    }
+
+    /// Verifies that the macOS save path sits under Application Support.
+    #[test]
+    fn macos_save_path_is_under_application_support() {
+        let home = Path::new("/Users/player");
+
+        assert_eq!(
+            macos_save_path_from_home(home),
+            home.join("Library/Application Support/PikPok/IntoTheDeadOurDarkestDays")
+        );
+    }
+
+    /// Verifies that candidates are de-duplicated and directories with saves come first.
+    #[test]
+    fn rank_save_path_candidates_dedups_and_puts_saves_first() {
+        let temp_dir = tempfile::tempdir().expect("temp directory created");
+        let empty = temp_dir.path().join("empty");
+        let only_bak = temp_dir.path().join("only_bak");
+        let with_saves = temp_dir.path().join("with_saves");
+        for dir in [&empty, &only_bak, &with_saves] {
+            std::fs::create_dir_all(dir).expect("candidate directory created");
+        }
+        std::fs::write(only_bak.join("gamesave_0.sav.bak"), "bak").expect("bak written");
+        std::fs::write(with_saves.join("gamesave_1.sav"), "save").expect("save written");
+
+        let ranked = rank_save_path_candidates(vec![
+            (empty.clone(), SavePathSource::LinuxPrefix),
+            (only_bak.clone(), SavePathSource::SteamProton),
+            (with_saves.clone(), SavePathSource::SteamUserdata),
+            (empty.join("..").join("empty"), SavePathSource::SteamProton),
+        ]);
+
+        let resolved = |dir: &Path| dir.canonicalize().unwrap().to_string_lossy().to_string();
+        assert_eq!(
+            ranked,
+            vec![
+                SavePathCandidate {
+                    path: resolved(&with_saves),
+                    source: SavePathSource::SteamUserdata,
+                    has_saves: true,
+                },
+                SavePathCandidate {
+                    path: resolved(&empty),
+                    source: SavePathSource::LinuxPrefix,
+                    has_saves: false,
+                },
+                SavePathCandidate {
+                    path: resolved(&only_bak),
+                    source: SavePathSource::SteamProton,
+                    has_saves: false,
+                },
+            ]
+        );
+    }
+
+    /// Verifies that detection is disabled on platforms without a known save location.
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
     #[test]
     fn detect_steam_save_paths_returns_empty_on_non_windows() {
         let results = block_on(detect_steam_save_paths());
@@ -116,8 +535,15 @@ mod tests {
         assert!(is_auto_detection_supported());
     }
 
-    /// Verifies that auto-detection is flagged as unsupported on non-Windows builds.
-    #[cfg(not(target_os = "windows"))]
+    /// Verifies that auto-detection is flagged as supported on Linux and macOS.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn is_auto_detection_supported_returns_true_on_linux_and_macos() {
+        assert!(is_auto_detection_supported());
+    }
+
+    /// Verifies that auto-detection is flagged as unsupported on other platforms.
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
     #[test]
     fn is_auto_detection_supported_returns_false_on_non_windows() {
         assert!(!is_auto_detection_supported());
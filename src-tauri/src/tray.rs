@@ -1,5 +1,7 @@
 use crate::game_manager;
 use crate::window::show_main_window;
+use chrono::{DateTime, Local};
+use std::time::SystemTime;
 use tauri::menu::{Menu, MenuItem};
 use tauri::tray::{TrayIconBuilder, TrayIconEvent};
 use tauri::{async_runtime, AppHandle, Manager, Runtime};
@@ -7,6 +9,9 @@ use tauri::{async_runtime, AppHandle, Manager, Runtime};
 // Store tray icon to prevent it from being dropped
 pub struct TrayState<R: Runtime>(pub tauri::tray::TrayIcon<R>);
 
+// Store the status item so its text can follow the watcher state
+pub struct TrayStatusItem<R: Runtime>(pub MenuItem<R>);
+
 /// Determines whether a tray icon event should show and focus the main window.
 fn should_show_main_window_from_tray_event(event: &TrayIconEvent) -> bool {
     match event {
@@ -25,9 +30,49 @@ fn tray_tooltip_text(product_name: Option<&str>) -> String {
         .unwrap_or_else(|| "ITD ODD Save Manager".to_string())
 }
 
+/// Appends the time of the last backup to the tray tooltip, when there is one.
+fn tooltip_with_last_backup(base: String, last_backup: Option<DateTime<Local>>) -> String {
+    match last_backup {
+        Some(time) => format!("{}\nLast backup: {}", base, time.format("%H:%M")),
+        None => base,
+    }
+}
+
+/// Updates the tray tooltip with the time of the watcher's last backup.
+pub fn set_last_backup_time<R: Runtime>(app: &AppHandle<R>, last_backup: SystemTime) {
+    let Some(tray) = app.try_state::<TrayState<R>>() else {
+        return;
+    };
+    let tooltip = tooltip_with_last_backup(
+        tray_tooltip_text(app.config().product_name.as_deref()),
+        Some(last_backup.into()),
+    );
+    if let Err(e) = tray.0.set_tooltip(Some(tooltip)) {
+        log::error!("Failed to update tray tooltip: {}", e);
+    }
+}
+
+/// Returns the status menu text for the watcher state.
+fn status_text(paused: bool) -> &'static str {
+    if paused {
+        "Status: Paused"
+    } else {
+        "Status: Monitoring"
+    }
+}
+
+/// Updates the tray status item to show whether auto-backups are paused.
+pub fn set_watcher_status<R: Runtime>(app: &AppHandle<R>, paused: bool) {
+    if let Some(item) = app.try_state::<TrayStatusItem<R>>() {
+        if let Err(e) = item.0.set_text(status_text(paused)) {
+            log::error!("Failed to update tray status: {}", e);
+        }
+    }
+}
+
 /// Creates and configures the system tray icon and menu.
 pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
-    let status_i = MenuItem::with_id(app, "status", "Status: Monitoring", false, None::<&str>)?;
+    let status_i = MenuItem::with_id(app, "status", status_text(false), false, None::<&str>)?;
     let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
     let open_i = MenuItem::with_id(app, "open", "Open", true, None::<&str>)?;
     let launch_i = MenuItem::with_id(app, "launch", "Launch Game", true, None::<&str>)?;
@@ -65,12 +110,14 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         .build(app)?;
 
     app.manage(TrayState(tray));
+    app.manage(TrayStatusItem(status_i));
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use tauri::tray::{MouseButton, MouseButtonState, TrayIconId};
 
     /// Helper to create a click event.
@@ -122,6 +169,26 @@ mod tests {
         assert!(!should_show_main_window_from_tray_event(&event));
     }
 
+    /// Verifies that the tooltip shows the last backup time only once there is one.
+    #[test]
+    fn tray_tooltip_appends_last_backup_time() {
+        let base = "ITD ODD Save Manager".to_string();
+        assert_eq!(tooltip_with_last_backup(base.clone(), None), base);
+
+        let time = Local.with_ymd_and_hms(2024, 1, 1, 9, 5, 0).unwrap();
+        assert_eq!(
+            tooltip_with_last_backup(base, Some(time)),
+            "ITD ODD Save Manager\nLast backup: 09:05"
+        );
+    }
+
+    /// Verifies that the status item text reflects a paused watcher.
+    #[test]
+    fn tray_status_text_reflects_pause() {
+        assert_eq!(status_text(false), "Status: Monitoring");
+        assert_eq!(status_text(true), "Status: Paused");
+    }
+
     /// Verifies that the tray tooltip prefers the configured product name.
     #[test]
     fn tray_tooltip_uses_product_name_when_available() {
@@ -1,19 +1,245 @@
 // ITD ODD Save Manager by andromarces
 
-use crate::backup::{ensure_backup_root, load_index, perform_backup_for_game_internal, save_index};
+use crate::backup::common::BACKUP_DIR_NAME;
+use crate::backup::{
+    claim_backup_dir, discover_save_slots, ensure_backup_root, index_live_saves, load_index,
+    perform_backup_for_game_internal, release_backup_dir, save_index, BackupOptions, BackupOutcome,
+    BackupSettings,
+};
 use crate::filename_utils;
 use log::{error, info};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
-// Default debounce duration to coalesce rapid writes
-const DEBOUNCE_DURATION: Duration = Duration::from_secs(2);
+// Default debounce duration in seconds to coalesce rapid writes
+pub(crate) const DEFAULT_DEBOUNCE_SECONDS: u64 = 2;
+
+// Default cap on how many event wakes the debounce loop handles per second
+pub(crate) const DEFAULT_MAX_EVENTS_PER_SECOND: u32 = 20;
+
+// Default number of seconds after a restore during which save changes are ignored
+pub(crate) const DEFAULT_RESTORE_SUPPRESSION_SECONDS: u64 = 5;
+
+// How often a paused debounce loop with queued work checks whether it was resumed
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// How long an idle debounce loop waits for events before checking its settings again
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What the initial scan does with saves that already exist when watching starts.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialScanMode {
+    /// Backs up every existing save that is not already backed up.
+    #[default]
+    BackupAll,
+    /// Only records existing saves in the dedup index; later changes are backed up.
+    IndexOnly,
+}
+
+/// Tunable watcher parameters that can be changed while the watcher runs.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct WatcherParams {
+    /// Quiet period after the last change before backups are taken.
+    pub debounce_seconds: u64,
+    /// Backup limit per game passed to enforcement (0 = unlimited).
+    pub limit: usize,
+}
+
+/// Changed game slots awaiting backup, keyed by the directory holding their saves.
+type PendingSaves = HashMap<PathBuf, HashSet<u32>>;
+
+/// Watched directory and the saves still pending there when a loop stopped.
+type PendingHandover = (PathBuf, PendingSaves);
+
+/// Callback told that the watched directory disappeared, with its path.
+pub(crate) type PathMissingCallback = Arc<dyn Fn(PathBuf) + Send + Sync + 'static>;
+
+/// Callback told what each watcher backup run did.
+pub(crate) type ActivityCallback = Arc<dyn Fn(WatcherActivity) + Send + Sync + 'static>;
+
+/// Counts of what a watcher backup run did, sent with the `watcher-activity` event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WatcherActivity {
+    /// Backups written.
+    pub created: usize,
+    /// Slots skipped because their save matched an existing backup.
+    pub skipped_duplicate: usize,
+    /// Slots skipped because their main save was missing.
+    pub skipped_no_save: usize,
+    /// Slots skipped because their main save was still being written.
+    pub skipped_unstable: usize,
+}
+
+impl WatcherActivity {
+    /// Counts one backup attempt.
+    fn record(&mut self, outcome: &BackupOutcome) {
+        match outcome {
+            BackupOutcome::Created(_) => self.created += 1,
+            BackupOutcome::SkippedDuplicate => self.skipped_duplicate += 1,
+            BackupOutcome::SkippedNoSave => self.skipped_no_save += 1,
+            BackupOutcome::SkippedUnstable => self.skipped_unstable += 1,
+        }
+    }
+
+    /// Returns true if no slot was backed up or skipped.
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::ops::AddAssign for WatcherActivity {
+    fn add_assign(&mut self, other: Self) {
+        self.created += other.created;
+        self.skipped_duplicate += other.skipped_duplicate;
+        self.skipped_no_save += other.skipped_no_save;
+        self.skipped_unstable += other.skipped_unstable;
+    }
+}
+
+/// Shared handles the debounce loop reads on every iteration.
+#[derive(Clone)]
+struct LiveParams {
+    debounce_seconds: Arc<AtomicU64>,
+    limit: Arc<AtomicUsize>,
+    max_events_per_second: Arc<AtomicU32>,
+    /// Count of file system events received since the watcher was created.
+    events_received: Arc<AtomicU64>,
+    /// Set while a restore is writing into the save directory.
+    restore_in_progress: Arc<AtomicBool>,
+    /// When the most recent restore finished.
+    restore_finished_at: Arc<Mutex<Option<Instant>>>,
+    /// How long after a restore finishes its file changes are still ignored.
+    restore_suppression_seconds: Arc<AtomicU64>,
+    /// Whether the initial scan only indexes existing saves instead of backing them up.
+    index_only_initial_scan: Arc<AtomicBool>,
+    /// Games still pending when a debounce loop stopped, with the directory they belong to.
+    handover: Arc<Mutex<Option<PendingHandover>>>,
+    /// Whether subdirectories of the save directory are watched and scanned too.
+    watch_recursive: Arc<AtomicBool>,
+    /// Set while auto-backups are paused; changes queue up until resumed.
+    paused: Arc<AtomicBool>,
+    /// When the watcher last created at least one backup, kept across restarts.
+    last_backup: Arc<Mutex<Option<SystemTime>>>,
+    /// Game slots the watcher never backs up.
+    disabled_games: Arc<Mutex<HashSet<u32>>>,
+    /// Told the counts of every backup run, kept across restarts.
+    on_activity: Arc<Mutex<Option<ActivityCallback>>>,
+    /// Seconds between scans that run regardless of file events; `0` disables them.
+    periodic_backup_seconds: Arc<AtomicU64>,
+    /// Backup behavior applied to every backup the watcher creates.
+    backup_settings: Arc<Mutex<BackupSettings>>,
+}
+
+impl LiveParams {
+    fn debounce(&self) -> Duration {
+        Duration::from_secs(self.debounce_seconds.load(Ordering::Relaxed))
+    }
+
+    fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    fn paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    fn periodic_interval(&self) -> Option<Duration> {
+        match self.periodic_backup_seconds.load(Ordering::Relaxed) {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        }
+    }
+
+    fn disabled_games(&self) -> HashSet<u32> {
+        self.disabled_games
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    fn backup_settings(&self) -> BackupSettings {
+        self.backup_settings
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    fn initial_scan_mode(&self) -> InitialScanMode {
+        if self.index_only_initial_scan.load(Ordering::Relaxed) {
+            InitialScanMode::IndexOnly
+        } else {
+            InitialScanMode::BackupAll
+        }
+    }
+
+    /// Returns true while a restore is running or finished within the suppression window.
+    fn restore_suppressed(&self) -> bool {
+        if self.restore_in_progress.load(Ordering::SeqCst) {
+            return true;
+        }
+        let window = Duration::from_secs(self.restore_suppression_seconds.load(Ordering::Relaxed));
+        self.restore_finished_at
+            .lock()
+            .ok()
+            .and_then(|guard| *guard)
+            .is_some_and(|finished| finished.elapsed() < window)
+    }
+
+    /// Records a successful backup run and notifies the caller's callback.
+    fn backups_created(&self, on_backup: Option<&Arc<dyn Fn() + Send + Sync + 'static>>) {
+        if let Ok(mut guard) = self.last_backup.lock() {
+            *guard = Some(SystemTime::now());
+        }
+        if let Some(cb) = on_backup {
+            cb();
+        }
+    }
+
+    /// Reports a finished backup run to the activity callback and, if it created
+    /// backups, to `on_backup`.
+    fn report_activity(
+        &self,
+        activity: WatcherActivity,
+        on_backup: Option<&Arc<dyn Fn() + Send + Sync + 'static>>,
+    ) {
+        if activity.created > 0 {
+            self.backups_created(on_backup);
+        }
+        if activity.is_empty() {
+            return;
+        }
+        let on_activity = self.on_activity.lock().ok().and_then(|guard| guard.clone());
+        if let Some(cb) = on_activity {
+            cb(activity);
+        }
+    }
+
+    /// Stores games a stopping loop did not get to back up for the next loop to pick up.
+    fn hand_over_pending(&self, save_dir: &Path, pending_games: PendingSaves) {
+        if let Ok(mut guard) = self.handover.lock() {
+            *guard = Some((save_dir.to_path_buf(), pending_games));
+        }
+    }
+
+    /// Takes the games handed over by the previous loop if it watched the same directory.
+    fn take_handed_over(&self, save_dir: &Path) -> PendingSaves {
+        self.handover
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take())
+            .filter(|(dir, _)| dir == save_dir)
+            .map(|(_, games)| games)
+            .unwrap_or_default()
+    }
+}
 
 /// Watches for file system changes in the save directory.
 #[derive(Clone)]
@@ -21,6 +247,9 @@ pub struct FileWatcher {
     watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
     shutdown: Arc<Mutex<Arc<AtomicBool>>>,
     thread_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    params: LiveParams,
+    owned_dir: Arc<Mutex<Option<PathBuf>>>,
+    owner_conflict: Arc<Mutex<Option<u32>>>,
 }
 
 impl FileWatcher {
@@ -30,17 +259,205 @@ impl FileWatcher {
             watcher: Arc::new(Mutex::new(None)),
             shutdown: Arc::new(Mutex::new(Arc::new(AtomicBool::new(false)))),
             thread_handle: Arc::new(Mutex::new(None)),
+            params: LiveParams {
+                debounce_seconds: Arc::new(AtomicU64::new(DEFAULT_DEBOUNCE_SECONDS)),
+                limit: Arc::new(AtomicUsize::new(0)),
+                max_events_per_second: Arc::new(AtomicU32::new(DEFAULT_MAX_EVENTS_PER_SECOND)),
+                events_received: Arc::new(AtomicU64::new(0)),
+                restore_in_progress: Arc::new(AtomicBool::new(false)),
+                restore_finished_at: Arc::new(Mutex::new(None)),
+                restore_suppression_seconds: Arc::new(AtomicU64::new(
+                    DEFAULT_RESTORE_SUPPRESSION_SECONDS,
+                )),
+                index_only_initial_scan: Arc::new(AtomicBool::new(false)),
+                handover: Arc::new(Mutex::new(None)),
+                watch_recursive: Arc::new(AtomicBool::new(false)),
+                paused: Arc::new(AtomicBool::new(false)),
+                last_backup: Arc::new(Mutex::new(None)),
+                disabled_games: Arc::new(Mutex::new(HashSet::new())),
+                on_activity: Arc::new(Mutex::new(None)),
+                periodic_backup_seconds: Arc::new(AtomicU64::new(0)),
+                backup_settings: Arc::new(Mutex::new(BackupSettings::default())),
+            },
+            owned_dir: Arc::new(Mutex::new(None)),
+            owner_conflict: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sets how many event wakes per second the debounce loop handles before coalescing.
+    ///
+    /// Takes effect immediately, including for a running loop. `0` disables the limit.
+    pub fn set_max_events_per_second(&self, max_events_per_second: u32) {
+        self.params
+            .max_events_per_second
+            .store(max_events_per_second, Ordering::Relaxed);
+    }
+
+    /// Sets what the initial scan does with existing saves on the next watcher start.
+    pub fn set_initial_scan_mode(&self, mode: InitialScanMode) {
+        self.params
+            .index_only_initial_scan
+            .store(mode == InitialScanMode::IndexOnly, Ordering::Relaxed);
+    }
+
+    /// Sets whether the next watcher start also watches and scans subdirectories.
+    ///
+    /// Saves found in a subdirectory are backed up into that subdirectory's own `.backups`.
+    pub fn set_watch_recursive(&self, recursive: bool) {
+        self.params
+            .watch_recursive
+            .store(recursive, Ordering::Relaxed);
+    }
+
+    /// Sets how often the save directory is scanned for changes without a file event.
+    ///
+    /// Takes effect within a minute, including for a running loop. `None` (or `0`)
+    /// relies on file events alone.
+    pub fn set_periodic_backup_minutes(&self, minutes: Option<u32>) {
+        let seconds = minutes.map_or(0, |minutes| u64::from(minutes) * 60);
+        self.params
+            .periodic_backup_seconds
+            .store(seconds, Ordering::Relaxed);
+    }
+
+    /// Sets the game slots the watcher skips, including for a running loop.
+    ///
+    /// Changes to disabled slots are ignored; manual backups are unaffected.
+    pub fn set_disabled_games(&self, game_numbers: &[u32]) {
+        if let Ok(mut guard) = self.params.disabled_games.lock() {
+            *guard = game_numbers.iter().copied().collect();
+        }
+    }
+
+    /// Sets the backup behavior used by watcher backups, including for a running loop.
+    pub fn set_backup_settings(&self, settings: BackupSettings) {
+        if let Ok(mut guard) = self.params.backup_settings.lock() {
+            *guard = settings;
+        }
+    }
+
+    /// Sets the callback told how many backups each watcher run created or skipped.
+    ///
+    /// Applies to the running loop and to later restarts.
+    pub fn set_on_activity(&self, on_activity: Option<ActivityCallback>) {
+        if let Ok(mut guard) = self.params.on_activity.lock() {
+            *guard = on_activity;
+        }
+    }
+
+    /// Pauses or resumes auto-backups without stopping the watch.
+    ///
+    /// While paused, save changes keep being collected but no backups are taken;
+    /// on resume the queued changes are backed up after the usual debounce. A watcher
+    /// started while paused also defers its initial scan until resumed.
+    pub fn set_paused(&self, paused: bool) {
+        self.params.paused.store(paused, Ordering::SeqCst);
+        info!("Auto-backups {}", if paused { "paused" } else { "resumed" });
+    }
+
+    /// Sets how long save changes are ignored after a restore finishes.
+    pub fn set_restore_suppression_seconds(&self, seconds: u64) {
+        self.params
+            .restore_suppression_seconds
+            .store(seconds, Ordering::Relaxed);
+    }
+
+    /// Marks a restore as started so the debounce loop ignores the files it writes.
+    pub fn begin_restore(&self) {
+        self.params
+            .restore_in_progress
+            .store(true, Ordering::SeqCst);
+    }
+
+    /// Marks the restore as finished, starting the post-restore suppression window.
+    pub fn end_restore(&self) {
+        if let Ok(mut guard) = self.params.restore_finished_at.lock() {
+            *guard = Some(Instant::now());
+        }
+        self.params
+            .restore_in_progress
+            .store(false, Ordering::SeqCst);
+    }
+
+    /// Updates the debounce period and backup limit, including for a running loop.
+    ///
+    /// The new values apply from the loop's next iteration without restarting the watcher.
+    pub fn set_params(&self, params: WatcherParams) {
+        self.params
+            .debounce_seconds
+            .store(params.debounce_seconds, Ordering::Relaxed);
+        self.params.limit.store(params.limit, Ordering::Relaxed);
+    }
+
+    /// Returns the debounce period and backup limit currently in effect.
+    pub fn params(&self) -> WatcherParams {
+        WatcherParams {
+            debounce_seconds: self.params.debounce_seconds.load(Ordering::Relaxed),
+            limit: self.params.limit(),
+        }
+    }
+
+    /// Returns whether a watch is currently running.
+    pub fn is_active(&self) -> bool {
+        self.watcher
+            .lock()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Returns how many file system events the debounce loop has received.
+    pub fn events_received(&self) -> u64 {
+        self.params.events_received.load(Ordering::Relaxed)
+    }
+
+    /// Returns when the watcher last created a backup, if it has since the app started.
+    pub fn last_backup_time(&self) -> Option<SystemTime> {
+        self.params.last_backup.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// Returns the PID of another instance that owned the watched directory at the last start.
+    pub fn owner_conflict(&self) -> Option<u32> {
+        self.owner_conflict.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// Claims the `.backups` owner marker for the watched directory, recording any conflict.
+    fn claim_owner(&self, watch_target: &Path) {
+        let conflict = match claim_backup_dir(watch_target, &self.params.backup_settings()) {
+            Ok(Some(pid)) => {
+                log::warn!(
+                    "Backup directory {:?} appears to be owned by another instance (PID {})",
+                    watch_target,
+                    pid
+                );
+                Some(pid)
+            }
+            Ok(None) => {
+                if let Ok(mut guard) = self.owned_dir.lock() {
+                    *guard = Some(watch_target.to_path_buf());
+                }
+                None
+            }
+            Err(e) => {
+                error!("Failed to write backup directory owner marker: {}", e);
+                None
+            }
+        };
+        if let Ok(mut guard) = self.owner_conflict.lock() {
+            *guard = conflict;
         }
     }
 
     /// Starts watching the specified path.
     ///
     /// * `on_backup` - Optional callback invoked when one or more backups are successfully created.
+    /// * `on_path_missing` - Optional callback invoked when the watched directory disappears,
+    ///   after which the watcher stops until it is started again.
     pub fn start(
         &self,
         path: PathBuf,
         limit: usize,
         on_backup: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+        on_path_missing: Option<PathMissingCallback>,
     ) -> Result<(), String> {
         self.stop();
 
@@ -58,10 +475,17 @@ impl FileWatcher {
             return Err(format!("Watch target does not exist: {:?}", watch_target));
         }
 
-        if let Err(e) = watcher.watch(&watch_target, RecursiveMode::NonRecursive) {
+        let mode = if self.params.watch_recursive.load(Ordering::Relaxed) {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        if let Err(e) = watcher.watch(&watch_target, mode) {
             return Err(format!("Failed to watch path: {}", e));
         }
 
+        self.claim_owner(&watch_target);
+
         let mut watcher_guard = self
             .watcher
             .lock()
@@ -77,8 +501,17 @@ impl FileWatcher {
             *shutdown_guard = shutdown_token.clone();
         }
 
+        self.params.limit.store(limit, Ordering::Relaxed);
+        let params = self.params.clone();
         let handle = thread::spawn(move || {
-            debounce_loop(rx, watch_target, shutdown_token, limit, on_backup);
+            debounce_loop(
+                rx,
+                watch_target,
+                shutdown_token,
+                params,
+                on_backup,
+                on_path_missing,
+            );
         });
 
         match self.thread_handle.lock() {
@@ -124,6 +557,15 @@ impl FileWatcher {
             }
             Err(e) => error!("Failed to lock thread handle during stop: {}", e),
         }
+
+        if let Some(dir) = self
+            .owned_dir
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take())
+        {
+            release_backup_dir(&dir, &self.params.backup_settings());
+        }
     }
 
     /// Returns the current shutdown token for test assertions.
@@ -144,29 +586,48 @@ impl FileWatcher {
 
 /// Executes backups for a set of games with a shared index load and save.
 ///
-/// Returns `true` if at least one backup was successfully created.
-fn perform_batch_backups(save_dir: &Path, game_numbers: &HashSet<u32>, limit: usize) -> bool {
-    if game_numbers.is_empty() {
-        return false;
+/// Games in `disabled_games` are skipped. Afterwards `game_numbers` holds only the games
+/// whose save was still being written, so the caller can retry them.
+/// Returns how many backups were created or skipped; failures are logged and not counted.
+fn perform_batch_backups(
+    save_dir: &Path,
+    game_numbers: &mut HashSet<u32>,
+    limit: usize,
+    disabled_games: &HashSet<u32>,
+    settings: &BackupSettings,
+) -> WatcherActivity {
+    let mut activity = WatcherActivity::default();
+    let to_back_up: HashSet<u32> = game_numbers.difference(disabled_games).copied().collect();
+    game_numbers.clear();
+    if to_back_up.is_empty() {
+        return activity;
     }
 
-    let mut backups_created = false;
-    if let Ok(backup_root) = ensure_backup_root(save_dir) {
+    if let Ok(backup_root) = ensure_backup_root(save_dir, settings) {
         let mut index = load_index(&backup_root);
 
-        for &game_number in game_numbers {
-            let backups =
-                crate::backup::get_backups(save_dir, true, Some(game_number)).unwrap_or_default();
+        for game_number in to_back_up {
+            let backups = crate::backup::get_backups(save_dir, true, Some(game_number), settings)
+                .unwrap_or_default();
             match perform_backup_for_game_internal(
                 save_dir,
                 &backup_root,
                 game_number,
                 &mut index,
-                limit,
                 &backups,
+                settings,
+                BackupOptions {
+                    limit,
+                    wait_until_settled: true,
+                    ..BackupOptions::default()
+                },
             ) {
-                Ok(Some(_)) => backups_created = true,
-                Ok(None) => {}
+                Ok(outcome) => {
+                    if outcome == BackupOutcome::SkippedUnstable {
+                        game_numbers.insert(game_number);
+                    }
+                    activity.record(&outcome);
+                }
                 Err(e) => error!("Backup failed for game {}: {}", game_number, e),
             }
         }
@@ -174,96 +635,369 @@ fn perform_batch_backups(save_dir: &Path, game_numbers: &HashSet<u32>, limit: us
             error!("Failed to persist backup index: {}", e);
         }
     }
-    backups_created
+    activity
+}
+
+/// Backs up the pending games of every directory in `pending`, except disabled slots.
+///
+/// Afterwards `pending` holds only the games whose save was still being written.
+/// Returns the combined counts of all directories.
+fn perform_pending_backups(
+    pending: &mut PendingSaves,
+    limit: usize,
+    disabled_games: &HashSet<u32>,
+    settings: &BackupSettings,
+) -> WatcherActivity {
+    let mut activity = WatcherActivity::default();
+    for (save_dir, game_numbers) in pending.iter_mut() {
+        activity += perform_batch_backups(save_dir, game_numbers, limit, disabled_games, settings);
+    }
+    pending.retain(|_, game_numbers| !game_numbers.is_empty());
+    activity
 }
 
 /// Performs an immediate scan of the directory and backs up any existing save files.
 ///
-/// Returns `true` if at least one backup was successfully created during the scan.
-pub(crate) fn scan_and_backup_existing(save_dir: &Path, limit: usize) -> bool {
-    info!("Performing initial scan of {:?}", save_dir);
-    if let Ok(entries) = std::fs::read_dir(save_dir) {
-        let mut pending_games = HashSet::new();
-        for entry in entries.flatten() {
-            if let Some(name) = entry.file_name().to_str() {
-                if !name.starts_with("gamesave_") {
-                    continue;
-                }
-            }
+/// With `recursive` set, every subdirectory (except backup folders) is scanned as its
+/// own save directory. In `IndexOnly` mode the saves are only recorded in the dedup index.
+/// Slots in `disabled_games` are never backed up.
+/// Returns how many backups the scan created or skipped.
+pub(crate) fn scan_and_backup_existing(
+    save_dir: &Path,
+    limit: usize,
+    mode: InitialScanMode,
+    recursive: bool,
+    disabled_games: &HashSet<u32>,
+    settings: &BackupSettings,
+) -> WatcherActivity {
+    info!("Performing initial scan of {:?} ({:?})", save_dir, mode);
+    let dirs = if recursive {
+        nested_save_dirs(save_dir)
+    } else {
+        vec![save_dir.to_path_buf()]
+    };
+
+    let mut activity = WatcherActivity::default();
+    for dir in dirs {
+        let Ok(slots) = discover_save_slots(&dir) else {
+            continue;
+        };
+        let mut pending_games: HashSet<u32> = slots.into_iter().collect();
+        if mode == InitialScanMode::IndexOnly {
+            index_existing_saves(&dir, &pending_games, settings);
+        } else {
+            activity +=
+                perform_batch_backups(&dir, &mut pending_games, limit, disabled_games, settings);
+        }
+    }
+    activity
+}
 
-            if let Some(info) = filename_utils::parse_path(&entry.path()) {
-                if !info.is_bak {
-                    pending_games.insert(info.game_number);
+/// Lists `root` and all directories below it, skipping backup directories.
+///
+/// Symlinked directories are not followed, so link cycles cannot loop the walk.
+fn nested_save_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+                if is_dir && entry.file_name() != BACKUP_DIR_NAME {
+                    stack.push(entry.path());
                 }
             }
         }
-        return perform_batch_backups(save_dir, &pending_games, limit);
+        dirs.push(dir);
     }
-    false
+    dirs
+}
+
+/// Records the existing saves of `game_numbers` in the index without backing them up.
+fn index_existing_saves(save_dir: &Path, game_numbers: &HashSet<u32>, settings: &BackupSettings) {
+    if game_numbers.is_empty() {
+        return;
+    }
+    if let Ok(backup_root) = ensure_backup_root(save_dir, settings) {
+        let mut index = load_index(&backup_root);
+        match index_live_saves(save_dir, &backup_root, game_numbers, &mut index, settings) {
+            Ok(indexed) => info!("Indexed {} existing save(s) without backing up", indexed),
+            Err(e) => error!("Failed to index existing saves: {}", e),
+        }
+        if let Err(e) = save_index(&backup_root, &index) {
+            error!("Failed to persist backup index: {}", e);
+        }
+    }
+}
+
+/// Bounds how often the debounce loop wakes to handle filesystem events.
+///
+/// Counts handled events in one-second windows. Once a window's budget is spent,
+/// the loop pauses for the rest of the window and then coalesces everything that
+/// queued up meanwhile in a single pass.
+struct EventRateLimiter {
+    max_per_second: u32,
+    window_start: Instant,
+    handled: u32,
+}
+
+impl EventRateLimiter {
+    /// Creates a limiter allowing `max_per_second` wakes per window (`0` = unlimited).
+    fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            window_start: Instant::now(),
+            handled: 0,
+        }
+    }
+
+    /// Records a handled event and returns how long to pause if the budget is exceeded.
+    fn throttle(&mut self, now: Instant) -> Option<Duration> {
+        if self.max_per_second == 0 {
+            return None;
+        }
+
+        let window = Duration::from_secs(1);
+        if now.saturating_duration_since(self.window_start) >= window {
+            self.window_start = now;
+            self.handled = 0;
+        }
+
+        self.handled += 1;
+        if self.handled <= self.max_per_second {
+            return None;
+        }
+
+        let pause = window.saturating_sub(now.saturating_duration_since(self.window_start));
+        self.window_start = now + pause;
+        self.handled = 0;
+        Some(pause)
+    }
+}
+
+/// Adds the game slots touched by an event to the pending set under their directory.
+///
+/// Files inside a `.backups` directory are ignored; recursive watches see them too.
+/// Returns `true` if the event referenced at least one main save file.
+fn collect_event_games(event: &notify::Event, pending_games: &mut PendingSaves) -> bool {
+    let mut relevant_event = false;
+    for path in &event.paths {
+        if path.components().any(|c| c.as_os_str() == BACKUP_DIR_NAME) {
+            continue;
+        }
+        let (Some(info), Some(dir)) = (filename_utils::parse_path(path), path.parent()) else {
+            continue;
+        };
+        if !info.is_bak {
+            pending_games
+                .entry(dir.to_path_buf())
+                .or_default()
+                .insert(info.game_number);
+            relevant_event = true;
+        }
+    }
+    relevant_event
+}
+
+/// Drains all queued events without blocking, coalescing them into the pending set.
+///
+/// Returns `true` if any drained event referenced a main save file.
+fn drain_queued_events(
+    rx: &Receiver<notify::Result<notify::Event>>,
+    pending_games: &mut PendingSaves,
+) -> bool {
+    let mut relevant_event = false;
+    while let Ok(result) = rx.try_recv() {
+        match result {
+            Ok(event) => relevant_event |= collect_event_games(&event, pending_games),
+            Err(e) => error!("Watch error: {:?}", e),
+        }
+    }
+    relevant_event
+}
+
+/// Runs the backups a debounce loop owes at startup: handed-over changes, then the initial scan.
+///
+/// Handed-over games go first so an index-only scan cannot record them as already seen.
+fn run_startup_backups(
+    save_dir: &Path,
+    params: &LiveParams,
+    on_backup: Option<&Arc<dyn Fn() + Send + Sync + 'static>>,
+) {
+    let mut activity = WatcherActivity::default();
+    let mut handed_over = params.take_handed_over(save_dir);
+    if !handed_over.is_empty() {
+        info!(
+            "Backing up {} game(s) left pending by the previous watcher.",
+            pending_count(&handed_over)
+        );
+        activity += perform_pending_backups(
+            &mut handed_over,
+            params.limit(),
+            &params.disabled_games(),
+            &params.backup_settings(),
+        );
+    }
+
+    // Initial Scan: Check for existing saves that need backup
+    activity += scan_and_backup_existing(
+        save_dir,
+        params.limit(),
+        params.initial_scan_mode(),
+        params.watch_recursive.load(Ordering::Relaxed),
+        &params.disabled_games(),
+        &params.backup_settings(),
+    );
+    params.report_activity(activity, on_backup);
+}
+
+/// Scans the save directory on the periodic backup timer.
+///
+/// Catches changes on storage that does not report file events; dedup skips slots
+/// that have not changed since their last backup.
+fn run_periodic_scan(
+    save_dir: &Path,
+    params: &LiveParams,
+    on_backup: Option<&Arc<dyn Fn() + Send + Sync + 'static>>,
+) {
+    info!("Periodic backup interval elapsed. Scanning {:?}.", save_dir);
+    let activity = scan_and_backup_existing(
+        save_dir,
+        params.limit(),
+        InitialScanMode::BackupAll,
+        params.watch_recursive.load(Ordering::Relaxed),
+        &params.disabled_games(),
+        &params.backup_settings(),
+    );
+    params.report_activity(activity, on_backup);
+}
+
+/// Counts the game slots pending across all directories.
+fn pending_count(pending: &PendingSaves) -> usize {
+    pending.values().map(HashSet::len).sum()
 }
 
 /// Runs the debounce loop to process file system events.
+///
+/// Games still pending when the loop stops are handed to the next loop on the same
+/// directory, which backs them up before its initial scan so a restart (e.g. after a
+/// settings change) cannot drop a save change or index it away. While paused, events
+/// keep accumulating and startup backups wait until the watcher is resumed. With a
+/// periodic interval set, the directory is also rescanned whenever it elapses.
+///
+/// The loop also stops when the directory disappears (deleted, or its drive unmounted),
+/// since the OS watch goes quiet then; `on_path_missing` is told once the loop has ended.
 fn debounce_loop(
     rx: Receiver<notify::Result<notify::Event>>,
     save_dir: PathBuf,
     shutdown: Arc<AtomicBool>,
-    limit: usize,
+    params: LiveParams,
     on_backup: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    on_path_missing: Option<PathMissingCallback>,
 ) {
-    // Initial Scan: Check for existing saves that need backup
-    if scan_and_backup_existing(&save_dir, limit) {
-        if let Some(cb) = &on_backup {
-            cb();
-        }
+    let mut startup_due = true;
+    if !params.paused() {
+        run_startup_backups(&save_dir, &params, on_backup.as_ref());
+        startup_due = false;
     }
 
-    let mut pending_games: HashSet<u32> = HashSet::new();
-    let mut last_change_time = std::time::Instant::now();
+    let mut pending_games = PendingSaves::new();
+    let mut last_change_time = Instant::now();
     let mut pending_change = false;
+    let mut last_periodic_scan = Instant::now();
+    let mut rate_limiter =
+        EventRateLimiter::new(params.max_events_per_second.load(Ordering::Relaxed));
 
+    let mut path_missing = false;
     loop {
         if shutdown.load(Ordering::SeqCst) {
             break;
         }
+        if !save_dir.exists() {
+            error!(
+                "Watched directory {:?} disappeared; stopping the watcher.",
+                save_dir
+            );
+            path_missing = true;
+            break;
+        }
+        let paused = params.paused();
+        if startup_due && !paused {
+            run_startup_backups(&save_dir, &params, on_backup.as_ref());
+            startup_due = false;
+            last_periodic_scan = Instant::now();
+        }
+        let periodic_interval = params.periodic_interval();
+        if !paused
+            && !startup_due
+            && periodic_interval.is_some_and(|interval| last_periodic_scan.elapsed() >= interval)
+        {
+            run_periodic_scan(&save_dir, &params, on_backup.as_ref());
+            last_periodic_scan = Instant::now();
+        }
+        // Wake up for the next periodic scan, or after the usual idle wait.
+        let idle_timeout = periodic_interval.map_or(IDLE_TIMEOUT, |interval| {
+            interval
+                .saturating_sub(last_periodic_scan.elapsed())
+                .clamp(PAUSED_POLL_INTERVAL, IDLE_TIMEOUT)
+        });
+        rate_limiter.max_per_second = params.max_events_per_second.load(Ordering::Relaxed);
+        let debounce = params.debounce();
 
         // Calculate timeout
-        let timeout = if pending_change {
+        let timeout = if paused && (pending_change || startup_due) {
+            // Poll for resume; queued changes stay pending until then.
+            PAUSED_POLL_INTERVAL
+        } else if pending_change {
             let elapsed = last_change_time.elapsed();
-            if elapsed >= DEBOUNCE_DURATION {
+            if elapsed >= debounce && params.restore_suppressed() {
+                info!("Discarding save changes made by a restore.");
+                pending_games.clear();
+                pending_change = false;
+                idle_timeout
+            } else if elapsed >= debounce {
                 info!(
                     "Debounce timeout. Backing up {} games.",
-                    pending_games.len()
+                    pending_count(&pending_games)
                 );
-                if perform_batch_backups(&save_dir, &pending_games, limit) {
-                    if let Some(cb) = &on_backup {
-                        cb();
-                    }
+                let activity = perform_pending_backups(
+                    &mut pending_games,
+                    params.limit(),
+                    &params.disabled_games(),
+                    &params.backup_settings(),
+                );
+                params.report_activity(activity, on_backup.as_ref());
+                // Saves still being written are retried after another debounce period.
+                pending_change = !pending_games.is_empty();
+                last_change_time = Instant::now();
+                if pending_change {
+                    debounce
+                } else {
+                    idle_timeout
                 }
-                pending_games.clear();
-                pending_change = false;
-                Duration::from_secs(60)
             } else {
-                DEBOUNCE_DURATION - elapsed
+                debounce - elapsed
             }
         } else {
-            Duration::from_secs(60)
+            idle_timeout
         };
 
         match rx.recv_timeout(timeout) {
             Ok(Ok(event)) => {
-                let mut relevant_event = false;
-                for path in event.paths {
-                    if let Some(info) = filename_utils::parse_path(&path) {
-                        if !info.is_bak {
-                            pending_games.insert(info.game_number);
-                            relevant_event = true;
-                        }
-                    }
+                params.events_received.fetch_add(1, Ordering::Relaxed);
+                let mut relevant_event = collect_event_games(&event, &mut pending_games);
+
+                if let Some(pause) = rate_limiter.throttle(Instant::now()) {
+                    // Event flood: sleep out the window, then coalesce the backlog at once.
+                    thread::sleep(pause);
+                    relevant_event |= drain_queued_events(&rx, &mut pending_games);
                 }
 
-                if relevant_event {
+                if relevant_event && params.restore_suppressed() {
+                    pending_games.clear();
+                } else if relevant_event {
                     pending_change = true;
-                    last_change_time = std::time::Instant::now();
+                    last_change_time = Instant::now();
                 }
             }
             Ok(Err(e)) => error!("Watch error: {:?}", e),
@@ -271,6 +1005,15 @@ fn debounce_loop(
             Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         }
     }
+
+    if pending_change && !pending_games.is_empty() {
+        params.hand_over_pending(&save_dir, pending_games);
+    }
+    if path_missing {
+        if let Some(cb) = on_path_missing {
+            cb(save_dir);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -284,7 +1027,9 @@ mod tests {
     fn test_file_watcher_lifecycle() {
         let watcher = FileWatcher::new();
         let dir = tempdir().unwrap();
-        assert!(watcher.start(dir.path().to_path_buf(), 100, None).is_ok());
+        assert!(watcher
+            .start(dir.path().to_path_buf(), 100, None, None)
+            .is_ok());
         watcher.stop();
     }
 
@@ -294,7 +1039,9 @@ mod tests {
         let watcher = FileWatcher::new();
         let dir = tempdir().unwrap();
 
-        assert!(watcher.start(dir.path().to_path_buf(), 100, None).is_ok());
+        assert!(watcher
+            .start(dir.path().to_path_buf(), 100, None, None)
+            .is_ok());
         let shutdown_token = watcher.debug_shutdown_token();
 
         watcher.stop();
@@ -303,7 +1050,9 @@ mod tests {
             "Shutdown token should be true after stop"
         );
 
-        assert!(watcher.start(dir.path().to_path_buf(), 100, None).is_ok());
+        assert!(watcher
+            .start(dir.path().to_path_buf(), 100, None, None)
+            .is_ok());
         assert!(
             shutdown_token.load(Ordering::SeqCst),
             "Shutdown token from the previous thread should remain true after restart"
@@ -361,14 +1110,22 @@ mod tests {
         std::fs::write(&save1, "data1").unwrap();
         std::fs::write(&save2, "data2").unwrap();
 
-        scan_and_backup_existing(&save_dir, 100);
+        scan_and_backup_existing(
+            &save_dir,
+            100,
+            InitialScanMode::BackupAll,
+            false,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
 
         let backups_dir = save_dir.join(".backups");
         assert!(backups_dir.exists());
         let index_path = backups_dir.join("index.json");
         assert!(index_path.exists());
 
-        let backups = crate::backup::get_backups(&save_dir, true, None).unwrap();
+        let backups =
+            crate::backup::get_backups(&save_dir, true, None, &BackupSettings::default()).unwrap();
         assert_eq!(backups.len(), 2);
 
         let games: std::collections::HashSet<u32> = backups.iter().map(|b| b.game_number).collect();
@@ -387,7 +1144,7 @@ mod tests {
         let watcher = FileWatcher::new();
         let start_time = std::time::Instant::now();
 
-        watcher.start(save_dir.clone(), 100, None).unwrap();
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
 
         let mut found = false;
         for _ in 0..15 {
@@ -408,4 +1165,514 @@ mod tests {
             elapsed
         );
     }
+
+    /// Verifies the rate limiter caps handled events within a single window.
+    #[test]
+    fn test_rate_limiter_bounds_event_flood() {
+        let mut limiter = EventRateLimiter::new(20);
+        let now = Instant::now();
+
+        let handled = (0..10_000)
+            .take_while(|_| limiter.throttle(now).is_none())
+            .count();
+        assert_eq!(handled, 20, "only the budget should pass before throttling");
+
+        let mut unlimited = EventRateLimiter::new(0);
+        assert!((0..10_000).all(|_| unlimited.throttle(now).is_none()));
+    }
+
+    /// Verifies that a queued flood of events is coalesced into the pending slot set.
+    #[test]
+    fn test_drain_queued_events_coalesces_flood() {
+        let (tx, rx) = channel();
+        for i in 0..5_000 {
+            let slot = if i == 4_999 { 2 } else { 1 };
+            let event = notify::Event::new(notify::EventKind::Any)
+                .add_path(PathBuf::from(format!("gamesave_{}.sav", slot)));
+            tx.send(Ok(event)).unwrap();
+        }
+
+        let mut pending = PendingSaves::new();
+        assert!(drain_queued_events(&rx, &mut pending));
+        assert_eq!(
+            pending,
+            PendingSaves::from([(PathBuf::new(), HashSet::from([1, 2]))])
+        );
+        assert!(
+            rx.try_recv().is_err(),
+            "the backlog should be fully drained"
+        );
+    }
+
+    /// Verifies that live parameter updates reach a running debounce loop.
+    #[test]
+    fn test_set_params_applies_to_running_watcher() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_1.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&save_dir, false, Some(1), &BackupSettings::default())
+                .unwrap()
+                .len()
+        };
+        for _ in 0..20 {
+            if backup_count() == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        assert_eq!(backup_count(), 1, "initial scan should back up the save");
+
+        let params = WatcherParams {
+            debounce_seconds: 0,
+            limit: 5,
+        };
+        watcher.set_params(params);
+        assert_eq!(watcher.params(), params);
+
+        // With the default 2s debounce this change could not be backed up within 1.5s.
+        thread::sleep(Duration::from_millis(1100));
+        std::fs::write(&save, "v2").unwrap();
+        let started = Instant::now();
+        while backup_count() < 2 && started.elapsed() < Duration::from_millis(1500) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(
+            backup_count(),
+            2,
+            "zero debounce should back up the change immediately"
+        );
+    }
+
+    /// Verifies that changes written during and just after a restore are not backed up.
+    #[test]
+    fn test_restore_suppresses_watcher_backups() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_1.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        watcher.set_params(WatcherParams {
+            debounce_seconds: 0,
+            limit: 100,
+        });
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&save_dir, false, Some(1), &BackupSettings::default())
+                .unwrap()
+                .len()
+        };
+        for _ in 0..20 {
+            if backup_count() == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        assert_eq!(backup_count(), 1, "initial scan should back up the save");
+
+        thread::sleep(Duration::from_millis(1100));
+        watcher.begin_restore();
+        std::fs::write(&save, "restored").unwrap();
+        thread::sleep(Duration::from_millis(300));
+        watcher.end_restore();
+        std::fs::write(&save, "restored again").unwrap();
+        thread::sleep(Duration::from_millis(1000));
+        assert_eq!(backup_count(), 1, "restore writes should not be backed up");
+
+        watcher.set_restore_suppression_seconds(0);
+        // Swap the file in atomically so the watcher never sees a truncated save.
+        let staged = save_dir.join("staged.tmp");
+        std::fs::write(&staged, "played after restore").unwrap();
+        std::fs::rename(&staged, &save).unwrap();
+        let started = Instant::now();
+        while backup_count() < 2 && started.elapsed() < Duration::from_millis(1500) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(
+            backup_count(),
+            2,
+            "changes after the window should be backed up"
+        );
+    }
+
+    /// Checks that an index-only initial scan records saves without creating backups.
+    #[test]
+    fn test_initial_scan_index_only_skips_backups() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_1.sav");
+        std::fs::write(&save, "existing").unwrap();
+
+        assert!(scan_and_backup_existing(
+            &save_dir,
+            100,
+            InitialScanMode::IndexOnly,
+            false,
+            &HashSet::new(),
+            &BackupSettings::default()
+        )
+        .is_empty());
+        assert!(
+            crate::backup::get_backups(&save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .is_empty()
+        );
+        let index = load_index(&save_dir.join(".backups"));
+        assert!(
+            index.games.contains_key(&1),
+            "the live save should be indexed"
+        );
+
+        // The unchanged save is already indexed; only a changed save gets backed up.
+        let activity = perform_batch_backups(
+            &save_dir,
+            &mut HashSet::from([1]),
+            100,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
+        assert_eq!(activity.created, 0);
+        assert_eq!(activity.skipped_duplicate, 1);
+        std::fs::write(&save, "changed").unwrap();
+        let activity = perform_batch_backups(
+            &save_dir,
+            &mut HashSet::from([1]),
+            100,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
+        assert_eq!(activity.created, 1);
+        assert_eq!(
+            crate::backup::get_backups(&save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    /// Verifies that a change still pending when the watcher restarts is backed up afterwards.
+    #[test]
+    fn test_pending_change_survives_watcher_restart() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_1.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        // Index-only restarts would otherwise record the change without backing it up.
+        watcher.set_initial_scan_mode(InitialScanMode::IndexOnly);
+        watcher.set_params(WatcherParams {
+            debounce_seconds: 60,
+            limit: 100,
+        });
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        std::fs::write(&save, "v2").unwrap();
+        let started = Instant::now();
+        while watcher.events_received() == 0 && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(watcher.events_received() > 0, "the change should be seen");
+
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&save_dir, false, Some(1), &BackupSettings::default())
+                .unwrap()
+                .len()
+        };
+        let started = Instant::now();
+        while backup_count() == 0 && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(backup_count(), 1, "the pending change should be backed up");
+    }
+
+    /// Verifies that recursive watching backs up saves nested in subdirectories.
+    #[test]
+    fn test_recursive_watch_backs_up_nested_saves() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let profile_dir = save_dir.join("profiles").join("abc");
+        std::fs::create_dir_all(&profile_dir).unwrap();
+        let save = profile_dir.join("gamesave_0.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        watcher.set_watch_recursive(true);
+        watcher.set_params(WatcherParams {
+            debounce_seconds: 0,
+            limit: 100,
+        });
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&profile_dir, false, Some(0), &BackupSettings::default())
+                .unwrap()
+                .len()
+        };
+        for _ in 0..20 {
+            if backup_count() == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        assert_eq!(
+            backup_count(),
+            1,
+            "initial scan should back up the nested save"
+        );
+
+        thread::sleep(Duration::from_millis(1100));
+        let staged = profile_dir.join("staged.tmp");
+        std::fs::write(&staged, "v2").unwrap();
+        std::fs::rename(&staged, &save).unwrap();
+        let started = Instant::now();
+        while backup_count() < 2 && started.elapsed() < Duration::from_secs(3) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(backup_count(), 2, "the nested change should be backed up");
+        assert!(
+            !save_dir.join(".backups").join("index.json").exists(),
+            "nested saves are backed up beside themselves"
+        );
+    }
+
+    /// Verifies that a paused watcher queues changes and backs them up on resume.
+    #[test]
+    fn test_paused_watcher_defers_backups_until_resumed() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_1.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        watcher.set_paused(true);
+        watcher.set_params(WatcherParams {
+            debounce_seconds: 0,
+            limit: 100,
+        });
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&save_dir, false, Some(1), &BackupSettings::default())
+                .map(|b| b.len())
+                .unwrap_or(0)
+        };
+
+        thread::sleep(Duration::from_millis(300));
+        let staged = save_dir.join("staged.tmp");
+        std::fs::write(&staged, "v2").unwrap();
+        std::fs::rename(&staged, &save).unwrap();
+        thread::sleep(Duration::from_millis(1000));
+        assert!(watcher.params.paused());
+        assert_eq!(backup_count(), 0, "no backup should run while paused");
+
+        watcher.set_paused(false);
+        let started = Instant::now();
+        while backup_count() == 0 && started.elapsed() < Duration::from_secs(3) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(backup_count(), 1, "the queued change should be backed up");
+    }
+
+    /// Verifies that the last backup time is recorded and kept across watcher restarts.
+    #[test]
+    fn test_last_backup_time_survives_restart() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        std::fs::write(save_dir.join("gamesave_0.sav"), "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        assert!(watcher.last_backup_time().is_none());
+        let before = SystemTime::now();
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let started = Instant::now();
+        while watcher.last_backup_time().is_none() && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        let recorded = watcher
+            .last_backup_time()
+            .expect("the initial scan backup should be recorded");
+        assert!(recorded >= before);
+
+        // Nothing changed, so the restarted watcher creates no backup of its own.
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        thread::sleep(Duration::from_millis(300));
+        watcher.stop();
+        assert_eq!(watcher.last_backup_time(), Some(recorded));
+    }
+
+    /// Verifies that disabled slots are skipped by the initial scan and batch backups.
+    #[test]
+    fn test_disabled_games_are_never_backed_up() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        std::fs::write(save_dir.join("gamesave_0.sav"), "slot one").unwrap();
+        std::fs::write(save_dir.join("gamesave_1.sav"), "slot two").unwrap();
+        let disabled = HashSet::from([1]);
+
+        let activity = scan_and_backup_existing(
+            &save_dir,
+            100,
+            InitialScanMode::BackupAll,
+            false,
+            &disabled,
+            &BackupSettings::default(),
+        );
+        assert_eq!(activity.created, 1);
+        let backed_up: Vec<u32> =
+            crate::backup::get_backups(&save_dir, false, None, &BackupSettings::default())
+                .unwrap()
+                .iter()
+                .map(|b| b.game_number)
+                .collect();
+        assert_eq!(backed_up, vec![0]);
+
+        assert!(perform_batch_backups(
+            &save_dir,
+            &mut HashSet::from([1]),
+            100,
+            &disabled,
+            &BackupSettings::default()
+        )
+        .is_empty());
+        let activity = perform_batch_backups(
+            &save_dir,
+            &mut HashSet::from([1]),
+            100,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
+        assert_eq!(activity.created, 1);
+    }
+
+    /// Verifies that batch backups count missing saves and duplicates separately.
+    #[test]
+    fn test_batch_backups_count_skipped_slots() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        std::fs::write(save_dir.join("gamesave_0.sav"), "slot one").unwrap();
+        let games = HashSet::from([0, 5]);
+
+        let first = perform_batch_backups(
+            &save_dir,
+            &mut games.clone(),
+            100,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
+        assert_eq!(
+            first,
+            WatcherActivity {
+                created: 1,
+                skipped_duplicate: 0,
+                skipped_no_save: 1,
+                skipped_unstable: 0,
+            }
+        );
+
+        let second = perform_batch_backups(
+            &save_dir,
+            &mut games.clone(),
+            100,
+            &HashSet::new(),
+            &BackupSettings::default(),
+        );
+        assert_eq!(
+            second,
+            WatcherActivity {
+                created: 0,
+                skipped_duplicate: 1,
+                skipped_no_save: 1,
+                skipped_unstable: 0,
+            }
+        );
+    }
+
+    /// Verifies that the periodic timer backs up a change no file event has flushed yet.
+    #[test]
+    fn test_periodic_scan_backs_up_without_debounced_event() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().to_path_buf();
+        let save = save_dir.join("gamesave_0.sav");
+        std::fs::write(&save, "v1").unwrap();
+
+        let watcher = FileWatcher::new();
+        // A long debounce keeps the event path from backing up the change itself.
+        watcher.set_params(WatcherParams {
+            debounce_seconds: 3600,
+            limit: 100,
+        });
+        watcher
+            .params
+            .periodic_backup_seconds
+            .store(1, Ordering::Relaxed);
+        watcher.start(save_dir.clone(), 100, None, None).unwrap();
+        let backup_count = || {
+            crate::backup::get_backups(&save_dir, false, Some(0), &BackupSettings::default())
+                .map(|b| b.len())
+                .unwrap_or(0)
+        };
+
+        let started = Instant::now();
+        while backup_count() == 0 && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert_eq!(
+            backup_count(),
+            1,
+            "the initial scan should back up the save"
+        );
+
+        std::fs::write(&save, "v2").unwrap();
+        let started = Instant::now();
+        while backup_count() == 1 && started.elapsed() < Duration::from_secs(4) {
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop();
+
+        assert_eq!(
+            backup_count(),
+            2,
+            "the periodic scan should back up the change"
+        );
+    }
+
+    /// Verifies that removing the watched directory reports it and stops the loop.
+    #[test]
+    fn test_missing_save_dir_is_reported() {
+        let dir = tempdir().unwrap();
+        let save_dir = dir.path().join("saves");
+        std::fs::create_dir(&save_dir).unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let tx = Mutex::new(tx);
+        let on_path_missing: PathMissingCallback = Arc::new(move |path| {
+            let _ = tx.lock().unwrap().send(path);
+        });
+        let watcher = FileWatcher::new();
+        watcher
+            .start(save_dir.clone(), 100, None, Some(on_path_missing))
+            .unwrap();
+
+        std::fs::remove_dir_all(&save_dir).unwrap();
+        let reported = rx.recv_timeout(Duration::from_secs(10));
+        watcher.stop();
+
+        assert_eq!(reported.unwrap(), save_dir);
+    }
 }
@@ -14,14 +14,13 @@ pub fn show_main_window<R: Runtime>(app: &AppHandle<R>, _from_second_instance: b
 
         #[cfg(target_os = "linux")]
         {
-            use tauri_plugin_notification::NotificationExt;
             if _from_second_instance {
-                let _ = app
-                    .notification()
-                    .builder()
-                    .title("Already Running")
-                    .body("ITD ODD Save Manager is already active.")
-                    .show();
+                crate::notifications::notify(
+                    app,
+                    crate::notifications::NotificationLevel::Important,
+                    "Already Running",
+                    "ITD ODD Save Manager is already active.",
+                );
             }
         }
     }
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::ffi::{OsStr, OsString};
 use std::process::Command;
 
@@ -43,6 +44,33 @@ pub struct WrapperLaunchPlan {
     pub args: Vec<OsString>,
 }
 
+/// How the manager was started, recorded once at startup for the UI.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct LaunchMode {
+    /// True when the manager started in wrapper mode and spawned the game.
+    pub wrapper_mode: bool,
+    /// The game executable launched in wrapper mode, if any.
+    pub game_program: Option<String>,
+}
+
+impl LaunchMode {
+    /// Records the launch mode from a parsed wrapper plan and whether spawning it succeeded.
+    ///
+    /// A detected plan whose launch failed is reported as a normal startup.
+    pub fn from_wrapper_plan(plan: Option<&WrapperLaunchPlan>, launched: bool) -> Self {
+        match plan {
+            Some(plan) if launched => Self {
+                wrapper_mode: true,
+                game_program: Some(plan.program.to_string_lossy().to_string()),
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Managed state holding the launch mode detected at startup.
+pub struct LaunchModeState(pub LaunchMode);
+
 /// Validates if the given path looks like an executable.
 ///
 /// On Windows, checks if the path ends with .exe (case-insensitive).
@@ -119,30 +147,31 @@ pub fn launch_wrapper_plan(plan: &WrapperLaunchPlan) -> Result<(), String> {
 
 /// Checks command-line arguments and launches game if in wrapper mode.
 ///
-/// Returns true if wrapper mode was detected and game was launched.
-/// Returns false if not in wrapper mode (normal manager startup).
+/// Returns a `LaunchMode` with `wrapper_mode` set if wrapper mode was detected and
+/// the game was launched, or the default (normal manager startup) otherwise.
 ///
 /// Note: Uses debug_output for logging since the Tauri logger plugin is not yet initialized.
 /// In Windows release builds, output is sent to OutputDebugString (viewable via DebugView).
-pub fn maybe_launch_from_wrapper_args() -> bool {
+pub fn maybe_launch_from_wrapper_args() -> LaunchMode {
     let args: Vec<OsString> = std::env::args_os().collect();
 
     if let Some(plan) = parse_wrapper_args_os(args) {
         debug_output("Wrapper mode detected");
 
-        match launch_wrapper_plan(&plan) {
+        let launched = match launch_wrapper_plan(&plan) {
             Ok(()) => {
                 debug_output("Wrapper launch completed successfully");
-                return true;
+                true
             }
             Err(e) => {
                 debug_output(&format!("Wrapper launch failed: {}", e));
-                return false;
+                false
             }
-        }
+        };
+        return LaunchMode::from_wrapper_plan(Some(&plan), launched);
     }
 
-    false
+    LaunchMode::default()
 }
 
 #[cfg(test)]
@@ -237,4 +266,38 @@ mod tests {
         let result = parse_wrapper_args_os(args);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn launch_mode_records_launched_wrapper_plan() {
+        let plan = WrapperLaunchPlan {
+            program: OsString::from("C:\\Game.exe"),
+            args: vec![OsString::from("-windowed")],
+        };
+
+        let mode = LaunchMode::from_wrapper_plan(Some(&plan), true);
+        assert_eq!(
+            mode,
+            LaunchMode {
+                wrapper_mode: true,
+                game_program: Some("C:\\Game.exe".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn launch_mode_is_normal_when_wrapper_launch_failed_or_absent() {
+        let plan = WrapperLaunchPlan {
+            program: OsString::from("C:\\Game.exe"),
+            args: vec![],
+        };
+
+        assert_eq!(
+            LaunchMode::from_wrapper_plan(Some(&plan), false),
+            LaunchMode::default()
+        );
+        assert_eq!(
+            LaunchMode::from_wrapper_plan(None, false),
+            LaunchMode::default()
+        );
+    }
 }
@@ -1,7 +1,7 @@
 import { invokeAction, logActivity } from "../ui_utils";
 import { listen } from "@tauri-apps/api/event";
 import type { AppElements } from "./dom";
-import type { BackupInfo } from "./types";
+import type { BackupInfo, PreparedRestore } from "./types";
 import {
   createBackupRow,
   createNoteRow,
@@ -333,7 +333,18 @@ export function createBackupsFeature(elements: BackupsElements): BackupsFeature
    * Restores a selected backup after user confirmation.
    */
   async function restoreBackup(backup: BackupInfo): Promise<void> {
-    const message = buildRestoreConfirmationMessage(backup);
+    const prepared = await invokeAction<PreparedRestore>(
+      "prepare_restore_command",
+      {
+        backup_path: backup.path,
+        target_path: backup.original_path,
+      },
+      "prepare restore",
+      { alertOnError: true },
+    );
+    if (!prepared) return;
+
+    const message = buildRestoreConfirmationMessage(backup, prepared);
     const confirmed = window.confirm(message);
     if (!confirmed) return;
 
@@ -342,6 +353,7 @@ export function createBackupsFeature(elements: BackupsElements): BackupsFeature
       {
         backup_path: backup.path,
         target_path: backup.original_path,
+        confirm_token: prepared.token,
       },
       "restore backup",
       {
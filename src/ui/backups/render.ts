@@ -1,6 +1,6 @@
 import { formatDate } from "../../ui_utils";
 export { formatDate };
-import type { BackupInfo } from "../types";
+import type { BackupInfo, PreparedRestore } from "../types";
 
 /**
  * Returns the display label for a backup entry.
@@ -11,11 +11,24 @@ export function getBackupDisplayName(backup: BackupInfo): string {
 
 /**
  * Builds the restore confirmation message for a backup entry.
+ * When a prepared restore is given, lists the files it will overwrite.
  */
-export function buildRestoreConfirmationMessage(backup: BackupInfo): string {
+export function buildRestoreConfirmationMessage(
+  backup: BackupInfo,
+  prepared?: PreparedRestore,
+): string {
   const gameLabel = getBackupDisplayName(backup);
   let message = `Are you sure you want to restore "${backup.original_filename}" (${gameLabel}) from ${formatDate(backup.modified)}?`;
   message += `\nThis will overwrite the current save files for ${gameLabel}.`;
+  const overwritten =
+    prepared?.plan.files.filter((file) => file.action === "overwrite") ?? [];
+  if (overwritten.length > 0) {
+    const names = overwritten.map((file) => file.file_name).join(", ");
+    message += `\nFiles replaced: ${names}.`;
+  }
+  if (prepared?.plan.live_save_newer) {
+    message += "\nThe current save is newer than this backup.";
+  }
   return message;
 }
 
@@ -124,7 +124,7 @@ describe("config refresh availability", () => {
     const { invoke } = await import("@tauri-apps/api/core");
     vi.mocked(invoke).mockImplementation((command: string) => {
       if (command === "set_save_path") {
-        return Promise.resolve("C:\\Saves");
+        return Promise.resolve({ path: "C:\\Saves", warning: null });
       }
       return Promise.resolve(undefined);
     });
@@ -149,7 +149,7 @@ describe("config refresh availability", () => {
     const { invoke } = await import("@tauri-apps/api/core");
     vi.mocked(invoke).mockImplementation((command: string) => {
       if (command === "set_save_path") {
-        return Promise.resolve("C:\\Saves");
+        return Promise.resolve({ path: "C:\\Saves", warning: null });
       }
       return Promise.resolve(undefined);
     });
@@ -169,6 +169,31 @@ describe("config refresh availability", () => {
     expect(loadBackups).toHaveBeenCalledWith(true);
   });
 
+  it("shows the backend warning when the saved path is a Steam Cloud folder", async () => {
+    const { invoke } = await import("@tauri-apps/api/core");
+    vi.mocked(invoke).mockImplementation((command: string) => {
+      if (command === "set_save_path") {
+        return Promise.resolve({ path: "C:\\Steam\\remote", warning: "Synced by Steam Cloud." });
+      }
+      return Promise.resolve(undefined);
+    });
+
+    const { createConfigFeature } = await import("./config");
+    const elements = createElements();
+    elements.manualInput.value = "C:\\Steam\\remote";
+    const setRefreshAvailability = vi.fn();
+
+    const feature = createConfigFeature(elements, {
+      loadBackups: vi.fn().mockResolvedValue(undefined),
+      setRefreshAvailability,
+    });
+
+    await feature.savePath();
+
+    expect(elements.configStatus.textContent).toBe("Synced by Steam Cloud.");
+    expect(setRefreshAvailability).toHaveBeenLastCalledWith(true);
+  });
+
   it("preserves the attempted path in the input when set_save_path rejects", async () => {
     const { invoke } = await import("@tauri-apps/api/core");
     vi.mocked(invoke).mockRejectedValue(
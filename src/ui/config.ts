@@ -6,7 +6,7 @@ import {
   withBusyButton,
 } from "../ui_utils";
 import type { AppElements } from "./dom";
-import type { AppConfig, StatusType } from "./types";
+import type { AppConfig, SavePathUpdate, StatusType } from "./types";
 
 type ConfigElements = Pick<
   AppElements,
@@ -132,7 +132,7 @@ export function createConfigFeature(
     await withBusyButton(elements.saveButton, "Saving...", async () => {
       setStatus("Saving...", "info");
 
-      const normalizedPath = await safeInvoke<string>(
+      const update = await safeInvoke<SavePathUpdate>(
         "set_save_path",
         { path },
         {
@@ -143,12 +143,17 @@ export function createConfigFeature(
         },
       );
 
-      if (!normalizedPath) return;
+      if (!update) return;
 
-      elements.manualInput.value = normalizedPath;
-      setValidPath(normalizedPath);
-      setStatus("Save path updated successfully.", "success");
-      logActivity(`Save path updated: ${normalizedPath}`);
+      elements.manualInput.value = update.path;
+      setValidPath(update.path);
+      if (update.warning) {
+        setStatus(update.warning, "info");
+        logActivity(`Save path warning: ${update.warning}`);
+      } else {
+        setStatus("Save path updated successfully.", "success");
+      }
+      logActivity(`Save path updated: ${update.path}`);
       void deps.loadBackups(true);
     });
   }
@@ -221,7 +226,7 @@ export function createConfigFeature(
     elements.detectButton.remove();
     elements.pathsList.innerHTML = "";
     const item = document.createElement("li");
-    item.textContent = "Auto-detection is only available on Windows. Enter a path manually.";
+    item.textContent = "Auto-detection is only available on Windows, Linux and macOS. Enter a path manually.";
     item.classList.add("empty");
     elements.pathsList.appendChild(item);
   }
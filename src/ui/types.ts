@@ -3,10 +3,14 @@ export interface AppConfig {
   auto_launch_game: boolean;
   auto_close: boolean;
   max_backups_per_game: number;
+  unlimited_backups?: boolean;
+  max_backup_age_days?: number | null;
+  max_total_backup_bytes?: number | null;
 }
 
 export interface BackupInfo {
   path: string;
+  id?: string | null;
   filename: string;
   original_filename: string;
   original_path: string;
@@ -14,8 +18,43 @@ export interface BackupInfo {
   modified: string;
   game_number: number;
   locked: boolean;
+  archived?: boolean;
   hash: string;
+  hash_algorithm?: string;
   note?: string | null;
+  tags?: string[];
+  favorite?: boolean;
+  metadata?: SaveMetadata | null;
+}
+
+export interface SaveMetadata {
+  raw_size: number;
+  encoding: "json" | "gzip_json" | "unknown";
+  day: number | null;
+  difficulty: string | null;
+  survivors: string[];
+}
+
+export interface RestoreFilePlan {
+  file_name: string;
+  size: number;
+  action: "create" | "overwrite" | "unchanged";
+}
+
+export interface PreparedRestore {
+  token: string;
+  expires_in_seconds: number;
+  plan: {
+    game_number: number;
+    files: RestoreFilePlan[];
+    safety_backup_pending: boolean;
+    live_save_newer: boolean;
+  };
+}
+
+export interface SavePathUpdate {
+  path: string;
+  warning: string | null;
 }
 
 export type StatusType = "info" | "success" | "error";